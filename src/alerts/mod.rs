@@ -0,0 +1,329 @@
+// src/alerts/mod.rs
+//! Threshold alerts evaluated against successive snapshots.
+//!
+//! Each configured [`AlertRule`] moves through a small state machine: while
+//! its condition holds it is pending, once it has held for the rule's
+//! duration it fires, and it resolves when the value has moved back past
+//! the threshold by the rule's hysteresis. Firing and resolving are
+//! reported as [`AlertEvent`]s.
+
+pub mod notify;
+pub mod rule;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::monitor::SystemSnapshot;
+pub use notify::Notifier;
+pub use rule::RuleExpr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    #[default]
+    Warning,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        })
+    }
+}
+
+/// One `[[alerts.rules]]` entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Shown in place of the expression when set
+    pub name: Option<String>,
+    /// Condition, e.g. `cpu.global > 90 for 2m`
+    pub expr: RuleExpr,
+    #[serde(default)]
+    pub severity: Severity,
+    /// How far the value has to move back past the threshold before a
+    /// firing alert resolves, in the unit of the value
+    #[serde(default)]
+    pub hysteresis: f64,
+    /// Names of the sinks to notify; all sinks when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<Vec<String>>,
+}
+
+impl AlertRule {
+    pub fn new(expr: RuleExpr) -> Self {
+        Self { name: None, expr, severity: Severity::default(), hysteresis: 0.0, notify: None }
+    }
+
+    pub fn display_name(&self) -> String {
+        self.name.clone().unwrap_or_else(|| self.expr.to_string())
+    }
+}
+
+/// Where a rule is in its life cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertState {
+    Inactive,
+    /// The condition holds, but not yet for the rule's duration
+    Pending { since: DateTime<Utc> },
+    Firing { since: DateTime<Utc> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertEventKind {
+    Firing,
+    Resolved,
+}
+
+/// A rule that started firing or resolved
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AlertEvent {
+    pub rule: String,
+    pub expr: String,
+    pub severity: Severity,
+    pub kind: AlertEventKind,
+    /// Value that caused the transition
+    pub value: f64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl fmt::Display for AlertEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self.kind {
+            AlertEventKind::Firing => format!("{} {} firing: {} (value {:.2})", self.severity, self.rule, self.expr, self.value),
+            AlertEventKind::Resolved => format!("{} resolved (value {:.2})", self.rule, self.value),
+        };
+        f.pad(&text)
+    }
+}
+
+/// A rule with its current state
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub rule: AlertRule,
+    pub state: AlertState,
+    /// Last evaluated value; `None` while the target has not been collected
+    pub value: Option<f64>,
+}
+
+impl Alert {
+    pub fn is_firing(&self) -> bool {
+        matches!(self.state, AlertState::Firing { .. })
+    }
+
+    fn event(&self, kind: AlertEventKind, value: f64, timestamp: DateTime<Utc>) -> AlertEvent {
+        AlertEvent {
+            rule: self.rule.display_name(),
+            expr: self.rule.expr.to_string(),
+            severity: self.rule.severity,
+            kind,
+            value,
+            timestamp,
+        }
+    }
+
+    /// Advance the state machine with the value observed at `now`
+    fn update(&mut self, value: Option<f64>, now: DateTime<Utc>) -> Option<AlertEvent> {
+        self.value = value;
+        // A target that is not collected (yet) keeps the current state
+        let value = value?;
+        let expr = &self.rule.expr;
+        let duration = chrono::Duration::from_std(expr.duration).unwrap_or(chrono::Duration::MAX);
+
+        match self.state {
+            AlertState::Firing { .. } if expr.still_holds(value, self.rule.hysteresis) => None,
+            AlertState::Firing { .. } => {
+                self.state = AlertState::Inactive;
+                Some(self.event(AlertEventKind::Resolved, value, now))
+            }
+            _ if !expr.holds(value) => {
+                self.state = AlertState::Inactive;
+                None
+            }
+            AlertState::Inactive if !duration.is_zero() => {
+                self.state = AlertState::Pending { since: now };
+                None
+            }
+            AlertState::Pending { since } if now.signed_duration_since(since) < duration => None,
+            AlertState::Inactive | AlertState::Pending { .. } => {
+                self.state = AlertState::Firing { since: now };
+                Some(self.event(AlertEventKind::Firing, value, now))
+            }
+        }
+    }
+}
+
+/// Evaluates a set of rules against successive snapshots
+#[derive(Debug, Clone, Default)]
+pub struct AlertEngine {
+    alerts: Vec<Alert>,
+}
+
+impl AlertEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        Self {
+            alerts: rules.into_iter()
+                .map(|rule| Alert { rule, state: AlertState::Inactive, value: None })
+                .collect(),
+        }
+    }
+
+    pub fn alerts(&self) -> &[Alert] {
+        &self.alerts
+    }
+
+    /// Rules that are firing, most severe first
+    pub fn firing(&self) -> Vec<&Alert> {
+        let mut firing: Vec<&Alert> = self.alerts.iter().filter(|a| a.is_firing()).collect();
+        firing.sort_by_key(|a| std::cmp::Reverse(a.rule.severity));
+        firing
+    }
+
+    /// Evaluate every rule against a snapshot taken at `now` and return the
+    /// alerts that started firing or resolved
+    pub fn evaluate(&mut self, snapshot: &SystemSnapshot, now: DateTime<Utc>) -> Vec<AlertEvent> {
+        self.alerts.iter_mut()
+            .filter_map(|alert| {
+                let value = alert.rule.expr.value(snapshot);
+                alert.update(value, now)
+            })
+            .collect()
+    }
+
+    /// Forget all state, e.g. when the shown data jumps back in time
+    pub fn reset(&mut self) {
+        for alert in &mut self.alerts {
+            alert.state = AlertState::Inactive;
+            alert.value = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::{DiskInfo, DiskSnapshot, ProcessInfo};
+    use rule::{Comparison, ProcessField, Target};
+    use std::time::Duration;
+
+    fn snapshot(disk_percent: f32) -> SystemSnapshot {
+        SystemSnapshot {
+            disk: Some(DiskSnapshot { total: 100, used: 0, available: 100, usage_percent: disk_percent }),
+            ..Default::default()
+        }
+    }
+
+    fn process(name: &str) -> ProcessInfo {
+        ProcessInfo {
+            pid: 1,
+            ppid: None,
+            name: name.to_string(),
+            user: "root".to_string(),
+            uid: Some(0),
+            effective_user: "root".to_string(),
+            euid: Some(0),
+            effective_group: "root".to_string(),
+            egid: Some(0),
+            cpu_usage: 0.0,
+            memory_usage: 0.0,
+            disk_read: 0,
+            disk_write: 0,
+            disk_read_rate: 0,
+            disk_write_rate: 0,
+            threads: 1,
+            status: "Running".to_string(),
+            start_time: 0,
+            exe_path: "N/A".to_string(),
+            cgroup: None,
+            container: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_expressions() {
+        let expr: RuleExpr = "cpu.global > 90 for 2m".parse().unwrap();
+        assert_eq!(expr.target, Target::Metric("cpu.global".to_string()));
+        assert_eq!(expr.comparison, Comparison::Greater);
+        assert_eq!(expr.threshold, 90.0);
+        assert_eq!(expr.duration, Duration::from_secs(120));
+        assert_eq!(expr.to_string(), "cpu.global > 90 for 2m");
+
+        let expr: RuleExpr = r#"disk["/"].usage>=85%"#.parse().unwrap();
+        assert_eq!(expr.target, Target::DiskUsage("/".to_string()));
+        assert_eq!(expr.to_string(), r#"disk["/"].usage >= 85"#);
+
+        let expr: RuleExpr = "process['postgres'].missing for 30s".parse().unwrap();
+        assert_eq!(expr.target, Target::Process("postgres".to_string(), ProcessField::Count));
+        assert_eq!(expr.to_string(), r#"process["postgres"].missing for 30s"#);
+
+        for invalid in ["cpu.global", "cpu.global > lots", "cpu.global > 90 during 2m", r#"disk["/"].inodes > 5"#, "> 5"] {
+            assert!(invalid.parse::<RuleExpr>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_duration_and_hysteresis() {
+        let mut rule = AlertRule::new("disk.percent > 90 for 2m".parse().unwrap());
+        rule.hysteresis = 10.0;
+        let mut engine = AlertEngine::new(vec![rule]);
+        let start = Utc::now();
+        let at = |seconds: i64| start + chrono::Duration::seconds(seconds);
+
+        assert!(engine.evaluate(&snapshot(95.0), at(0)).is_empty());
+        assert!(matches!(engine.alerts()[0].state, AlertState::Pending { .. }));
+        assert!(engine.evaluate(&snapshot(95.0), at(60)).is_empty());
+
+        let events = engine.evaluate(&snapshot(96.0), at(120));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, AlertEventKind::Firing);
+        assert_eq!(events[0].value, 96.0);
+
+        // Dropping under the threshold but within the hysteresis keeps it firing
+        assert!(engine.evaluate(&snapshot(85.0), at(130)).is_empty());
+        assert_eq!(engine.firing().len(), 1);
+        // No data keeps the state
+        assert!(engine.evaluate(&SystemSnapshot::default(), at(135)).is_empty());
+
+        let events = engine.evaluate(&snapshot(79.0), at(140));
+        assert_eq!(events[0].kind, AlertEventKind::Resolved);
+        assert!(engine.firing().is_empty());
+
+        // A dip restarts the duration
+        engine.evaluate(&snapshot(95.0), at(200));
+        engine.evaluate(&snapshot(50.0), at(260));
+        assert!(engine.evaluate(&snapshot(95.0), at(330)).is_empty());
+    }
+
+    #[test]
+    fn test_disk_and_process_targets() {
+        let mut engine = AlertEngine::new(vec![
+            AlertRule::new(r#"disk["/"].usage > 85"#.parse().unwrap()),
+            AlertRule::new(r#"process["postgres"].missing"#.parse().unwrap()),
+        ]);
+        let mut snapshot = SystemSnapshot::default();
+        assert!(engine.evaluate(&snapshot, Utc::now()).is_empty());
+
+        snapshot.disk_list = vec![DiskInfo {
+            name: "sda1".to_string(),
+            mount_point: "/".to_string(),
+            fs_type: "ext4".to_string(),
+            total: 100,
+            used: 90,
+            available: 10,
+            usage_percent: 90.0,
+            is_removable: false,
+        }];
+        snapshot.processes = vec![process("nginx")];
+        assert_eq!(engine.evaluate(&snapshot, Utc::now()).len(), 2);
+
+        snapshot.processes.push(process("postgres"));
+        let events = engine.evaluate(&snapshot, Utc::now());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].rule, r#"process["postgres"].missing"#);
+        assert_eq!(events[0].kind, AlertEventKind::Resolved);
+    }
+}
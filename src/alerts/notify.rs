@@ -0,0 +1,710 @@
+// src/alerts/notify.rs
+//! Delivery of alert events to notification sinks.
+//!
+//! Sinks are configured as `[[alerts.sinks]]` and picked per rule with its
+//! `notify` list (all sinks when the rule has none). Each sink has a rate
+//! limit: a rule that keeps flapping is reported at most once per
+//! `rate_limit` to that sink, and a resolution is only sent when the sink
+//! was told about the firing. A firing held back after the sink heard the
+//! resolution is sent once the limit has passed if the rule still fires, so
+//! the sink never stays on "resolved" for an open alert. Events are
+//! delivered one after the other by a single background task, so a sink
+//! never hears about a resolution before the firing it resolves.
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::{AlertEvent, AlertEventKind, Severity};
+use crate::config::AlertsConfig;
+
+/// Time a command sink may run before it is killed
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Somewhere alert events can be delivered
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    async fn send(&self, event: &AlertEvent) -> Result<()>;
+}
+
+/// One `[[alerts.sinks]]` entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SinkConfig {
+    /// Referred to from the `notify` list of a rule
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: SinkKind,
+    /// Minimum time between two notifications of the same rule
+    #[serde(default = "default_rate_limit", with = "crate::config::humantime_serde")]
+    pub rate_limit: Duration,
+}
+
+fn default_rate_limit() -> Duration {
+    Duration::from_secs(300)
+}
+
+/// Options of a sink by its `type`; keys another type would take are rejected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase", deny_unknown_fields)]
+pub enum SinkKind {
+    /// Run a shell command with the alert in `SYSTRIX_ALERT_*` variables
+    Command { command: String },
+    /// POST the event as JSON
+    Webhook {
+        url: String,
+        /// Further attempts after a failed delivery
+        #[serde(default = "default_retries")]
+        retries: u32,
+    },
+    /// Send to the local syslog daemon
+    Syslog {
+        /// Datagram socket of the daemon (default: /dev/log)
+        socket: Option<PathBuf>,
+    },
+    /// Append the event as one JSON line
+    File { path: PathBuf },
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+impl SinkKind {
+    pub fn build(&self) -> Result<Arc<dyn NotificationSink>> {
+        Ok(match self {
+            SinkKind::Command { command } => Arc::new(CommandSink::new(command)),
+            #[cfg(feature = "remote")]
+            SinkKind::Webhook { url, retries } => Arc::new(WebhookSink::new(url, *retries)?),
+            #[cfg(not(feature = "remote"))]
+            SinkKind::Webhook { .. } => bail!("webhook sinks need systrix built with the `remote` feature"),
+            SinkKind::Syslog { socket } => Arc::new(SyslogSink::new(socket.clone().unwrap_or_else(|| PathBuf::from("/dev/log")))),
+            SinkKind::File { path } => Arc::new(FileSink::new(path.clone())),
+        })
+    }
+}
+
+/// Runs a shell command per event
+pub struct CommandSink {
+    command: String,
+}
+
+impl CommandSink {
+    pub fn new(command: &str) -> Self {
+        Self { command: command.to_string() }
+    }
+}
+
+#[async_trait]
+impl NotificationSink for CommandSink {
+    async fn send(&self, event: &AlertEvent) -> Result<()> {
+        #[cfg(unix)]
+        let mut command = {
+            let mut command = tokio::process::Command::new("sh");
+            command.arg("-c").arg(&self.command);
+            command
+        };
+        #[cfg(not(unix))]
+        let mut command = {
+            let mut command = tokio::process::Command::new("cmd");
+            command.arg("/C").arg(&self.command);
+            command
+        };
+
+        command
+            .env("SYSTRIX_ALERT_RULE", &event.rule)
+            .env("SYSTRIX_ALERT_EXPR", &event.expr)
+            .env("SYSTRIX_ALERT_SEVERITY", event.severity.to_string())
+            .env("SYSTRIX_ALERT_STATE", state(event.kind))
+            .env("SYSTRIX_ALERT_VALUE", event.value.to_string())
+            .env("SYSTRIX_ALERT_TIMESTAMP", event.timestamp.to_rfc3339())
+            // Output must not reach a terminal the TUI draws on; stderr explains failures
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
+
+        let output = tokio::time::timeout(COMMAND_TIMEOUT, command.output()).await
+            .with_context(|| format!("'{}' did not finish within {}s", self.command, COMMAND_TIMEOUT.as_secs()))?
+            .with_context(|| format!("cannot run '{}'", self.command))?;
+        if !output.status.success() {
+            match String::from_utf8_lossy(&output.stderr).trim() {
+                "" => bail!("'{}' failed with {}", self.command, output.status),
+                stderr => bail!("'{}' failed with {}: {}", self.command, output.status, stderr),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// POSTs each event as JSON, retrying failed deliveries with a growing delay
+#[cfg(feature = "remote")]
+pub struct WebhookSink {
+    url: String,
+    retries: u32,
+    backoff: Duration,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "remote")]
+impl WebhookSink {
+    pub fn new(url: &str, retries: u32) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        Ok(Self {
+            url: url.to_string(),
+            retries,
+            backoff: Duration::from_secs(1),
+            client,
+        })
+    }
+
+    /// Delay before the first retry; it doubles for every further one
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    async fn post(&self, event: &AlertEvent) -> Result<(), (anyhow::Error, bool)> {
+        let response = self.client.post(&self.url).json(event).send().await
+            .map_err(|e| (anyhow::Error::new(e).context(format!("cannot reach {}", self.url)), true))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        // Client errors will not go away by sending the same request again
+        let retry = !status.is_client_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+        Err((anyhow::anyhow!("{} answered {}", self.url, status), retry))
+    }
+}
+
+#[cfg(feature = "remote")]
+#[async_trait]
+impl NotificationSink for WebhookSink {
+    async fn send(&self, event: &AlertEvent) -> Result<()> {
+        let mut delay = self.backoff;
+        let mut attempt = 0;
+        loop {
+            match self.post(event).await {
+                Ok(()) => return Ok(()),
+                Err((error, retry)) if retry && attempt < self.retries => {
+                    tracing::debug!("webhook delivery failed, retrying in {:?}: {:#}", delay, error);
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err((error, _)) => return Err(error.context(format!("gave up after {} attempts", attempt + 1))),
+            }
+        }
+    }
+}
+
+/// Writes RFC 3164 messages to the syslog socket
+pub struct SyslogSink {
+    socket: PathBuf,
+}
+
+impl SyslogSink {
+    pub fn new(socket: PathBuf) -> Self {
+        Self { socket }
+    }
+
+    /// `<priority>systrix[pid]: text` with the `user` facility
+    fn message(event: &AlertEvent) -> String {
+        const USER: u8 = 1;
+        let level = match (event.kind, event.severity) {
+            (AlertEventKind::Firing, Severity::Critical) => 2,
+            (AlertEventKind::Firing, Severity::Warning) => 4,
+            (AlertEventKind::Resolved, _) => 5,
+        };
+        format!("<{}>systrix[{}]: {}", USER * 8 + level, std::process::id(), event)
+    }
+}
+
+#[async_trait]
+impl NotificationSink for SyslogSink {
+    #[cfg(unix)]
+    async fn send(&self, event: &AlertEvent) -> Result<()> {
+        let socket = tokio::net::UnixDatagram::unbound()?;
+        socket.send_to(Self::message(event).as_bytes(), &self.socket).await
+            .with_context(|| format!("cannot write to syslog at {}", self.socket.display()))?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    async fn send(&self, _event: &AlertEvent) -> Result<()> {
+        bail!("syslog is only available on Unix")
+    }
+}
+
+/// Appends each event to a file as a JSON line
+pub struct FileSink {
+    path: PathBuf,
+    /// Keeps concurrent deliveries from interleaving their lines
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl FileSink {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, lock: tokio::sync::Mutex::new(()) }
+    }
+}
+
+#[async_trait]
+impl NotificationSink for FileSink {
+    async fn send(&self, event: &AlertEvent) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&self.path).await
+            .with_context(|| format!("cannot open {}", self.path.display()))?;
+        file.write_all(&line).await?;
+        // Tokio hands the write to a blocking thread; wait until it reached the file
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+fn state(kind: AlertEventKind) -> &'static str {
+    match kind {
+        AlertEventKind::Firing => "firing",
+        AlertEventKind::Resolved => "resolved",
+    }
+}
+
+struct Route {
+    name: String,
+    sink: Arc<dyn NotificationSink>,
+    rate_limit: Duration,
+}
+
+/// What a sink was last told about a rule
+#[derive(Default)]
+struct Delivered {
+    last_firing: Option<chrono::DateTime<chrono::Utc>>,
+    /// A firing notification went out that was not resolved yet
+    open: bool,
+    /// A firing the rate limit held back after the sink heard the resolution;
+    /// sent once the limit has passed unless the rule resolves first
+    held: Option<AlertEvent>,
+}
+
+/// Routes alert events to the configured sinks
+pub struct Notifier {
+    routing: Arc<Routing>,
+    /// Feeds the delivery task, which the first notification starts
+    queue: OnceLock<mpsc::UnboundedSender<AlertEvent>>,
+}
+
+/// Sinks and what each of them was told, shared with the delivery task
+#[derive(Default)]
+struct Routing {
+    routes: Vec<Route>,
+    /// Indices into `routes` per rule name; rules not listed go to every sink
+    rules: HashMap<String, Vec<usize>>,
+    delivered: Mutex<HashMap<(usize, String), Delivered>>,
+}
+
+impl Notifier {
+    pub fn from_config(config: &AlertsConfig) -> Result<Self> {
+        let mut routing = Routing::default();
+        for sink in &config.sinks {
+            if routing.routes.iter().any(|r| r.name == sink.name) {
+                bail!("alert sink '{}' is defined twice", sink.name);
+            }
+            let built = sink.kind.build().with_context(|| format!("alert sink '{}'", sink.name))?;
+            routing.routes.push(Route { name: sink.name.clone(), sink: built, rate_limit: sink.rate_limit });
+        }
+
+        for rule in &config.rules {
+            let Some(names) = &rule.notify else {
+                continue;
+            };
+            let indices = names.iter()
+                .map(|name| routing.routes.iter().position(|r| r.name == *name)
+                    .with_context(|| format!("rule '{}' notifies unknown sink '{}'", rule.display_name(), name)))
+                .collect::<Result<Vec<_>>>()?;
+            routing.rules.insert(rule.display_name(), indices);
+        }
+        Ok(Self { routing: Arc::new(routing), queue: OnceLock::new() })
+    }
+
+    pub fn new() -> Self {
+        Self {
+            routing: Arc::default(),
+            queue: OnceLock::new(),
+        }
+    }
+
+    /// Add a sink that every rule notifies, before the first notification
+    pub fn add_sink(&mut self, name: &str, sink: Arc<dyn NotificationSink>, rate_limit: Duration) {
+        let routing = Arc::get_mut(&mut self.routing).expect("sinks are added before notifying");
+        routing.routes.push(Route { name: name.to_string(), sink, rate_limit });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.routing.routes.is_empty()
+    }
+
+    /// Deliver an event and wait for every sink, returning failures by sink name
+    pub async fn deliver(&self, event: &AlertEvent) -> Vec<(String, anyhow::Error)> {
+        self.routing.deliver(event).await
+    }
+
+    /// Queue events for delivery in the background, logging failures
+    pub fn notify(&self, events: Vec<AlertEvent>) {
+        if self.is_empty() || events.is_empty() {
+            return;
+        }
+        let queue = self.queue.get_or_init(|| {
+            let (sender, mut receiver) = mpsc::unbounded_channel::<AlertEvent>();
+            let routing = self.routing.clone();
+            // Ends once the notifier is dropped and the queue is drained
+            tokio::spawn(async move {
+                loop {
+                    // Wake up for held firings even when no further events come
+                    let next = match routing.next_held() {
+                        Some(at) => {
+                            let wait = (at - chrono::Utc::now()).to_std().unwrap_or(Duration::ZERO);
+                            tokio::time::timeout(wait, receiver.recv()).await.ok()
+                        }
+                        None => Some(receiver.recv().await),
+                    };
+                    match next {
+                        Some(Some(event)) => {
+                            for (sink, error) in routing.deliver(&event).await {
+                                tracing::warn!("cannot notify {} about {}: {:#}", sink, event.rule, error);
+                            }
+                        }
+                        Some(None) => break,
+                        None => {}
+                    }
+                    for (sink, event, error) in routing.deliver_held(chrono::Utc::now()).await {
+                        tracing::warn!("cannot notify {} about {}: {:#}", sink, event.rule, error);
+                    }
+                }
+            });
+            sender
+        });
+        for event in events {
+            let _ = queue.send(event);
+        }
+    }
+}
+
+impl Routing {
+    /// Sinks the event goes to after routing and rate limiting
+    fn targets(&self, event: &AlertEvent) -> Vec<usize> {
+        let routed: Vec<usize> = match self.rules.get(&event.rule) {
+            Some(indices) => indices.clone(),
+            None => (0..self.routes.len()).collect(),
+        };
+
+        let mut delivered = self.delivered.lock().unwrap();
+        routed.into_iter()
+            .filter(|&index| {
+                let state = delivered.entry((index, event.rule.clone())).or_default();
+                match event.kind {
+                    AlertEventKind::Firing => {
+                        if state.last_firing.is_some_and(|last| !self.limit_passed(index, last, event.timestamp)) {
+                            if !state.open {
+                                state.held = Some(event.clone());
+                            }
+                            return false;
+                        }
+                        state.last_firing = Some(event.timestamp);
+                        state.open = true;
+                        state.held = None;
+                        true
+                    }
+                    // The sink still has the resolution of a held firing
+                    AlertEventKind::Resolved => state.held.take().is_none() && std::mem::take(&mut state.open),
+                }
+            })
+            .collect()
+    }
+
+    /// End of the rate limit of sink `index` after a firing sent at `last`
+    fn limit_end(&self, index: usize, last: chrono::DateTime<chrono::Utc>) -> Option<chrono::DateTime<chrono::Utc>> {
+        last.checked_add_signed(chrono::Duration::from_std(self.routes[index].rate_limit).ok()?)
+    }
+
+    fn limit_passed(&self, index: usize, last: chrono::DateTime<chrono::Utc>, at: chrono::DateTime<chrono::Utc>) -> bool {
+        self.limit_end(index, last).is_some_and(|end| at >= end)
+    }
+
+    /// When the next held firing may be sent
+    fn next_held(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let delivered = self.delivered.lock().unwrap();
+        delivered.iter()
+            .filter(|(_, state)| state.held.is_some())
+            .filter_map(|((index, _), state)| self.limit_end(*index, state.last_firing?))
+            .min()
+    }
+
+    /// Held firings whose rate limit has passed by `now`, with their sinks
+    fn take_held(&self, now: chrono::DateTime<chrono::Utc>) -> Vec<(usize, AlertEvent)> {
+        let mut delivered = self.delivered.lock().unwrap();
+        delivered.iter_mut()
+            .filter(|((index, _), state)| state.last_firing.is_some_and(|last| self.limit_passed(*index, last, now)))
+            .filter_map(|((index, _), state)| {
+                let event = state.held.take()?;
+                state.last_firing = Some(now);
+                state.open = true;
+                Some((*index, event))
+            })
+            .collect()
+    }
+
+    async fn deliver(&self, event: &AlertEvent) -> Vec<(String, anyhow::Error)> {
+        let mut failures = Vec::new();
+        for index in self.targets(event) {
+            let route = &self.routes[index];
+            if let Err(error) = route.sink.send(event).await {
+                failures.push((route.name.clone(), error));
+            }
+        }
+        failures
+    }
+
+    async fn deliver_held(&self, now: chrono::DateTime<chrono::Utc>) -> Vec<(String, AlertEvent, anyhow::Error)> {
+        let mut failures = Vec::new();
+        for (index, event) in self.take_held(now) {
+            let route = &self.routes[index];
+            if let Err(error) = route.sink.send(&event).await {
+                failures.push((route.name.clone(), event, error));
+            }
+        }
+        failures
+    }
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn event(kind: AlertEventKind, seconds: i64) -> AlertEvent {
+        AlertEvent {
+            rule: "cpu".to_string(),
+            expr: "cpu.global > 90".to_string(),
+            severity: Severity::Critical,
+            kind,
+            value: 95.0,
+            timestamp: Utc::now() + chrono::Duration::seconds(seconds),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_and_command_sinks() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("alerts.ndjson");
+        let out = dir.path().join("command.txt");
+
+        let mut notifier = Notifier::new();
+        notifier.add_sink("file", Arc::new(FileSink::new(log.clone())), Duration::ZERO);
+        #[cfg(unix)]
+        notifier.add_sink("command", Arc::new(CommandSink::new(&format!(
+            "echo \"$SYSTRIX_ALERT_RULE $SYSTRIX_ALERT_STATE $SYSTRIX_ALERT_SEVERITY\" > {}",
+            out.display()
+        ))), Duration::ZERO);
+
+        assert!(notifier.deliver(&event(AlertEventKind::Firing, 0)).await.is_empty());
+        let lines = std::fs::read_to_string(&log).unwrap();
+        let logged: serde_json::Value = serde_json::from_str(lines.lines().next().unwrap()).unwrap();
+        assert_eq!(logged["rule"], "cpu");
+        assert_eq!(logged["kind"], "firing");
+        #[cfg(unix)]
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "cpu firing critical\n");
+
+        let failing = CommandSink::new("exit 3");
+        assert!(failing.send(&event(AlertEventKind::Firing, 0)).await.is_err());
+        #[cfg(unix)]
+        {
+            let failing = CommandSink::new("echo output; echo broken >&2; exit 3");
+            let error = failing.send(&event(AlertEventKind::Firing, 0)).await.unwrap_err().to_string();
+            assert!(error.ends_with("exit status: 3: broken"), "{}", error);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_syslog_sink_writes_a_datagram() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.sock");
+        let listener = tokio::net::UnixDatagram::bind(&path).unwrap();
+
+        SyslogSink::new(path).send(&event(AlertEventKind::Firing, 0)).await.unwrap();
+        let mut buffer = [0u8; 512];
+        let length = listener.recv(&mut buffer).await.unwrap();
+        let message = String::from_utf8_lossy(&buffer[..length]);
+        assert!(message.starts_with("<10>systrix["), "{}", message);
+        assert!(message.contains("cpu firing"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_pairs_resolutions_with_sent_firings() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("alerts.ndjson");
+        let mut notifier = Notifier::new();
+        notifier.add_sink("file", Arc::new(FileSink::new(log.clone())), Duration::from_secs(60));
+
+        for (kind, seconds) in [
+            (AlertEventKind::Firing, 0),
+            (AlertEventKind::Resolved, 10),
+            // Flapping within the minute is not reported again, and the
+            // held firing goes away with the resolution that follows
+            (AlertEventKind::Firing, 20),
+            (AlertEventKind::Resolved, 30),
+            (AlertEventKind::Firing, 70),
+        ] {
+            notifier.deliver(&event(kind, seconds)).await;
+        }
+
+        let kinds: Vec<String> = std::fs::read_to_string(&log).unwrap().lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["kind"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(kinds, ["firing", "resolved", "firing"]);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_firing_is_sent_once_the_limit_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("alerts.ndjson");
+        let mut notifier = Notifier::new();
+        notifier.add_sink("file", Arc::new(FileSink::new(log.clone())), Duration::from_millis(300));
+
+        // The rule fires again right after resolving and then stays firing
+        let start = Utc::now();
+        let at = |millis| AlertEvent { timestamp: start + chrono::Duration::milliseconds(millis), ..event(AlertEventKind::Firing, 0) };
+        notifier.notify(vec![
+            at(0),
+            AlertEvent { kind: AlertEventKind::Resolved, ..at(10) },
+            at(20),
+        ]);
+
+        let kinds = || -> Vec<String> {
+            std::fs::read_to_string(&log).unwrap_or_default().lines()
+                .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["kind"].as_str().unwrap().to_string())
+                .collect()
+        };
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(kinds(), ["firing", "resolved"]);
+        for _ in 0..50 {
+            if kinds().len() == 3 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(kinds(), ["firing", "resolved", "firing"]);
+    }
+
+    #[tokio::test]
+    async fn test_notifications_keep_their_order() {
+        // Stand-in for a webhook that is still retrying the first event
+        struct SlowFirst(Mutex<Vec<AlertEventKind>>);
+        #[async_trait]
+        impl NotificationSink for SlowFirst {
+            async fn send(&self, event: &AlertEvent) -> Result<()> {
+                if self.0.lock().unwrap().is_empty() && event.kind == AlertEventKind::Firing {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                }
+                self.0.lock().unwrap().push(event.kind);
+                Ok(())
+            }
+        }
+
+        let sink = Arc::new(SlowFirst(Mutex::new(Vec::new())));
+        let mut notifier = Notifier::new();
+        notifier.add_sink("slow", sink.clone(), Duration::ZERO);
+        notifier.notify(vec![event(AlertEventKind::Firing, 0)]);
+        notifier.notify(vec![event(AlertEventKind::Resolved, 1)]);
+
+        for _ in 0..50 {
+            if sink.0.lock().unwrap().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(*sink.0.lock().unwrap(), [AlertEventKind::Firing, AlertEventKind::Resolved]);
+    }
+
+    #[cfg(feature = "remote")]
+    #[tokio::test]
+    async fn test_webhook_retries_until_delivered() {
+        use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // Stand-in receiver that fails the first two requests
+        #[derive(Clone, Default)]
+        struct Receiver {
+            attempts: Arc<AtomicUsize>,
+            received: Arc<Mutex<Vec<serde_json::Value>>>,
+        }
+        let receiver = Receiver::default();
+        let app = Router::new()
+            .route("/hook", post(|State(receiver): State<Receiver>, Json(body): Json<serde_json::Value>| async move {
+                if receiver.attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                    return StatusCode::SERVICE_UNAVAILABLE;
+                }
+                receiver.received.lock().unwrap().push(body);
+                StatusCode::OK
+            }))
+            .with_state(receiver.clone());
+        let (attempts, received) = (receiver.attempts, receiver.received);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let sink = WebhookSink::new(&url, 3).unwrap().backoff(Duration::from_millis(10));
+        sink.send(&event(AlertEventKind::Firing, 0)).await.unwrap();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+        assert_eq!(received.lock().unwrap()[0]["severity"], "critical");
+
+        // Out of retries
+        attempts.store(0, Ordering::SeqCst);
+        let sink = WebhookSink::new(&url, 1).unwrap().backoff(Duration::from_millis(10));
+        let error = sink.send(&event(AlertEventKind::Firing, 0)).await.unwrap_err();
+        assert!(format!("{:#}", error).contains("gave up after 2 attempts"), "{:#}", error);
+    }
+
+    #[test]
+    fn test_routing_checks_sink_names() {
+        let config: crate::config::Config = toml::from_str(r#"
+[[alerts.sinks]]
+name = "log"
+type = "file"
+path = "/tmp/alerts.ndjson"
+rate_limit = "1m"
+
+[[alerts.rules]]
+expr = "cpu.global > 90"
+notify = ["log"]
+"#).unwrap();
+        assert_eq!(config.alerts.sinks[0].rate_limit, Duration::from_secs(60));
+        assert!(Notifier::from_config(&config.alerts).is_ok());
+
+        let mut alerts = config.alerts.clone();
+        alerts.rules[0].notify = Some(vec!["pager".to_string()]);
+        let error = Notifier::from_config(&alerts).err().unwrap();
+        assert!(format!("{:#}", error).contains("unknown sink 'pager'"));
+
+        let typo = toml::from_str::<crate::config::Config>("[[alerts.sinks]]\nname = \"ops\"\ntype = \"webhook\"\nurll = \"http://ops\"");
+        assert!(typo.unwrap_err().to_string().contains("unknown field `urll`"));
+        let misplaced = toml::from_str::<crate::config::Config>("[[alerts.sinks]]\nname = \"log\"\ntype = \"file\"\npath = \"a\"\nretries = 2");
+        assert!(misplaced.unwrap_err().to_string().contains("unknown field `retries`"));
+    }
+}
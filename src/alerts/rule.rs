@@ -0,0 +1,247 @@
+// src/alerts/rule.rs
+//! Alert rule expressions.
+//!
+//! An expression names a value, compares it with a threshold and optionally
+//! says how long the comparison has to hold before the alert fires:
+//!
+//! ```text
+//! cpu.global > 90 for 2m
+//! disk["/"].usage >= 85
+//! process["postgres"].missing for 30s
+//! process["java"].memory > 40
+//! ```
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::monitor::SystemSnapshot;
+
+/// The value an expression looks at
+#[derive(Debug, Clone, PartialEq)]
+pub enum Target {
+    /// Any name understood by [`SystemSnapshot::metric`]
+    Metric(String),
+    /// Usage in percent of the filesystem mounted here
+    DiskUsage(String),
+    /// Value summed over all processes with this exact name
+    Process(String, ProcessField),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessField {
+    Count,
+    Cpu,
+    Memory,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    /// Longest symbols first, so `>=` is not read as `>`
+    const SYMBOLS: [(&'static str, Comparison); 6] = [
+        (">=", Comparison::GreaterOrEqual),
+        ("<=", Comparison::LessOrEqual),
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        (">", Comparison::Greater),
+        ("<", Comparison::Less),
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        Self::SYMBOLS.iter().find(|(_, c)| *c == self).map(|(s, _)| *s).unwrap_or("?")
+    }
+
+    fn compare(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Greater => value > threshold,
+            Comparison::GreaterOrEqual => value >= threshold,
+            Comparison::Less => value < threshold,
+            Comparison::LessOrEqual => value <= threshold,
+            Comparison::Equal => value == threshold,
+            Comparison::NotEqual => value != threshold,
+        }
+    }
+}
+
+/// A parsed rule expression, written as text in the config file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RuleExpr {
+    pub target: Target,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    /// How long the comparison has to hold before the alert fires
+    pub duration: Duration,
+}
+
+impl RuleExpr {
+    /// Current value of the target, `None` while it has not been collected
+    pub fn value(&self, snapshot: &SystemSnapshot) -> Option<f64> {
+        match &self.target {
+            Target::Metric(name) => snapshot.metric(name),
+            Target::DiskUsage(mount) => snapshot.disk_list.iter()
+                .find(|d| d.mount_point == *mount)
+                .map(|d| d.usage_percent as f64),
+            Target::Process(name, field) => {
+                // An empty list means the processes were not collected yet
+                if snapshot.processes.is_empty() {
+                    return None;
+                }
+                let matching = snapshot.processes.iter().filter(|p| p.name == *name);
+                Some(match field {
+                    ProcessField::Count => matching.count() as f64,
+                    ProcessField::Cpu => matching.map(|p| p.cpu_usage as f64).sum(),
+                    ProcessField::Memory => matching.map(|p| p.memory_usage as f64).sum(),
+                })
+            }
+        }
+    }
+
+    /// Whether the value comes from the process list, which has to be
+    /// complete for the rule to be meaningful
+    pub fn reads_processes(&self) -> bool {
+        matches!(self.target, Target::Process(..))
+    }
+
+    /// Whether the comparison holds for `value`
+    pub fn holds(&self, value: f64) -> bool {
+        self.comparison.compare(value, self.threshold)
+    }
+
+    /// Whether a firing alert stays active: the value has to move `hysteresis`
+    /// past the threshold before it resolves. Equality checks ignore it.
+    pub fn still_holds(&self, value: f64, hysteresis: f64) -> bool {
+        let threshold = match self.comparison {
+            Comparison::Greater | Comparison::GreaterOrEqual => self.threshold - hysteresis,
+            Comparison::Less | Comparison::LessOrEqual => self.threshold + hysteresis,
+            Comparison::Equal | Comparison::NotEqual => self.threshold,
+        };
+        self.comparison.compare(value, threshold)
+    }
+}
+
+impl FromStr for RuleExpr {
+    type Err = anyhow::Error;
+
+    fn from_str(expr: &str) -> Result<Self> {
+        let expr = expr.trim();
+        let (target, missing, rest) = parse_target(expr)?;
+
+        // `.missing` is shorthand for a count of zero
+        let (comparison, threshold, rest) = if missing {
+            (Comparison::Equal, 0.0, rest)
+        } else {
+            parse_comparison(rest.trim_start(), expr)?
+        };
+
+        let rest = rest.trim();
+        let duration = match rest.strip_prefix("for") {
+            Some(duration) if duration.starts_with(char::is_whitespace) => humantime::parse_duration(duration.trim())
+                .with_context(|| format!("invalid duration '{}' in '{}'", duration.trim(), expr))?,
+            _ if rest.is_empty() => Duration::ZERO,
+            _ => bail!("unexpected '{}' in '{}', expected 'for <duration>'", rest, expr),
+        };
+
+        Ok(Self { target, comparison, threshold, duration })
+    }
+}
+
+/// The target and whether it was written as `.missing`, followed by the rest of `expr`
+fn parse_target(expr: &str) -> Result<(Target, bool, &str)> {
+    for kind in ["disk", "process"] {
+        let Some(rest) = expr.strip_prefix(kind).and_then(|r| r.strip_prefix('[')) else {
+            continue;
+        };
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')
+            .with_context(|| format!("expected a quoted name after '{}[' in '{}'", kind, expr))?;
+        let rest = &rest[1..];
+        let end = rest.find(quote).with_context(|| format!("unterminated name in '{}'", expr))?;
+        let name = rest[..end].to_string();
+        let rest = rest[end + 1..].strip_prefix("].")
+            .with_context(|| format!("expected '].<field>' after the name in '{}'", expr))?;
+        let field_end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        let (field, rest) = rest.split_at(field_end);
+
+        let target = match (kind, field) {
+            ("disk", "usage") => Target::DiskUsage(name),
+            ("process", "missing" | "count") => Target::Process(name, ProcessField::Count),
+            ("process", "cpu") => Target::Process(name, ProcessField::Cpu),
+            ("process", "memory") => Target::Process(name, ProcessField::Memory),
+            ("disk", _) => bail!("unknown disk field '{}' in '{}', expected usage", field, expr),
+            _ => bail!("unknown process field '{}' in '{}', expected missing, count, cpu or memory", field, expr),
+        };
+        return Ok((target, field == "missing", rest));
+    }
+
+    let end = expr.find(|c: char| c.is_whitespace() || "<>=!".contains(c)).unwrap_or(expr.len());
+    let (name, rest) = expr.split_at(end);
+    if name.is_empty() {
+        bail!("missing metric name in '{}'", expr);
+    }
+    Ok((Target::Metric(name.to_string()), false, rest))
+}
+
+fn parse_comparison<'a>(rest: &'a str, expr: &str) -> Result<(Comparison, f64, &'a str)> {
+    let (symbol, comparison) = Comparison::SYMBOLS.iter()
+        .find(|(symbol, _)| rest.starts_with(symbol))
+        .with_context(|| format!("expected a comparison (>, >=, <, <=, ==, !=) in '{}'", expr))?;
+    let rest = rest[symbol.len()..].trim_start();
+    let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+    let (number, rest) = rest.split_at(end);
+    let threshold = number.trim_end_matches('%').parse::<f64>()
+        .with_context(|| format!("invalid threshold '{}' in '{}'", number, expr))?;
+    Ok((*comparison, threshold, rest))
+}
+
+impl fmt::Display for RuleExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match &self.target {
+            Target::Process(name, ProcessField::Count) if self.comparison == Comparison::Equal && self.threshold == 0.0 => {
+                format!("process[{:?}].missing", name)
+            }
+            target => {
+                let target = match target {
+                    Target::Metric(name) => name.clone(),
+                    Target::DiskUsage(mount) => format!("disk[{:?}].usage", mount),
+                    Target::Process(name, field) => format!("process[{:?}].{}", name, match field {
+                        ProcessField::Count => "count",
+                        ProcessField::Cpu => "cpu",
+                        ProcessField::Memory => "memory",
+                    }),
+                };
+                format!("{} {} {}", target, self.comparison.symbol(), self.threshold)
+            }
+        };
+        let text = if self.duration.is_zero() {
+            text
+        } else {
+            format!("{} for {}", text, humantime::format_duration(self.duration))
+        };
+        f.pad(&text)
+    }
+}
+
+impl TryFrom<String> for RuleExpr {
+    type Error = anyhow::Error;
+
+    fn try_from(expr: String) -> Result<Self> {
+        expr.parse()
+    }
+}
+
+impl From<RuleExpr> for String {
+    fn from(expr: RuleExpr) -> Self {
+        expr.to_string()
+    }
+}
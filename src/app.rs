@@ -4,10 +4,20 @@
 #[cfg(feature = "tui")]
 use anyhow::Result;
 
+#[cfg(feature = "tui")]
+use crate::alerts::Notifier;
+#[cfg(feature = "tui")]
+use crate::config::{Config, ConfigWatcher, RELOAD_INTERVAL};
 #[cfg(feature = "tui")]
 use crate::tui::{event::EventHandler, ui::Ui};
 #[cfg(feature = "tui")]
-use crate::monitor::SysinfoBackend;
+use crate::monitor::{Collector, CollectorConfig, MonitorBackend, ReplayBackend};
+#[cfg(all(feature = "tui", feature = "remote"))]
+use crate::config::HostConfig;
+#[cfg(all(feature = "tui", feature = "remote"))]
+use crate::monitor::{fleet::Fleet, RemoteBackend};
+#[cfg(all(feature = "tui", feature = "remote"))]
+use crate::tui::ui::FleetRequest;
 #[cfg(feature = "tui")]
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
@@ -18,38 +28,205 @@ use crossterm::{
 use ratatui::{backend::CrosstermBackend, Terminal};
 #[cfg(feature = "tui")]
 use std::io;
+#[cfg(feature = "tui")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "tui")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "tui")]
+use std::sync::{Arc, Mutex};
+
+/// Set while the TUI owns the terminal, where log lines would draw over it
+#[cfg(feature = "tui")]
+static TERMINAL_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Latest warning logged while the TUI owns the terminal, for the footer
+#[cfg(feature = "tui")]
+static LAST_WARNING: Mutex<Option<String>> = Mutex::new(None);
+
+/// Writer of the log: stderr, or nothing while the TUI is drawn
+#[cfg(feature = "tui")]
+pub fn log_writer() -> Box<dyn io::Write> {
+    if TERMINAL_TAKEN.load(Ordering::Relaxed) {
+        Box::new(io::sink())
+    } else {
+        Box::new(io::stderr())
+    }
+}
+
+/// Log layer keeping the message of warnings and errors logged while the
+/// TUI is drawn, which the footer shows in place of the log line
+#[cfg(feature = "tui")]
+pub struct FooterLog;
+
+#[cfg(feature = "tui")]
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for FooterLog {
+    fn on_event(&self, event: &tracing::Event<'_>, _: tracing_subscriber::layer::Context<'_, S>) {
+        if !TERMINAL_TAKEN.load(Ordering::Relaxed) || *event.metadata().level() > tracing::Level::WARN {
+            return;
+        }
+
+        struct Message(String);
+        impl tracing::field::Visit for Message {
+            fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+                if field.name() == "message" {
+                    self.0 = format!("{:?}", value);
+                }
+            }
+        }
+        let mut message = Message(String::new());
+        event.record(&mut message);
+        *LAST_WARNING.lock().unwrap() = Some(message.0);
+    }
+}
 
 #[cfg(feature = "tui")]
 pub struct App {
     refresh_interval: u64,
-    backend: SysinfoBackend,
+    /// Collector schedule for a refresh interval
+    schedule: fn(u64) -> CollectorConfig,
+    backend: Arc<dyn MonitorBackend>,
+    /// Missing while the fleet table is shown
+    collector: Option<Collector>,
+    replay: Option<Arc<ReplayBackend>>,
+    config: Config,
+    notifier: Option<Arc<Notifier>>,
+    watcher: Option<ConfigWatcher>,
+    /// Set when `--refresh-interval` was given, which edits do not override
+    refresh_interval_fixed: bool,
+    last_reload_check: std::time::Instant,
+    #[cfg(feature = "remote")]
+    remote: Option<Arc<RemoteBackend>>,
+    #[cfg(feature = "remote")]
+    fleet: Option<Fleet>,
 }
 
 #[cfg(feature = "tui")]
 impl App {
-    pub fn new(refresh_interval: u64) -> Result<Self> {
+    pub fn new(backend: Arc<dyn MonitorBackend>, refresh_interval: u64) -> Result<Self> {
+        Ok(Self::with_backend(backend, refresh_interval, CollectorConfig::with_refresh_interval))
+    }
+
+    /// Play back a recording instead of monitoring the live system
+    pub fn replay(path: &Path, refresh_interval: u64) -> Result<Self> {
+        let replay = Arc::new(ReplayBackend::open(path)?);
+        // A recording is read from memory, so it can follow seeks on every metric
+        let mut app = Self::with_backend(replay.clone(), refresh_interval, CollectorConfig::uniform);
+        app.replay = Some(replay);
+        Ok(app)
+    }
+
+    /// Monitor the machine running the remote agent at `url`
+    #[cfg(feature = "remote")]
+    pub fn remote(url: &str, token: Option<String>, refresh_interval: u64) -> Result<Self> {
+        let interval = std::time::Duration::from_millis(refresh_interval);
+        let remote = Arc::new(RemoteBackend::new(url, token)?.cache_for(interval / 2));
+        // Every metric comes from the same fetched snapshot, so they can all follow it
+        let mut app = Self::with_backend(remote.clone(), refresh_interval, CollectorConfig::uniform);
+        app.remote = Some(remote);
+        Ok(app)
+    }
+
+    /// Watch every configured agent on the fleet table
+    #[cfg(feature = "remote")]
+    pub fn fleet(hosts: &[HostConfig], refresh_interval: u64) -> Result<Self> {
+        let fleet = Fleet::spawn(hosts, refresh_interval)?;
+        let Some(first) = fleet.hosts().first() else {
+            anyhow::bail!("no [[hosts]] configured");
+        };
+        
         Ok(Self {
             refresh_interval,
-            backend: SysinfoBackend::new(),
+            // Opened hosts are read through the same snapshot, like a single remote host
+            schedule: CollectorConfig::uniform,
+            backend: first.remote.clone(),
+            collector: None,
+            replay: None,
+            config: Config::default(),
+            notifier: None,
+            watcher: None,
+            refresh_interval_fixed: false,
+            last_reload_check: std::time::Instant::now(),
+            remote: None,
+            fleet: Some(fleet),
         })
     }
 
+    fn with_backend(backend: Arc<dyn MonitorBackend>, refresh_interval: u64, schedule: fn(u64) -> CollectorConfig) -> Self {
+        // Sampling runs in the background so input handling never waits on it
+        let collector = Collector::spawn(backend.clone(), Self::collector_config(schedule, refresh_interval));
+        
+        Self {
+            refresh_interval,
+            schedule,
+            backend,
+            collector: Some(collector),
+            replay: None,
+            config: Config::default(),
+            notifier: None,
+            watcher: None,
+            refresh_interval_fixed: false,
+            last_reload_check: std::time::Instant::now(),
+            #[cfg(feature = "remote")]
+            remote: None,
+            #[cfg(feature = "remote")]
+            fleet: None,
+        }
+    }
+
+    /// Use the theme, limits and mouse setting of the configuration, and
+    /// evaluate its alert rules against the shown data: firing ones are shown
+    /// in the header and sent to the configured sinks
+    pub fn with_config(mut self, config: Config) -> Result<Self> {
+        let notifier = Notifier::from_config(&config.alerts)?;
+        self.notifier = (!notifier.is_empty()).then(|| Arc::new(notifier));
+        self.config = config;
+        Ok(self)
+    }
+
+    /// Apply edits of the configuration file at `path` while running. With
+    /// `refresh_interval_fixed`, the interval given on the command line stays.
+    pub fn watch_config(mut self, path: PathBuf, refresh_interval_fixed: bool) -> Self {
+        self.watcher = Some(ConfigWatcher::new(path));
+        self.refresh_interval_fixed = refresh_interval_fixed;
+        self
+    }
+
+    fn collector_config(schedule: fn(u64) -> CollectorConfig, refresh_interval: u64) -> CollectorConfig {
+        // The tree view needs every process; the list view applies its own limit
+        schedule(refresh_interval).process_limit(usize::MAX)
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         // Setup terminal
         enable_raw_mode()?;
         let mut stdout = io::stdout();
-        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        execute!(stdout, EnterAlternateScreen)?;
+        set_mouse_capture(self.config.tui.enable_mouse)?;
         let backend = CrosstermBackend::new(stdout);
         let mut terminal = Terminal::new(backend)?;
+        TERMINAL_TAKEN.store(true, Ordering::Relaxed);
 
         // Create UI and event handler
-        let mut ui = Ui::with_refresh_interval(self.refresh_interval);
+        let mut ui = Ui::new(self.backend.clone(), self.refresh_interval);
+        ui.apply_config(&self.config, self.notifier.clone());
+        if let Some(replay) = &self.replay {
+            ui.set_replay(replay.clone());
+        }
+        #[cfg(feature = "remote")]
+        if let Some(remote) = &self.remote {
+            ui.set_remote(remote.clone());
+        }
+        #[cfg(feature = "remote")]
+        if self.fleet.is_some() {
+            ui.enable_fleet();
+        }
         let mut event_handler = EventHandler::new(self.refresh_interval);
 
         // Run the app
         let result = self.run_loop(&mut terminal, &mut ui, &mut event_handler).await;
 
         // Restore terminal
+        TERMINAL_TAKEN.store(false, Ordering::Relaxed);
         disable_raw_mode()?;
         execute!(
             terminal.backend_mut(),
@@ -68,8 +245,18 @@ impl App {
         event_handler: &mut EventHandler,
     ) -> Result<()> {
         loop {
-            // Update data
-            ui.update_data(&self.backend).await?;
+            self.reload_config(ui, event_handler)?;
+            if let Some(warning) = LAST_WARNING.lock().unwrap().take() {
+                ui.show_warning(warning);
+            }
+            
+            #[cfg(feature = "remote")]
+            self.follow_fleet(ui);
+
+            // Update data from the latest collected snapshot
+            if let Some(collector) = &self.collector {
+                ui.update_data(collector.latest());
+            }
 
             // Draw UI
             terminal.draw(|f| ui.render(f))?;
@@ -82,4 +269,95 @@ impl App {
 
         Ok(())
     }
+
+    /// Apply the watched configuration file when it changed; an edit that
+    /// does not load is shown in the footer and the running settings stay
+    fn reload_config(&mut self, ui: &mut Ui, event_handler: &mut EventHandler) -> Result<()> {
+        let Some(watcher) = &mut self.watcher else {
+            return Ok(());
+        };
+        if self.last_reload_check.elapsed() < RELOAD_INTERVAL {
+            return Ok(());
+        }
+        self.last_reload_check = std::time::Instant::now();
+        
+        let Some(loaded) = watcher.poll() else {
+            return Ok(());
+        };
+        // Sinks are built up front, so a broken one also leaves the old settings in place.
+        // Unchanged alerts keep their notifier, which knows what the sinks were told.
+        let (config, notifier) = match loaded.and_then(|config| {
+            let notifier = match config.alerts != self.config.alerts {
+                true => Some(Notifier::from_config(&config.alerts)?),
+                false => None,
+            };
+            Ok((config, notifier))
+        }) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                ui.set_config_error(Some(format!("{:#}", e)));
+                return Ok(());
+            }
+        };
+        
+        let refresh_interval = config.general.refresh_interval_ms;
+        if !self.refresh_interval_fixed && refresh_interval != self.refresh_interval {
+            self.refresh_interval = refresh_interval;
+            if let Some(collector) = &mut self.collector {
+                collector.restart(Self::collector_config(self.schedule, refresh_interval));
+            }
+            event_handler.set_refresh_interval(refresh_interval);
+            ui.set_refresh_interval(refresh_interval);
+        }
+        if config.tui.enable_mouse != self.config.tui.enable_mouse {
+            set_mouse_capture(config.tui.enable_mouse)?;
+        }
+        
+        if let Some(notifier) = notifier {
+            self.notifier = (!notifier.is_empty()).then(|| Arc::new(notifier));
+        }
+        ui.apply_config(&config, self.notifier.clone());
+        ui.set_config_error(None);
+        self.config = config;
+        Ok(())
+    }
+
+    /// Open or close the host chosen on the fleet table and refresh the table
+    #[cfg(feature = "remote")]
+    fn follow_fleet(&mut self, ui: &mut Ui) {
+        let Some(fleet) = &self.fleet else {
+            return;
+        };
+
+        match ui.take_fleet_request() {
+            Some(FleetRequest::Open(index)) => {
+                let remote = fleet.hosts()[index].remote.clone();
+                // The opened host gets a full collector; the fleet keeps polling the summary
+                self.collector = Some(Collector::spawn(
+                    remote.clone(),
+                    Self::collector_config(self.schedule, self.refresh_interval),
+                ));
+                ui.enter_host(index, remote);
+            }
+            Some(FleetRequest::Close) => {
+                self.collector = None;
+                ui.show_fleet();
+            }
+            None => {}
+        }
+
+        if let (true, Some(fleet)) = (ui.is_fleet_overview(), &self.fleet) {
+            ui.update_fleet(fleet.rows());
+        }
+    }
+}
+
+#[cfg(feature = "tui")]
+fn set_mouse_capture(enabled: bool) -> Result<()> {
+    if enabled {
+        execute!(io::stdout(), EnableMouseCapture)?;
+    } else {
+        execute!(io::stdout(), DisableMouseCapture)?;
+    }
+    Ok(())
 }
@@ -4,18 +4,90 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
+use std::sync::Arc;
 
-use crate::monitor::{MonitorBackend, SysinfoBackend};
+use crate::monitor::collector::collect_once;
+use crate::monitor::{CollectorConfig, MonitorBackend, ProcessSort, SortOrder, SysinfoBackend};
 use crate::utils;
 
+/// Interval between the two samples taken by one-shot commands that report rates
+const SAMPLE_INTERVAL_MS: u64 = 500;
+
 #[derive(Parser)]
 #[command(name = "systrix")]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
+    /// Configuration file (default: $XDG_CONFIG_HOME/systrix/config.toml)
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+/// Implementation used to read the live system
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum BackendKind {
+    /// Cross-platform, based on the sysinfo crate
+    Sysinfo,
+    /// Reads /proc and /sys directly (Linux only, lighter)
+    Procfs,
+}
+
+/// Ranking used by `systrix history --top`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum HistoryTop {
+    /// Average CPU usage
+    Cpu,
+    /// Average memory usage
+    Mem,
+}
+
+/// Output of commands that print records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Subcommand)]
+pub enum HistoryCommand {
+    /// Write snapshots to the history store until Ctrl+C, as the agent does with [history] enabled
+    Record,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print the effective settings, after environment overrides, with tokens masked
+    Show,
+    /// Print the path of the configuration file
+    Path,
+    /// Write the documented default configuration file
+    Init {
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Check the configuration file; exits with 1 when it is invalid
+    Validate,
+}
+
+#[derive(Subcommand)]
+pub enum AlertsCommand {
+    /// Evaluate the alert rules once against the current system; exits with 1 when any fires
+    /// or, for rules with a duration, has its condition met
+    Check {
+        /// Rule to check instead of the configured ones, e.g. "cpu.global > 90" (repeatable)
+        #[arg(long = "rule")]
+        rules: Vec<crate::alerts::RuleExpr>,
+        
+        /// Print the results as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Display system information summary
@@ -23,17 +95,35 @@ pub enum Commands {
     
     /// List processes
     Ps {
-        /// Sort by: cpu, mem, io
-        #[arg(long, default_value = "cpu")]
-        sort: String,
+        /// Sort by column
+        #[arg(long, value_enum, default_value_t = ProcessSort::Cpu)]
+        sort: ProcessSort,
+        
+        /// Sort direction (defaults to descending for resource columns)
+        #[arg(long, value_enum)]
+        order: Option<SortOrder>,
         
-        /// Filter processes by name
+        /// Filter processes by name or user name
         #[arg(long)]
         filter: Option<String>,
         
-        /// Limit number of results
-        #[arg(long, default_value = "20")]
-        limit: usize,
+        /// Limit number of results (default: process_limit in the [tui] section)
+        #[arg(long)]
+        limit: Option<usize>,
+        
+        /// Show processes as a parent/child tree
+        #[arg(long)]
+        tree: bool,
+    },
+    
+    /// Show command line, environment, open files and limits of a process
+    Inspect {
+        /// Process ID to inspect
+        pid: u32,
+        
+        /// Print the detail as JSON
+        #[arg(long)]
+        json: bool,
     },
     
     /// Kill a process
@@ -54,14 +144,153 @@ pub enum Commands {
     Net,
     
     /// Display disk partitions and usage
-    Disk,
+    Disk {
+        /// Show per-device throughput, IOPS, latency and utilization (Linux)
+        #[arg(long)]
+        io: bool,
+    },
+    
+    /// Display GPUs with utilization, memory, clocks and temperature
+    Gpu {
+        /// Print the GPU list as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Display sockets with their state, addresses and owning process (Linux)
+    Sockets {
+        /// Only show listening TCP sockets and bound UDP and Unix sockets
+        #[arg(long)]
+        listen: bool,
+        
+        /// Only show sockets with this local or remote port
+        #[arg(long)]
+        port: Option<u16>,
+    },
+    
+    /// Display cgroups and containers with their resource usage (Linux)
+    Cgroups {
+        /// Only show cgroups that belong to a container
+        #[arg(long)]
+        containers: bool,
+        
+        /// Limit number of results
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
     
     /// Launch interactive TUI
     #[cfg(feature = "tui")]
     Tui {
-        /// Refresh interval in milliseconds
-        #[arg(long, default_value = "500")]
-        refresh_interval: u64,
+        /// Refresh interval in milliseconds (default: refresh_interval_ms in the [general] section)
+        #[arg(long)]
+        refresh_interval: Option<u64>,
+        
+        /// Play back a recording made with `systrix record` instead of monitoring live
+        #[arg(long)]
+        replay: Option<PathBuf>,
+        
+        /// Data source for live monitoring
+        #[arg(long, value_enum, default_value_t = BackendKind::Sysinfo)]
+        backend: BackendKind,
+        
+        /// Monitor the machine running `systrix agent` at this address, e.g. http://box:8080
+        #[arg(long, conflicts_with = "replay")]
+        host: Option<String>,
+        
+        /// Token of the remote agent
+        #[arg(long, requires = "host")]
+        token: Option<String>,
+        
+        /// Show every agent from the `[[hosts]]` section of the config file
+        #[arg(long, conflicts_with_all = ["replay", "host"])]
+        fleet: bool,
+    },
+    
+    /// Serve this machine's metrics over HTTP for `systrix tui --host`
+    #[cfg(feature = "remote")]
+    Agent {
+        /// Address to listen on (default: bind in the [remote] section)
+        #[arg(long)]
+        bind: Option<String>,
+        
+        /// Port to listen on (default: port in the [remote] section)
+        #[arg(long)]
+        port: Option<u16>,
+        
+        /// Token clients must send as `Authorization: Bearer <token>` (default: token in the [remote] section)
+        #[arg(long)]
+        token: Option<String>,
+        
+        /// Maximum number of processes served per snapshot (default: process_limit in the [remote] section)
+        #[arg(long)]
+        process_limit: Option<usize>,
+    },
+    
+    /// Record full snapshots to an NDJSON file for later replay
+    Record {
+        /// Time between snapshots, e.g. 500ms, 1s, 1m
+        #[arg(long, default_value = "1s", value_parser = humantime::parse_duration)]
+        interval: std::time::Duration,
+        
+        /// Stop after this long, e.g. 10m, 2h (default: until Ctrl+C)
+        #[arg(long, value_parser = humantime::parse_duration)]
+        duration: Option<std::time::Duration>,
+        
+        /// Output file
+        #[arg(short, long, default_value = "session.ndjson")]
+        output: PathBuf,
+        
+        /// Maximum number of processes stored per snapshot
+        #[arg(long, default_value = "100")]
+        process_limit: usize,
+    },
+    
+    /// Query metrics and top processes stored on disk
+    #[command(args_conflicts_with_subcommands = true)]
+    History {
+        #[command(subcommand)]
+        command: Option<HistoryCommand>,
+        
+        /// Only this metric, e.g. cpu.global, memory.percent, network.rx_rate, cpu.core.0
+        #[arg(long, conflicts_with = "top")]
+        metric: Option<String>,
+        
+        /// Show the processes that used the most CPU or memory instead
+        #[arg(long, value_enum)]
+        top: Option<HistoryTop>,
+        
+        /// Start of the range: RFC 3339, 'YYYY-MM-DD HH:MM', 'HH:MM' or a duration ago such as 2h
+        #[arg(long, default_value = "1h")]
+        from: String,
+        
+        /// End of the range, in the same forms (default: now)
+        #[arg(long)]
+        to: Option<String>,
+        
+        /// Maximum number of points; longer ranges are averaged down to it
+        #[arg(long, default_value = "60")]
+        points: usize,
+        
+        /// Number of processes shown with --top
+        #[arg(long, default_value = "10")]
+        limit: usize,
+        
+        /// Output format
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    
+    /// Threshold alerts from the [[alerts.rules]] section of the config file
+    Alerts {
+        #[command(subcommand)]
+        command: AlertsCommand,
+    },
+    
+    /// Inspect, create and check the configuration file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
     },
     
     /// Export system report
@@ -73,9 +302,9 @@ pub enum Commands {
     
     /// Export system data
     Export {
-        /// Export format: csv, json
-        #[arg(long, default_value = "json")]
-        format: String,
+        /// Export format: csv, json, html (default: default_format in the [export] section)
+        #[arg(long)]
+        format: Option<String>,
         
         /// Output file path (optional, auto-generated if not provided)
         #[arg(long)]
@@ -94,26 +323,79 @@ pub enum Commands {
     Version,
 }
 
+/// The live system, with GPUs left out when `[monitoring]` disables them
+fn live_backend(monitoring: &crate::config::MonitoringConfig) -> SysinfoBackend {
+    let backend = SysinfoBackend::new();
+    if monitoring.enable_gpu {
+        backend
+    } else {
+        backend.without_gpu()
+    }
+}
+
 /// Execute the CLI command
 pub async fn execute(cli: Cli) -> Result<()> {
     match cli.command {
         Some(Commands::Info) => cmd_info().await,
-        Some(Commands::Ps { sort, filter, limit }) => cmd_ps(sort, filter, limit).await,
-        Some(Commands::Kill { pid, signal, force }) => cmd_kill(pid, signal, force).await,
+        Some(Commands::Ps { sort, order, filter, limit, tree }) => {
+            let config = crate::config::Config::load_or_default(cli.config.as_deref())?;
+            let limit = limit.unwrap_or(config.tui.process_limit);
+            if tree {
+                cmd_ps_tree(filter, limit).await
+            } else {
+                cmd_ps(sort, order, filter, limit).await
+            }
+        }
+        Some(Commands::Inspect { pid, json }) => cmd_inspect(pid, json).await,
+        Some(Commands::Kill { pid, signal, force }) => {
+            let config = crate::config::Config::load_or_default(cli.config.as_deref())?;
+            cmd_kill(pid, signal, force, &config.process_management).await
+        }
         Some(Commands::Net) => cmd_net().await,
-        Some(Commands::Disk) => cmd_disk().await,
+        Some(Commands::Disk { io }) => cmd_disk(io).await,
+        Some(Commands::Gpu { json }) => cmd_gpu(json).await,
+        Some(Commands::Sockets { listen, port }) => cmd_sockets(listen, port).await,
+        Some(Commands::Cgroups { containers, limit }) => cmd_cgroups(containers, limit).await,
         #[cfg(feature = "tui")]
-        Some(Commands::Tui { refresh_interval }) => cmd_tui(refresh_interval).await,
-        Some(Commands::Report { output }) => cmd_report(output).await,
+        Some(Commands::Tui { refresh_interval, replay, backend, host, token, fleet }) => {
+            if fleet {
+                cmd_fleet(refresh_interval, cli.config.as_deref()).await
+            } else {
+                cmd_tui(refresh_interval, replay, backend, host, token, cli.config.as_deref()).await
+            }
+        }
+        #[cfg(feature = "remote")]
+        Some(Commands::Agent { bind, port, token, process_limit }) => {
+            cmd_agent(bind, port, token, process_limit, cli.config.as_deref()).await
+        }
+        Some(Commands::Record { interval, duration, output, process_limit }) => {
+            cmd_record(interval, duration, output, process_limit).await
+        }
+        Some(Commands::History { command: Some(HistoryCommand::Record), .. }) => {
+            cmd_history_record(cli.config.as_deref()).await
+        }
+        Some(Commands::History { command: None, metric, top, from, to, points, limit, format }) => {
+            cmd_history(cli.config.as_deref(), HistoryQuery { metric, top, from, to, points, limit }, format)
+        }
+        Some(Commands::Alerts { command: AlertsCommand::Check { rules, json } }) => {
+            cmd_alerts_check(cli.config.as_deref(), rules, json).await
+        }
+        Some(Commands::Config { command }) => cmd_config(cli.config.as_deref(), command),
+        Some(Commands::Report { output }) => {
+            let config = crate::config::Config::load_or_default(cli.config.as_deref())?;
+            cmd_report(output, &config).await
+        }
         Some(Commands::Export { format, output, processes, process_limit }) => {
-            cmd_export(format, output, processes, process_limit).await
+            let config = crate::config::Config::load_or_default(cli.config.as_deref())?;
+            let format = format.unwrap_or_else(|| config.export.default_format.extension().to_string());
+            cmd_export(format, output, processes, process_limit, &config).await
         },
         Some(Commands::Version) => cmd_version(),
         None => {
             // Default: launch TUI if available, otherwise show help
             #[cfg(feature = "tui")]
             {
-                cmd_tui(500).await
+                cmd_tui(None, None, BackendKind::Sysinfo, None, None, cli.config.as_deref()).await
             }
             #[cfg(not(feature = "tui"))]
             {
@@ -126,10 +408,13 @@ pub async fn execute(cli: Cli) -> Result<()> {
 }
 
 async fn cmd_info() -> Result<()> {
-    let backend = SysinfoBackend::new();
-    let cpu = backend.cpu_snapshot().await?;
-    let memory = backend.memory_snapshot().await?;
-    let disk = backend.disk_snapshot().await?;
+    let snapshot = collect_once(
+        Arc::new(SysinfoBackend::new()),
+        CollectorConfig::default().process_limit(0),
+    ).await;
+    let cpu = snapshot.cpu.ok_or_else(|| anyhow::anyhow!("Failed to collect CPU information"))?;
+    let memory = snapshot.memory.ok_or_else(|| anyhow::anyhow!("Failed to collect memory information"))?;
+    let disk = snapshot.disk.ok_or_else(|| anyhow::anyhow!("Failed to collect disk information"))?;
     
     println!("╔══════════════════════════════════════════════════════════╗");
     println!("║                    SYSTRIX - System Info                 ║");
@@ -150,60 +435,202 @@ async fn cmd_info() -> Result<()> {
     println!("  Total: {}", utils::format_bytes(memory.total));
     println!("  Used: {} ({:.1}%)", utils::format_bytes(memory.used), memory.usage_percent);
     println!("  Available: {}", utils::format_bytes(memory.available));
+    if let Some(detail) = &memory.detail {
+        println!("  Buffers/Cache: {} / {}", utils::format_bytes(detail.buffers), utils::format_bytes(detail.cached));
+        println!("  Slab: {}", utils::format_bytes(detail.slab_reclaimable + detail.slab_unreclaimable));
+        println!("  Committed: {} of {} ({:.0}%)",
+                 utils::format_bytes(detail.committed_as),
+                 utils::format_bytes(detail.commit_limit),
+                 detail.commit_percent());
+    }
     println!();
     println!("Disk:");
     println!("  Total: {}", utils::format_bytes(disk.total));
     println!("  Used: {} ({:.1}%)", utils::format_bytes(disk.used), disk.usage_percent);
     println!("  Available: {}", utils::format_bytes(disk.available));
     
+    if let Some(sensors) = snapshot.sensors.filter(|s| !s.temperatures.is_empty() || !s.fans.is_empty()) {
+        use crate::monitor::sensors::SensorStatus;
+        
+        println!();
+        println!("Sensors:");
+        for temp in &sensors.temperatures {
+            let status = match temp.status() {
+                SensorStatus::Normal => "",
+                SensorStatus::Hot => "  ⚠ HOT",
+                SensorStatus::Critical => "  ⚠ CRITICAL (throttling)",
+            };
+            println!("  {:<30} {:>6.1}°C{}{}",
+                     temp.label,
+                     temp.current,
+                     temp.critical.map(|c| format!("  (crit {:.0}°C)", c)).unwrap_or_default(),
+                     status);
+        }
+        for fan in &sensors.fans {
+            println!("  {:<30} {:>6} RPM{}",
+                     fan.label,
+                     fan.rpm,
+                     if fan.is_stalled() { "  ⚠ STALLED" } else { "" });
+        }
+    }
+    
+    if let Some(pressure) = &snapshot.pressure {
+        println!();
+        println!("Pressure (stall %, avg10 / avg60 / avg300):");
+        for (name, resource) in [("cpu", &pressure.cpu), ("memory", &pressure.memory), ("io", &pressure.io)] {
+            let Some(resource) = resource else {
+                continue;
+            };
+            let line = |l: &crate::monitor::pressure::PressureLine| format!("{:>6.2} / {:>6.2} / {:>6.2}", l.avg10, l.avg60, l.avg300);
+            println!("  {:<7} some {}   full {}",
+                     name,
+                     line(&resource.some),
+                     resource.full.as_ref().map(line).unwrap_or_else(|| "-".to_string()));
+        }
+    }
+    
     Ok(())
 }
 
-async fn cmd_ps(sort: String, filter: Option<String>, limit: usize) -> Result<()> {
+async fn cmd_ps(sort: ProcessSort, order: Option<SortOrder>, filter: Option<String>, limit: usize) -> Result<()> {
     let backend = SysinfoBackend::new();
-    let mut processes = backend.process_list(filter.as_deref(), &sort, limit).await?;
+    let order = order.unwrap_or_else(|| sort.default_order());
     
-    // Sort processes
-    match sort.as_str() {
-        "mem" => processes.sort_by(|a, b| b.memory_usage.partial_cmp(&a.memory_usage).unwrap()),
-        "io" => processes.sort_by(|a, b| (b.disk_read + b.disk_write).partial_cmp(&(a.disk_read + a.disk_write)).unwrap()),
-        _ => processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap()),
-    }
+    // The first sample only establishes a baseline for CPU and IO rates
+    backend.process_list(None, ProcessSort::Pid, SortOrder::Asc, 0).await?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(SAMPLE_INTERVAL_MS)).await;
+    let processes = backend.process_list(filter.as_deref(), sort, order, limit).await?;
     
-    println!("{:<8} {:<10} {:<20} {:>6} {:>6} {:>8} {:>8} {:>8}", 
-             "PID", "USER", "NAME", "CPU%", "MEM%", "IO_R", "IO_W", "THREADS");
-    println!("{}", "─".repeat(80));
+    println!("{:<8} {:<10} {:<20} {:>6} {:>6} {:>10} {:>10} {:>10} {:>10} {:>8}", 
+             "PID", "USER", "NAME", "CPU%", "MEM%", "IO_R/s", "IO_W/s", "IO_R", "IO_W", "THREADS");
+    println!("{}", "─".repeat(110));
     
-    for proc in processes.iter().take(limit) {
-        println!("{:<8} {:<10} {:<20} {:>5.1}% {:>5.1}% {:>8} {:>8} {:>8}",
+    for proc in &processes {
+        println!("{:<8} {:<10} {:<20} {:>5.1}% {:>5.1}% {:>10} {:>10} {:>10} {:>10} {:>8}",
                  proc.pid,
                  proc.user.chars().take(10).collect::<String>(),
                  proc.name.chars().take(20).collect::<String>(),
                  proc.cpu_usage,
                  proc.memory_usage,
+                 format!("{}/s", utils::format_bytes(proc.disk_read_rate)),
+                 format!("{}/s", utils::format_bytes(proc.disk_write_rate)),
                  utils::format_bytes(proc.disk_read),
                  utils::format_bytes(proc.disk_write),
                  proc.threads);
     }
     
     println!();
-    println!("Showing {} of {} processes", processes.len().min(limit), processes.len());
+    println!("Showing {} processes", processes.len());
     
     Ok(())
 }
 
-async fn cmd_kill(pid: u32, signal: String, force: bool) -> Result<()> {
+async fn cmd_ps_tree(filter: Option<String>, limit: usize) -> Result<()> {
+    use crate::monitor::process::ProcessTree;
+    use std::collections::HashSet;
+    
+    let backend = SysinfoBackend::new();
+    
+    // The first sample only establishes a baseline for CPU rates
+    backend.process_list(None, ProcessSort::Pid, SortOrder::Asc, 0).await?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(SAMPLE_INTERVAL_MS)).await;
+    
+    // The tree needs every process, the limit only applies to printed rows
+    let processes = backend.process_list(filter.as_deref(), ProcessSort::Pid, SortOrder::Asc, usize::MAX).await?;
+    let tree = ProcessTree::build(&processes);
+    let rows = tree.flatten(&HashSet::new());
+    
+    println!("{:<8} {:<10} {:>6} {:>6} {:>7} {:>7}  TREE", 
+             "PID", "USER", "CPU%", "MEM%", "ΣCPU%", "ΣMEM%");
+    println!("{}", "─".repeat(80));
+    
+    for row in rows.iter().take(limit) {
+        let proc = &row.node.process;
+        println!("{:<8} {:<10} {:>5.1}% {:>5.1}% {:>6.1}% {:>6.1}%  {}{}",
+                 proc.pid,
+                 proc.user.chars().take(10).collect::<String>(),
+                 proc.cpu_usage,
+                 proc.memory_usage,
+                 row.node.subtree_cpu,
+                 row.node.subtree_memory,
+                 row.prefix,
+                 proc.name);
+    }
+    
+    println!();
+    println!("Showing {} of {} processes", rows.len().min(limit), rows.len());
+    
+    Ok(())
+}
+
+async fn cmd_inspect(pid: u32, json: bool) -> Result<()> {
+    let backend = SysinfoBackend::new();
+    let detail = backend.process_detail(pid).await?;
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&detail)?);
+        return Ok(());
+    }
+    
+    let or_na = |value: &Option<String>| value.clone().unwrap_or_else(|| "N/A".to_string());
+    
+    println!("Process {} ({})", detail.pid, detail.name);
+    println!("  Command: {}", detail.cmdline.join(" "));
+    println!("  Executable: {}", or_na(&detail.exe));
+    println!("  Working dir: {}", or_na(&detail.cwd));
+    println!("  Root: {}", or_na(&detail.root));
+    println!();
+    
+    println!("Environment ({} variables):", detail.environ.len());
+    for (key, value) in &detail.environ {
+        println!("  {}={}", key, value);
+    }
+    println!();
+    
+    println!("Open files ({}):", detail.open_files.len());
+    for file in &detail.open_files {
+        println!("  {:>5}  {}", file.fd, file.target);
+    }
+    println!();
+    
+    let maps = &detail.maps;
+    println!("Memory maps ({} regions, {} mapped):", maps.regions, utils::format_bytes(maps.total_size));
+    println!("  Anonymous: {}", utils::format_bytes(maps.anonymous_size));
+    println!("  Heap: {}", utils::format_bytes(maps.heap_size));
+    println!("  Stack: {}", utils::format_bytes(maps.stack_size));
+    println!("  File-backed: {} in {} files", utils::format_bytes(maps.file_backed_size), maps.files.len());
+    for file in maps.files.iter().take(10) {
+        println!("    {:>10}  {}", utils::format_bytes(file.size), file.path);
+    }
+    println!();
+    
+    let limit = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_else(|| "unlimited".to_string());
+    println!("Limits:");
+    println!("  {:<26} {:>20} {:>20}  UNITS", "LIMIT", "SOFT", "HARD");
+    for l in &detail.limits {
+        println!("  {:<26} {:>20} {:>20}  {}", l.name, limit(l.soft), limit(l.hard), l.units);
+    }
+    
+    if !detail.unavailable.is_empty() {
+        println!();
+        println!("Not readable (try elevated privileges): {}", detail.unavailable.join(", "));
+    }
+    
+    Ok(())
+}
+
+async fn cmd_kill(pid: u32, signal: String, force: bool, protection: &crate::config::ProcessManagementConfig) -> Result<()> {
     use crate::monitor::ProcessManager;
     
     // Safety check: prevent killing critical processes
-    if pid <= 1 && !force {
-        anyhow::bail!("Cannot kill system process (PID {}). Use --force to override (not recommended)", pid);
+    if protection.is_protected(pid) && !force {
+        anyhow::bail!("Cannot kill system process (PID {}), it is protected in [process_management]. Use --force to override (not recommended)", pid);
     }
     
     let backend = SysinfoBackend::new();
     
     // Confirmation
-    if !force {
+    if !force && protection.require_confirmation {
         println!("About to kill process {} with signal {}", pid, signal);
         println!("Continue? (y/N): ");
         let mut input = String::new();
@@ -230,27 +657,99 @@ async fn cmd_kill(pid: u32, signal: String, force: bool) -> Result<()> {
 
 async fn cmd_net() -> Result<()> {
     let backend = SysinfoBackend::new();
+    
+    // The first sample only establishes a baseline for the rate calculation
+    backend.network_snapshot().await?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(SAMPLE_INTERVAL_MS)).await;
     let networks = backend.network_snapshot().await?;
     
     println!("Network Interfaces:");
-    println!("{:<15} {:>12} {:>12} {:>12} {:>12}", 
-             "INTERFACE", "RX_BYTES", "TX_BYTES", "RX_RATE", "TX_RATE");
-    println!("{}", "─".repeat(70));
+    println!("{:<15} {:>12} {:>12} {:>12} {:>12} {:>10} {:>10} {:>8}", 
+             "INTERFACE", "RX_BYTES", "TX_BYTES", "RX_RATE", "TX_RATE", "RX_PKT/s", "TX_PKT/s", "ERR/s");
+    println!("{}", "─".repeat(100));
     
     for net in &networks.interfaces {
-        println!("{:<15} {:>12} {:>12} {:>12} {:>12}",
+        println!("{:<15} {:>12} {:>12} {:>12} {:>12} {:>10.1} {:>10.1} {:>8.1}",
                  net.name,
                  utils::format_bytes(net.received),
                  utils::format_bytes(net.transmitted),
                  format!("{}/s", utils::format_bytes(net.rx_rate)),
-                 format!("{}/s", utils::format_bytes(net.tx_rate)));
+                 format!("{}/s", utils::format_bytes(net.tx_rate)),
+                 net.packets_rx_rate,
+                 net.packets_tx_rate,
+                 net.errors_rx_rate + net.errors_tx_rate);
+    }
+    
+    Ok(())
+}
+
+async fn cmd_cgroups(containers_only: bool, limit: usize) -> Result<()> {
+    use crate::monitor::cgroup::ContainerRuntime;
+    use std::collections::HashMap;
+    
+    let backend = SysinfoBackend::new();
+    
+    // CPU usage is derived from the usage counter of two samples
+    let before: HashMap<String, u64> = backend.cgroups().await?
+        .into_iter()
+        .filter_map(|cg| Some((cg.path, cg.cpu?.usage_usec)))
+        .collect();
+    tokio::time::sleep(tokio::time::Duration::from_millis(SAMPLE_INTERVAL_MS)).await;
+    let elapsed_usec = (SAMPLE_INTERVAL_MS * 1000) as f64;
+    
+    let mut cgroups: Vec<_> = backend.cgroups().await?
+        .into_iter()
+        .filter(|cg| !containers_only || cg.container.as_ref().is_some_and(|c| c.runtime != ContainerRuntime::Systemd))
+        .map(|cg| {
+            let cpu_percent = match (&cg.cpu, before.get(&cg.path)) {
+                (Some(cpu), Some(&previous)) => cpu.usage_usec.saturating_sub(previous) as f64 / elapsed_usec * 100.0,
+                _ => 0.0,
+            };
+            (cg, cpu_percent)
+        })
+        .collect();
+    cgroups.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.path.cmp(&b.0.path)));
+    
+    println!("{:<28} {:>6} {:>7} {:>10} {:>10} {:>10} {:>9} {:>17}  CGROUP", 
+             "CONTAINER", "PROCS", "CPU%", "MEMORY", "IO_READ", "IO_WRITE", "THROTTLED", "PSI CPU/MEM/IO");
+    println!("{}", "─".repeat(128));
+    
+    let or_dash = |value: Option<u64>| value.map(utils::format_bytes).unwrap_or_else(|| "-".to_string());
+    
+    // Share of time some task was stalled over the last 10 seconds
+    let psi = |pressure: Option<&crate::monitor::pressure::Pressure>| pressure
+        .map(|p| format!("{:.1}", p.some.avg10))
+        .unwrap_or_else(|| "-".to_string());
+    
+    for (cg, cpu_percent) in cgroups.iter().take(limit) {
+        let pressure = cg.pressure.as_ref()
+            .map(|p| format!("{}/{}/{}", psi(p.cpu.as_ref()), psi(p.memory.as_ref()), psi(p.io.as_ref())))
+            .unwrap_or_else(|| "-".to_string());
+        println!("{:<28} {:>6} {:>6.1}% {:>10} {:>10} {:>10} {:>9} {:>17}  {}",
+                 cg.container.as_ref().map(|c| c.to_string()).unwrap_or_else(|| "-".to_string()).chars().take(28).collect::<String>(),
+                 cg.processes,
+                 cpu_percent,
+                 or_dash(cg.memory_current),
+                 or_dash(cg.io.as_ref().map(|io| io.read_bytes)),
+                 or_dash(cg.io.as_ref().map(|io| io.write_bytes)),
+                 cg.cpu.as_ref().map(|cpu| cpu.nr_throttled.to_string()).unwrap_or_else(|| "-".to_string()),
+                 pressure,
+                 cg.path);
     }
     
+    println!();
+    println!("Showing {} of {} cgroups", cgroups.len().min(limit), cgroups.len());
+    
     Ok(())
 }
 
-async fn cmd_disk() -> Result<()> {
+async fn cmd_disk(io: bool) -> Result<()> {
     let backend = SysinfoBackend::new();
+    
+    if io {
+        return cmd_disk_io(&backend).await;
+    }
+    
     let disks = backend.disk_list().await?;
     
     println!("Disk Partitions:");
@@ -271,40 +770,639 @@ async fn cmd_disk() -> Result<()> {
     Ok(())
 }
 
+async fn cmd_disk_io(backend: &SysinfoBackend) -> Result<()> {
+    // Rates are the difference between two samples
+    backend.disk_io().await?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(SAMPLE_INTERVAL_MS)).await;
+    let devices = backend.disk_io().await?;
+    
+    println!("Disk I/O:");
+    println!("{:<12} {:>12} {:>12} {:>8} {:>8} {:>9} {:>7} {:>6}  MOUNTS",
+             "DEVICE", "READ/s", "WRITE/s", "R_IOPS", "W_IOPS", "AWAIT", "QUEUE", "UTIL%");
+    println!("{}", "─".repeat(100));
+    
+    for device in &devices {
+        println!("{:<12} {:>12} {:>12} {:>8.1} {:>8.1} {:>7.2}ms {:>7.2} {:>5.1}%  {}",
+                 device.device,
+                 utils::format_bytes(device.read_bytes_per_sec),
+                 utils::format_bytes(device.write_bytes_per_sec),
+                 device.read_iops,
+                 device.write_iops,
+                 device.await_ms,
+                 device.queue_depth,
+                 device.utilization,
+                 device.mount_points.join(", "));
+    }
+    
+    Ok(())
+}
+
+async fn cmd_gpu(json: bool) -> Result<()> {
+    let backend = SysinfoBackend::new();
+    
+    // Some drivers only expose busy time, so utilization needs two samples
+    backend.gpu_list().await?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(SAMPLE_INTERVAL_MS)).await;
+    let gpus = backend.gpu_list().await?;
+    
+    if json {
+        println!("{}", serde_json::to_string_pretty(&gpus)?);
+        return Ok(());
+    }
+    
+    if gpus.is_empty() {
+        println!("No GPUs found");
+        return Ok(());
+    }
+    
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    
+    println!("GPUs:");
+    println!("{:<4} {:<32} {:<8} {:>6} {:>21} {:>9} {:>9} {:>7} {:>8}",
+             "IDX", "NAME", "DRIVER", "UTIL%", "MEMORY", "CORE", "MEM CLK", "TEMP", "POWER");
+    println!("{}", "─".repeat(112));
+    
+    for gpu in &gpus {
+        let memory = match (gpu.memory_used, gpu.memory_total) {
+            (Some(used), Some(total)) => format!("{} / {}", utils::format_bytes(used), utils::format_bytes(total)),
+            _ => "-".to_string(),
+        };
+        println!("{:<4} {:<32} {:<8} {:>6} {:>21} {:>9} {:>9} {:>7} {:>8}",
+                 gpu.index,
+                 gpu.name.chars().take(32).collect::<String>(),
+                 gpu.driver,
+                 or_dash(gpu.utilization.map(|u| format!("{:.0}%", u))),
+                 memory,
+                 or_dash(gpu.core_clock_mhz.map(|mhz| format!("{} MHz", mhz))),
+                 or_dash(gpu.memory_clock_mhz.map(|mhz| format!("{} MHz", mhz))),
+                 or_dash(gpu.temperature.map(|t| format!("{:.0}°C", t))),
+                 or_dash(gpu.power_watts.map(|w| format!("{:.1} W", w))));
+    }
+    
+    Ok(())
+}
+
+async fn cmd_sockets(listen: bool, port: Option<u16>) -> Result<()> {
+    let backend = SysinfoBackend::new();
+    
+    let sockets: Vec<_> = backend.sockets().await?
+        .into_iter()
+        .filter(|s| !listen || s.is_listening())
+        .filter(|s| port.map_or(true, |port| s.local_port() == Some(port) || s.remote_port() == Some(port)))
+        .collect();
+    
+    println!("{:<6} {:<12} {:<40} {:<40} PROCESS", "PROTO", "STATE", "LOCAL", "REMOTE");
+    println!("{}", "─".repeat(120));
+    
+    for socket in &sockets {
+        println!("{:<6} {:<12} {:<40} {:<40} {}",
+                 socket.protocol,
+                 socket.state,
+                 socket.local_display(),
+                 socket.remote_display(),
+                 socket.owner_display());
+    }
+    
+    println!();
+    println!("Showing {} sockets", sockets.len());
+    if sockets.iter().any(|s| s.pid.is_none()) {
+        println!("Owners of sockets held by other users' processes are only visible as root");
+    }
+    
+    Ok(())
+}
+
 #[cfg(feature = "tui")]
-async fn cmd_tui(refresh_interval: u64) -> Result<()> {
+async fn cmd_tui(refresh_interval: Option<u64>, replay: Option<PathBuf>, backend: BackendKind, host: Option<String>, token: Option<String>, config_path: Option<&std::path::Path>) -> Result<()> {
     use crate::app::App;
+    use crate::config::Config;
+    use crate::monitor::ProcfsBackend;
+    
+    let config = Config::load_or_default(config_path)?;
     
     // Clamp refresh interval to minimum 100ms
-    let refresh_interval = refresh_interval.max(100);
+    let refresh_interval_fixed = refresh_interval.is_some();
+    let refresh_interval = refresh_interval
+        .unwrap_or(config.general.refresh_interval_ms)
+        .max(crate::config::MIN_REFRESH_INTERVAL_MS);
     
-    let mut app = App::new(refresh_interval)?;
+    let mut app = match (replay, host, backend) {
+        (Some(path), _, _) => App::replay(&path, refresh_interval)?,
+        #[cfg(feature = "remote")]
+        (None, Some(host), _) => App::remote(&host, token, refresh_interval)?,
+        #[cfg(not(feature = "remote"))]
+        (None, Some(_), _) => {
+            let _ = token;
+            anyhow::bail!("--host needs systrix built with the `remote` feature")
+        }
+        (None, None, BackendKind::Sysinfo) => App::new(Arc::new(live_backend(&config.monitoring)), refresh_interval)?,
+        (None, None, BackendKind::Procfs) => {
+            let backend = ProcfsBackend::new();
+            let backend = if config.monitoring.enable_gpu { backend } else { backend.without_gpu() };
+            App::new(Arc::new(backend), refresh_interval)?
+        }
+    }
+    .with_config(config)?;
+    if let Some(file) = Config::file_in_use(config_path) {
+        app = app.watch_config(file, refresh_interval_fixed);
+    }
     app.run().await?;
     
     Ok(())
 }
 
-async fn cmd_report(output: PathBuf) -> Result<()> {
-    // Legacy report command - use new export functionality
-    cmd_export("json".to_string(), Some(output), true, 50).await
+#[cfg(feature = "tui")]
+async fn cmd_fleet(refresh_interval: Option<u64>, config_path: Option<&std::path::Path>) -> Result<()> {
+    use crate::config::Config;
+    
+    let config = Config::load_or_default(config_path)?;
+    if config.hosts.is_empty() {
+        anyhow::bail!("no [[hosts]] in the config file; add one per agent with its url and token");
+    }
+    
+    #[cfg(feature = "remote")]
+    {
+        let refresh_interval_fixed = refresh_interval.is_some();
+        let refresh_interval = refresh_interval
+            .unwrap_or(config.general.refresh_interval_ms)
+            .max(crate::config::MIN_REFRESH_INTERVAL_MS);
+        // Edits apply to the shown hosts' panels; the [[hosts]] list itself is read once
+        let mut app = crate::app::App::fleet(&config.hosts, refresh_interval)?
+            .with_config(config)?;
+        if let Some(file) = Config::file_in_use(config_path) {
+            app = app.watch_config(file, refresh_interval_fixed);
+        }
+        app.run().await
+    }
+    #[cfg(not(feature = "remote"))]
+    {
+        let _ = (refresh_interval, config_path);
+        anyhow::bail!("--fleet needs systrix built with the `remote` feature")
+    }
 }
 
-async fn cmd_export(format: String, output: Option<PathBuf>, include_processes: bool, process_limit: usize) -> Result<()> {
-    use crate::export::{export_snapshot, ExportFormat};
+#[cfg(feature = "remote")]
+async fn cmd_agent(bind: Option<String>, port: Option<u16>, token: Option<String>, process_limit: Option<usize>, config_path: Option<&std::path::Path>) -> Result<()> {
+    use crate::config::{Config, ConfigWatcher};
+    use crate::remote_agent::{RemoteAgent, RemoteConfig};
     
-    let backend = SysinfoBackend::new();
-    let cpu = Some(backend.cpu_snapshot().await?);
-    let memory = Some(backend.memory_snapshot().await?);
-    let disk = Some(backend.disk_snapshot().await?);
-    let network = Some(backend.network_snapshot().await?);
-    let battery = crate::monitor::battery::get_battery_info().await.ok();
-    let disk_list = backend.disk_list().await?;
-    
-    let processes = if include_processes {
-        backend.process_list(None, "cpu", process_limit).await?
+    let config = Config::load_or_default(config_path)?;
+    let bind = bind.unwrap_or_else(|| config.remote.bind.clone());
+    let port = port.unwrap_or(config.remote.port);
+    let token = token.or_else(|| config.remote.token.clone())
+        .ok_or_else(|| anyhow::anyhow!("no agent token: pass --token or set token in the [remote] section"))?;
+    if token.is_empty() {
+        anyhow::bail!("the agent token must not be empty");
+    }
+    
+    // A limit given on the command line stays when the file changes
+    let schedule = |config: &Config| (config.general.refresh_interval_ms, process_limit.unwrap_or(config.remote.process_limit));
+    let (refresh_interval_ms, limit) = schedule(&config);
+    
+    println!("Serving metrics on http://{}:{} (Ctrl+C to stop)", bind, port);
+    let agent = RemoteAgent::with_backend(
+        Arc::new(live_backend(&config.monitoring)),
+        RemoteConfig { bind, port, token, refresh_interval_ms, process_limit: limit, enable_cors: config.remote.enable_cors },
+    );
+    apply_agent_config(&agent, &config, None)?;
+    
+    // The schedule, alert rules and history settings follow edits of the file; the rest needs a restart
+    let watch = async {
+        let Some(file) = Config::file_in_use(config_path) else {
+            return std::future::pending().await;
+        };
+        let mut watcher = ConfigWatcher::new(file);
+        let mut current = config;
+        let mut ticker = tokio::time::interval(crate::config::RELOAD_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let Some(loaded) = watcher.poll() else {
+                continue;
+            };
+            match loaded.and_then(|config| apply_agent_config(&agent, &config, Some(&current)).map(|_| config)) {
+                Ok(config) => {
+                    if schedule(&config) != schedule(&current) {
+                        let (refresh_interval_ms, limit) = schedule(&config);
+                        agent.set_schedule(refresh_interval_ms, limit);
+                    }
+                    let pending = agent_restart_keys(&current, &config);
+                    match pending.is_empty() {
+                        true => tracing::info!("Reloaded {}", watcher.path().display()),
+                        false => tracing::warn!("Reloaded {}, but changes to {} only take effect after restarting the agent", watcher.path().display(), pending.join(", ")),
+                    }
+                    current = config;
+                }
+                Err(e) => tracing::warn!("Keeping the previous settings: {:#}", e),
+            }
+        }
+    };
+    
+    tokio::select! {
+        result = agent.start() => result,
+        _ = tokio::signal::ctrl_c() => Ok(()),
+        _ = watch => Ok(()),
+    }
+}
+
+/// Start, restart or stop the history recording and alert evaluation of the
+/// agent where `config` differs from `previous`. Nothing changes on an error.
+#[cfg(feature = "remote")]
+fn apply_agent_config(agent: &crate::remote_agent::RemoteAgent, config: &crate::config::Config, previous: Option<&crate::config::Config>) -> Result<()> {
+    let history = &config.history;
+    let history_changed = previous.map(|p| &p.history) != Some(history);
+    let alerts_changed = previous.map(|p| &p.alerts) != Some(&config.alerts);
+    
+    // Everything that can fail comes first
+    let store = match history_changed && history.enabled {
+        true => Some(crate::monitor::MetricsStore::open(history.store_config()?)?),
+        false => None,
+    };
+    let notifier = match alerts_changed && !config.alerts.rules.is_empty() {
+        true => Some(crate::alerts::Notifier::from_config(&config.alerts)?),
+        false => None,
+    };
+    
+    if history_changed {
+        match store {
+            Some(store) => {
+                println!("Recording history to {}", store.config().dir.display());
+                agent.record_history(store, history.interval, history.process_limit);
+            }
+            None => agent.stop_recording_history(),
+        }
+    }
+    if alerts_changed {
+        match notifier {
+            Some(notifier) => {
+                println!("Evaluating {} alert rules, notifying {} sinks", config.alerts.rules.len(), config.alerts.sinks.len());
+                agent.watch_alerts(config.alerts.rules.clone(), Arc::new(notifier));
+            }
+            None => agent.stop_watching_alerts(),
+        }
+    }
+    Ok(())
+}
+
+/// Keys read only when the agent starts that differ between `previous` and `config`
+#[cfg(feature = "remote")]
+fn agent_restart_keys(previous: &crate::config::Config, config: &crate::config::Config) -> Vec<&'static str> {
+    let (old, new) = (&previous.remote, &config.remote);
+    [
+        ("remote.bind", old.bind != new.bind),
+        ("remote.port", old.port != new.port),
+        ("remote.token", old.token != new.token),
+        ("remote.enable_cors", old.enable_cors != new.enable_cors),
+        ("monitoring.enable_gpu", previous.monitoring != config.monitoring),
+    ]
+    .into_iter()
+    .filter_map(|(key, changed)| changed.then_some(key))
+    .collect()
+}
+
+async fn cmd_record(interval: std::time::Duration, duration: Option<std::time::Duration>, output: PathBuf, process_limit: usize) -> Result<()> {
+    use crate::monitor::replay::{Frame, RecordingHeader, RecordingWriter};
+    use crate::monitor::Collector;
+    
+    let interval = interval.max(std::time::Duration::from_millis(100));
+    let file = std::fs::File::create(&output)
+        .map_err(|e| anyhow::anyhow!("failed to create {}: {}", output.display(), e))?;
+    let hostname = sysinfo::System::host_name();
+    let mut writer = RecordingWriter::new(std::io::BufWriter::new(file), &RecordingHeader::new(hostname, interval))?;
+    
+    let mut collector = Collector::spawn(
+        Arc::new(SysinfoBackend::new()),
+        CollectorConfig::with_refresh_interval(interval.as_millis() as u64).process_limit(process_limit),
+    );
+    collector.wait_ready().await;
+    
+    println!("Recording to {} every {} (Ctrl+C to stop)", output.display(), humantime::format_duration(interval));
+    
+    let deadline = duration.map(|d| tokio::time::Instant::now() + d);
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+        if deadline.is_some_and(|deadline| tokio::time::Instant::now() >= deadline) {
+            break;
+        }
+        writer.write_frame(&Frame {
+            timestamp: chrono::Utc::now(),
+            snapshot: collector.latest(),
+        })?;
+    }
+    
+    println!("Recorded {} snapshots to {}", writer.frames(), output.display());
+    
+    Ok(())
+}
+
+/// What `systrix history` prints
+struct HistoryQuery {
+    metric: Option<String>,
+    top: Option<HistoryTop>,
+    from: String,
+    to: Option<String>,
+    points: usize,
+    limit: usize,
+}
+
+fn cmd_history(config: Option<&std::path::Path>, query: HistoryQuery, format: OutputFormat) -> Result<()> {
+    use crate::monitor::store::{self, parse_time};
+    use crate::monitor::{MetricsHistory, MetricsStore};
+    
+    let config = crate::config::Config::load_or_default(config)?;
+    let store_config = config.history.store_config()?;
+    let now = chrono::Utc::now();
+    let from = parse_time(&query.from, now)?;
+    let to = query.to.as_deref().map(|to| parse_time(to, now)).transpose()?.unwrap_or(now);
+    if from >= to {
+        anyhow::bail!("--from must be before --to");
+    }
+    
+    if !store_config.dir.exists() {
+        anyhow::bail!("no history in {}; enable [history] in the config file or run `systrix history record`",
+                      store_config.dir.display());
+    }
+    let records = MetricsStore::open(store_config)?.query(from, to)?;
+    let time = |t: chrono::DateTime<chrono::Utc>| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string();
+    
+    if let Some(top) = query.top {
+        let sort = match top {
+            HistoryTop::Cpu => ProcessSort::Cpu,
+            HistoryTop::Mem => ProcessSort::Mem,
+        };
+        let processes = store::top_processes(&records, sort, query.limit);
+        match format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&processes)?),
+            OutputFormat::Csv => {
+                println!("pid,name,user,avg_cpu,max_cpu,avg_memory,max_memory,samples,first_seen,last_seen");
+                for p in &processes {
+                    println!("{},{},{},{:.2},{:.2},{:.2},{:.2},{},{},{}",
+                             p.pid, csv_field(&p.name), csv_field(&p.user),
+                             p.avg_cpu, p.max_cpu, p.avg_memory, p.max_memory,
+                             p.samples, p.first_seen.to_rfc3339(), p.last_seen.to_rfc3339());
+                }
+            }
+            OutputFormat::Table => {
+                println!("Top processes by {} from {} to {} ({} snapshots)",
+                         if sort == ProcessSort::Mem { "memory" } else { "CPU" }, time(from), time(to), records.len());
+                println!("{:<8} {:<10} {:<20} {:>8} {:>8} {:>8} {:>8} {:>8}",
+                         "PID", "USER", "NAME", "AVG_CPU%", "MAX_CPU%", "AVG_MEM%", "MAX_MEM%", "SEEN");
+                println!("{}", "─".repeat(86));
+                for p in &processes {
+                    println!("{:<8} {:<10} {:<20} {:>7.1}% {:>7.1}% {:>7.1}% {:>7.1}% {:>8}",
+                             p.pid,
+                             p.user.chars().take(10).collect::<String>(),
+                             p.name.chars().take(20).collect::<String>(),
+                             p.avg_cpu, p.max_cpu, p.avg_memory, p.max_memory, p.samples);
+                }
+            }
+        }
+        return Ok(());
+    }
+    
+    // Average the range down to at most `points` samples
+    let mut history = MetricsHistory::new(records.len());
+    for record in &records {
+        history.push(record.metrics.clone());
+    }
+    let span = history.latest()
+        .map(|last| (last.timestamp - from).to_std().unwrap_or_default() + std::time::Duration::from_millis(1))
+        .unwrap_or_default();
+    let samples = history.window(span, query.points.max(1));
+    
+    if let Some(metric) = &query.metric {
+        let points: Vec<_> = samples.iter()
+            .filter_map(|s| Some((s.timestamp, s.metric(metric)?)))
+            .collect();
+        match format {
+            OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&serde_json::json!({
+                "metric": metric,
+                "points": points,
+            }))?),
+            OutputFormat::Csv => {
+                println!("timestamp,{}", metric);
+                for (timestamp, value) in &points {
+                    println!("{},{}", timestamp.to_rfc3339(), value);
+                }
+            }
+            OutputFormat::Table => {
+                println!("{:<20} {:>12}", "TIME", metric);
+                println!("{}", "─".repeat(33));
+                for (timestamp, value) in &points {
+                    println!("{:<20} {:>12.2}", time(*timestamp), value);
+                }
+                if points.is_empty() {
+                    println!("No values for {} between {} and {}", metric, time(from), time(to));
+                }
+            }
+        }
+        return Ok(());
+    }
+    
+    let or_dash = |value: Option<f32>| value.map(|v| format!("{:.1}%", v)).unwrap_or_else(|| "-".to_string());
+    let rate = |value: Option<u64>| value.map(|v| format!("{}/s", utils::format_bytes(v))).unwrap_or_else(|| "-".to_string());
+    let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&samples)?),
+        OutputFormat::Csv => {
+            println!("timestamp,cpu,memory_percent,swap_percent,disk_percent,network_rx_rate,network_tx_rate");
+            for s in &samples {
+                println!("{},{},{},{},{},{},{}",
+                         s.timestamp.to_rfc3339(),
+                         number(s.metric("cpu.global")),
+                         number(s.metric("memory.percent")),
+                         number(s.metric("swap.percent")),
+                         number(s.metric("disk.percent")),
+                         number(s.metric("network.rx_rate")),
+                         number(s.metric("network.tx_rate")));
+            }
+        }
+        OutputFormat::Table => {
+            println!("{:<20} {:>7} {:>7} {:>7} {:>7} {:>12} {:>12}",
+                     "TIME", "CPU%", "MEM%", "SWAP%", "DISK%", "RX/s", "TX/s");
+            println!("{}", "─".repeat(78));
+            for s in &samples {
+                println!("{:<20} {:>7} {:>7} {:>7} {:>7} {:>12} {:>12}",
+                         time(s.timestamp),
+                         or_dash(s.cpu),
+                         or_dash(s.memory_percent),
+                         or_dash(s.swap_percent),
+                         or_dash(s.disk_percent),
+                         rate(s.network_rx_rate),
+                         rate(s.network_tx_rate));
+            }
+            println!();
+            println!("{} snapshots from {} to {}, shown as {} points", records.len(), time(from), time(to), samples.len());
+        }
+    }
+    
+    Ok(())
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
     } else {
-        Vec::new()
+        value.to_string()
+    }
+}
+
+async fn cmd_history_record(config: Option<&std::path::Path>) -> Result<()> {
+    use crate::monitor::store::spawn_recorder;
+    use crate::monitor::{Collector, MetricsStore};
+    
+    let config = crate::config::Config::load_or_default(config)?;
+    let history = config.history;
+    let store = MetricsStore::open(history.store_config()?)?;
+    let dir = store.config().dir.clone();
+    
+    let mut collector = Collector::spawn(
+        Arc::new(live_backend(&config.monitoring)),
+        CollectorConfig::with_refresh_interval(1000).process_limit(usize::MAX),
+    );
+    collector.wait_ready().await;
+    let collector = Arc::new(collector);
+    
+    println!("Recording history to {} every {} (Ctrl+C to stop)", dir.display(), humantime::format_duration(history.interval));
+    let recorder = spawn_recorder(store, collector, history.interval, history.process_limit);
+    tokio::signal::ctrl_c().await?;
+    recorder.abort();
+    
+    Ok(())
+}
+
+async fn cmd_alerts_check(config: Option<&std::path::Path>, rules: Vec<crate::alerts::RuleExpr>, json: bool) -> Result<()> {
+    use crate::alerts::AlertRule;
+    
+    let rules = if rules.is_empty() {
+        crate::config::Config::load_or_default(config)?.alerts.rules
+    } else {
+        rules.into_iter().map(AlertRule::new).collect()
     };
+    if rules.is_empty() {
+        anyhow::bail!("no alert rules; add [[alerts.rules]] to the config file or pass --rule");
+    }
+    
+    // The first process sample only establishes a baseline for CPU rates
+    let backend = Arc::new(SysinfoBackend::new());
+    backend.process_list(None, ProcessSort::Pid, SortOrder::Asc, 0).await?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(SAMPLE_INTERVAL_MS)).await;
+    let snapshot = collect_once(backend, CollectorConfig::default().process_limit(usize::MAX)).await;
+    
+    // A single look cannot tell how long a condition has held, so rules with
+    // a duration are only reported as having their condition met
+    let results: Vec<_> = rules.iter()
+        .map(|rule| {
+            let value = rule.expr.value(&snapshot);
+            (rule, value, value.is_some_and(|v| rule.expr.holds(v)))
+        })
+        .collect();
+    let firing = results.iter().filter(|(rule, _, met)| *met && rule.expr.duration.is_zero()).count();
+    let met = results.iter().filter(|(rule, _, met)| *met && !rule.expr.duration.is_zero()).count();
+    
+    if json {
+        let results: Vec<_> = results.iter()
+            .map(|(rule, value, met)| serde_json::json!({
+                "rule": rule.display_name(),
+                "expr": rule.expr.to_string(),
+                "severity": rule.severity,
+                "value": value,
+                "condition_met": met,
+                "duration_evaluated": rule.expr.duration.is_zero(),
+                "firing": *met && rule.expr.duration.is_zero(),
+            }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&results)?);
+    } else {
+        println!("{:<24} {:<9} {:>10} {:<8} EXPRESSION", "RULE", "SEVERITY", "VALUE", "STATUS");
+        println!("{}", "─".repeat(90));
+        for (rule, value, met) in &results {
+            let status = match (value, met) {
+                (None, _) => "no data",
+                (Some(_), true) if rule.expr.duration.is_zero() => "FIRING",
+                (Some(_), true) => "MET*",
+                (Some(_), false) => "ok",
+            };
+            println!("{:<24} {:<9} {:>10} {:<8} {}",
+                     rule.display_name().chars().take(24).collect::<String>(),
+                     rule.severity,
+                     value.map(|v| format!("{:.2}", v)).unwrap_or_else(|| "-".to_string()),
+                     status,
+                     rule.expr);
+        }
+        println!();
+        println!("{} of {} rules firing", firing, results.len());
+        if met > 0 {
+            println!("* {} rule(s) with their condition met (duration not evaluated)", met);
+        }
+    }
+    
+    match (firing, met) {
+        (0, 0) => {}
+        (_, 0) => anyhow::bail!("{} alert rule(s) firing", firing),
+        _ => anyhow::bail!("{} alert rule(s) firing, {} with their condition met (duration not evaluated)", firing, met),
+    }
+    Ok(())
+}
+
+fn cmd_config(path: Option<&std::path::Path>, command: ConfigCommand) -> Result<()> {
+    use crate::config::Config;
+    
+    let file = path.map(PathBuf::from).or_else(Config::default_path)
+        .ok_or_else(|| anyhow::anyhow!("cannot locate the configuration directory; set XDG_CONFIG_HOME or pass --config"))?;
+    
+    match command {
+        ConfigCommand::Show => print!("{}", Config::load_or_default(path)?.to_masked_toml()?),
+        ConfigCommand::Path => {
+            println!("{}", file.display());
+            if !file.exists() {
+                eprintln!("(does not exist; the built-in defaults are used)");
+            }
+        }
+        ConfigCommand::Init { force } => {
+            if file.exists() && !force {
+                anyhow::bail!("{} already exists; pass --force to overwrite it", file.display());
+            }
+            if let Some(dir) = file.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)
+                    .map_err(|e| anyhow::anyhow!("failed to create {}: {}", dir.display(), e))?;
+            }
+            std::fs::write(&file, crate::config::DEFAULT_FILE)
+                .map_err(|e| anyhow::anyhow!("failed to write {}: {}", file.display(), e))?;
+            println!("✓ Wrote {}", file.display());
+        }
+        ConfigCommand::Validate => {
+            let config = Config::load_or_default(path)?;
+            // Sinks are only built when alerts are delivered, so check them here too
+            crate::alerts::Notifier::from_config(&config.alerts)
+                .map_err(|e| anyhow::anyhow!("alerts.sinks: {:#}", e))?;
+            if file.exists() {
+                println!("✓ {} is valid", file.display());
+            } else {
+                println!("✓ No file at {}; the built-in defaults are valid", file.display());
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+async fn cmd_report(output: PathBuf, config: &crate::config::Config) -> Result<()> {
+    // Legacy report command - use new export functionality
+    cmd_export("json".to_string(), Some(output), true, 50, config).await
+}
+
+async fn cmd_export(format: String, output: Option<PathBuf>, include_processes: bool, process_limit: usize, config: &crate::config::Config) -> Result<()> {
+    use crate::export::{export_snapshot, ExportFormat};
+    
+    let snapshot = collect_once(
+        Arc::new(live_backend(&config.monitoring)),
+        CollectorConfig::default().process_limit(if include_processes { process_limit } else { 0 }),
+    ).await;
     
     let export_format = match format.to_lowercase().as_str() {
         "csv" => ExportFormat::Csv,
@@ -318,17 +1416,7 @@ async fn cmd_export(format: String, output: Option<PathBuf>, include_processes:
     
     let output_path = output.map(|p| p.to_string_lossy().to_string());
     
-    match export_snapshot(
-        &cpu,
-        &memory,
-        &disk,
-        &network,
-        &battery,
-        &processes,
-        &disk_list,
-        export_format,
-        output_path.as_deref(),
-    ) {
+    match export_snapshot(&snapshot, export_format, output_path.as_deref(), config.export.include_timestamps) {
         Ok(filename) => {
             // Get full path
             let full_path = std::env::current_dir()
@@ -340,8 +1428,8 @@ async fn cmd_export(format: String, output: Option<PathBuf>, include_processes:
             println!("📁 Filename: {}", filename);
             println!("📂 Location: {}", full_path);
             println!("📊 Format: {}", format.to_uppercase());
-            println!("🔢 Processes: {}", processes.len());
-            if let Some(battery) = &battery {
+            println!("🔢 Processes: {}", snapshot.processes.len());
+            if let Some(battery) = &snapshot.battery {
                 if battery.is_present {
                     println!("🔋 Battery: Included");
                 }
@@ -0,0 +1,836 @@
+// src/config.rs
+//! Configuration file loading.
+//!
+//! The file is TOML, by default `$XDG_CONFIG_HOME/systrix/config.toml`
+//! (falling back to `~/.config/systrix/config.toml`); `--config` overrides it.
+//! See `config/default.toml` for the documented settings.
+//!
+//! Keys of the plain sections can be overridden from the environment as
+//! `SYSTRIX_<SECTION>_<KEY>`, e.g. `SYSTRIX_GENERAL_REFRESH_INTERVAL_MS=1000`
+//! or `SYSTRIX_REMOTE_TOKEN=secret`.
+//!
+//! The TUI and the agent poll the file in use with a [`ConfigWatcher`] and
+//! apply edits while running; an edit that does not load is reported and the
+//! previous settings stay in effect.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::alerts::notify::SinkConfig;
+use crate::alerts::AlertRule;
+use crate::export::ExportFormat;
+use crate::monitor::store::{self, StoreConfig};
+
+/// The documented configuration written by `systrix config init`
+pub const DEFAULT_FILE: &str = include_str!("../config/default.toml");
+
+/// Prefix of the environment variables that override settings
+const ENV_PREFIX: &str = "SYSTRIX_";
+
+/// Sections whose keys can be set from the environment
+const ENV_SECTIONS: [&str; 7] = ["general", "tui", "monitoring", "remote", "process_management", "export", "history"];
+
+/// Lowest refresh interval the collector is run at
+pub const MIN_REFRESH_INTERVAL_MS: u64 = 100;
+
+/// How often a running TUI or agent checks the file for edits
+pub const RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Settings read from the configuration file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    pub general: GeneralConfig,
+    
+    #[serde(default)]
+    pub tui: TuiConfig,
+    
+    #[serde(default)]
+    pub monitoring: MonitoringConfig,
+    
+    /// Defaults of `systrix agent`
+    #[serde(default)]
+    pub remote: RemoteSettings,
+    
+    #[serde(default)]
+    pub process_management: ProcessManagementConfig,
+    
+    #[serde(default)]
+    pub export: ExportConfig,
+    
+    /// On-disk metrics history
+    #[serde(default)]
+    pub history: HistoryConfig,
+    
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+    
+    /// Agents shown on the fleet dashboard
+    #[serde(default)]
+    pub hosts: Vec<HostConfig>,
+}
+
+/// The `[general]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneralConfig {
+    /// Time between two samples of the TUI and the agent, in milliseconds
+    pub refresh_interval_ms: u64,
+    pub theme: ThemeName,
+    /// Used unless `RUST_LOG` is set
+    pub log_level: LogLevel,
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            refresh_interval_ms: 500,
+            theme: ThemeName::Dark,
+            log_level: LogLevel::Info,
+        }
+    }
+}
+
+/// Color theme the TUI starts with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    Dracula,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        })
+    }
+}
+
+/// The `[tui]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TuiConfig {
+    /// CPU history graph on the overview
+    pub show_graphs: bool,
+    /// Rows on the process panel, and of `systrix ps` without `--limit`
+    pub process_limit: usize,
+    /// Per-core usage bars on the overview
+    pub show_per_core_cpu: bool,
+    /// Capture mouse events; leaves text selection to the terminal when off
+    pub enable_mouse: bool,
+    pub keys: KeyBindings,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            show_graphs: true,
+            process_limit: 100,
+            show_per_core_cpu: true,
+            enable_mouse: false,
+            keys: KeyBindings::default(),
+        }
+    }
+}
+
+/// The `[tui.keys]` section: the key of each single-letter action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyBindings {
+    /// Also works as the upper case letter
+    pub quit: char,
+    pub kill: char,
+    pub suspend: char,
+    pub resume: char,
+    pub tree: char,
+    pub group: char,
+    pub connections: char,
+    pub pause: char,
+    pub theme: char,
+    pub export: char,
+    pub search: char,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            quit: 'q',
+            kill: 'k',
+            suspend: 's',
+            resume: 'r',
+            tree: 'v',
+            group: 'g',
+            connections: 'c',
+            pause: 'p',
+            theme: 't',
+            export: 'e',
+            search: '/',
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Keys with a fixed meaning: panels, playback, fleet sorting and the
+    /// kill confirmation
+    const RESERVED: &'static str = "1234567+-=[]{}oOyYnN";
+
+    pub fn actions(&self) -> [(&'static str, char); 11] {
+        [
+            ("quit", self.quit),
+            ("kill", self.kill),
+            ("suspend", self.suspend),
+            ("resume", self.resume),
+            ("tree", self.tree),
+            ("group", self.group),
+            ("connections", self.connections),
+            ("pause", self.pause),
+            ("theme", self.theme),
+            ("export", self.export),
+            ("search", self.search),
+        ]
+    }
+}
+
+/// The `[monitoring]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MonitoringConfig {
+    /// Probe for GPUs in the TUI, the agent, exports and history; `systrix gpu` always does
+    pub enable_gpu: bool,
+    /// Deprecated and ignored; accepted so that older files still load
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_ttl_seconds: Option<u64>,
+    /// Deprecated and ignored; accepted so that older files still load
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_smart: Option<bool>,
+}
+
+impl Default for MonitoringConfig {
+    fn default() -> Self {
+        Self {
+            enable_gpu: true,
+            cache_ttl_seconds: None,
+            enable_smart: None,
+        }
+    }
+}
+
+/// The `[remote]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RemoteSettings {
+    /// This machine is meant to run the agent; a token is then required
+    pub enabled: bool,
+    pub port: u16,
+    pub bind: String,
+    /// Used when `systrix agent` gets no `--token`
+    pub token: Option<String>,
+    /// Allow browsers on other origins to call the agent
+    pub enable_cors: bool,
+    /// Processes in each snapshot the agent publishes, unless `--process-limit` is given
+    pub process_limit: usize,
+}
+
+impl Default for RemoteSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 8080,
+            bind: "127.0.0.1".to_string(),
+            token: None,
+            enable_cors: false,
+            process_limit: 200,
+        }
+    }
+}
+
+/// The `[process_management]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProcessManagementConfig {
+    /// Ask before a process is killed
+    pub require_confirmation: bool,
+    /// Refuse to kill PID 0, PID 1 and `protected_pids`
+    pub protect_system_processes: bool,
+    pub protected_pids: Vec<u32>,
+}
+
+impl Default for ProcessManagementConfig {
+    fn default() -> Self {
+        Self {
+            require_confirmation: true,
+            protect_system_processes: true,
+            protected_pids: vec![1],
+        }
+    }
+}
+
+impl ProcessManagementConfig {
+    /// Whether killing `pid` needs an explicit override
+    pub fn is_protected(&self, pid: u32) -> bool {
+        self.protect_system_processes && (pid <= 1 || self.protected_pids.contains(&pid))
+    }
+}
+
+/// The `[export]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    /// Format of `systrix export` without `--format`, preselected in the TUI
+    pub default_format: ExportFormat,
+    pub include_timestamps: bool,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            default_format: ExportFormat::Json,
+            include_timestamps: true,
+        }
+    }
+}
+
+/// The `[alerts]` section
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertsConfig {
+    /// Evaluated by the TUI, the agent and `systrix alerts check`
+    #[serde(default)]
+    pub rules: Vec<AlertRule>,
+    
+    /// Where firing and resolved alerts are sent
+    #[serde(default)]
+    pub sinks: Vec<SinkConfig>,
+}
+
+/// One `[[hosts]]` entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HostConfig {
+    /// Display name; the address is shown when missing
+    pub name: Option<String>,
+    /// Address of the agent, e.g. `http://box:8080`
+    pub url: String,
+    /// Agent token
+    pub token: Option<String>,
+}
+
+impl HostConfig {
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.url)
+    }
+}
+
+/// The `[history]` section
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HistoryConfig {
+    /// Record history while `systrix agent` runs
+    pub enabled: bool,
+    /// Store directory (default: `$XDG_DATA_HOME/systrix/history`)
+    pub path: Option<PathBuf>,
+    /// Time between two stored snapshots
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+    /// How long snapshots are kept
+    #[serde(with = "humantime_serde")]
+    pub retention: Duration,
+    /// Size cap of the whole store; the oldest data is deleted first
+    pub max_size_mb: u64,
+    /// Size at which a new segment file is started
+    pub segment_size_mb: u64,
+    /// Processes stored per snapshot, by CPU and by memory
+    pub process_limit: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            interval: Duration::from_secs(10),
+            retention: Duration::from_secs(7 * 24 * 3600),
+            max_size_mb: 256,
+            segment_size_mb: 8,
+            process_limit: 10,
+        }
+    }
+}
+
+impl HistoryConfig {
+    /// Store settings for these limits
+    pub fn store_config(&self) -> Result<StoreConfig> {
+        let dir = self.path.clone()
+            .or_else(store::default_dir)
+            .context("no history directory: set path in the [history] section")?;
+        let mut config = StoreConfig::new(dir);
+        config.retention = self.retention;
+        config.max_bytes = self.max_size_mb.saturating_mul(1024 * 1024);
+        config.segment_bytes = self.segment_size_mb.max(1).saturating_mul(1024 * 1024).min(config.max_bytes.max(1));
+        Ok(config)
+    }
+}
+
+/// Durations written like `10s` or `7d`
+pub(crate) mod humantime_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&humantime::format_duration(*duration).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        humantime::parse_duration(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Config {
+    /// Where the configuration file is looked up when `--config` is not given
+    pub fn default_path() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(base.join("systrix").join("config.toml"))
+    }
+
+    /// Parse the file content alone, without environment overrides or validation
+    pub fn parse(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// Parse `content`, apply the `SYSTRIX_*` overrides among `vars` and validate the result
+    pub fn from_sources(content: &str, vars: impl IntoIterator<Item = (String, String)>) -> Result<Self> {
+        // Parsed on its own first, so errors in the file point at its lines
+        let config = Self::parse(content)?;
+        let overrides = env_overrides(vars)?;
+        let config = if overrides.is_empty() {
+            config
+        } else {
+            let mut table: toml::Table = toml::from_str(content)?;
+            let names: Vec<&str> = overrides.iter().map(|o| o.variable.as_str()).collect();
+            for o in &overrides {
+                let section = table.entry(o.section)
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+                let Some(section) = section.as_table_mut() else {
+                    bail!("{}: [{}] is not a section in the file", o.variable, o.section);
+                };
+                section.insert(o.key.clone(), o.value.clone());
+            }
+            Self::deserialize(table).with_context(|| format!("invalid value in {}", names.join(", ")))?
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+        let config = Self::from_sources(&content, std::env::vars())
+            .with_context(|| format!("invalid configuration in {}", path.display()))?;
+        config.warn_deprecated(path);
+        Ok(config)
+    }
+
+    /// Keys that are still accepted but no longer do anything
+    pub fn deprecated_keys(&self) -> Vec<&'static str> {
+        [
+            ("monitoring.cache_ttl_seconds", self.monitoring.cache_ttl_seconds.is_some()),
+            ("monitoring.enable_smart", self.monitoring.enable_smart.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(key, set)| set.then_some(key))
+        .collect()
+    }
+
+    fn warn_deprecated(&self, path: &Path) {
+        for key in self.deprecated_keys() {
+            tracing::warn!("{}: {} is deprecated and ignored", path.display(), key);
+        }
+    }
+
+    /// The file [`Config::load_or_default`] reads, if there is one
+    pub fn file_in_use(path: Option<&Path>) -> Option<PathBuf> {
+        match path {
+            Some(path) => Some(path.to_path_buf()),
+            None => Self::default_path().filter(|path| path.exists()),
+        }
+    }
+
+    /// Load `path`, or the default file; a missing default file gives the
+    /// defaults with the environment overrides applied
+    pub fn load_or_default(path: Option<&Path>) -> Result<Self> {
+        match (path, Self::default_path()) {
+            (Some(path), _) => Self::load(path),
+            (None, Some(path)) if path.exists() => Self::load(&path),
+            _ => Self::from_sources("", std::env::vars()).context("invalid configuration in the environment"),
+        }
+    }
+
+    /// Check values the file format cannot express; every message starts with the offending key
+    pub fn validate(&self) -> Result<()> {
+        let mut problems = Vec::new();
+        let mut problem = |key: String, message: String| problems.push(format!("{}: {}", key, message));
+
+        if self.general.refresh_interval_ms < MIN_REFRESH_INTERVAL_MS {
+            problem("general.refresh_interval_ms".into(), format!("must be at least {}, got {}", MIN_REFRESH_INTERVAL_MS, self.general.refresh_interval_ms));
+        }
+        if self.tui.process_limit == 0 {
+            problem("tui.process_limit".into(), "must be at least 1".into());
+        }
+        let actions = self.tui.keys.actions();
+        for (i, (action, key)) in actions.iter().enumerate() {
+            if key.is_whitespace() || key.is_control() || KeyBindings::RESERVED.contains(*key) {
+                problem(format!("tui.keys.{}", action), format!("'{}' cannot be bound", key));
+            } else if let Some((other, _)) = actions[..i].iter().find(|(other, k)| {
+                // Quit is matched in either case, so it also claims the other case
+                k == key || ((*other == "quit" || *action == "quit") && k.eq_ignore_ascii_case(key))
+            }) {
+                problem(format!("tui.keys.{}", action), format!("'{}' is already bound to {}", key, other));
+            }
+        }
+        if self.remote.process_limit == 0 {
+            problem("remote.process_limit".into(), "must be at least 1".into());
+        }
+        if self.remote.bind.trim().is_empty() {
+            problem("remote.bind".into(), "must not be empty".into());
+        }
+        match &self.remote.token {
+            Some(token) if token.is_empty() => problem("remote.token".into(), "must not be empty".into()),
+            None if self.remote.enabled => problem("remote.token".into(), "required when remote.enabled is set".into()),
+            _ => {}
+        }
+        if self.history.interval < Duration::from_millis(MIN_REFRESH_INTERVAL_MS) {
+            problem("history.interval".into(), format!("must be at least {}ms", MIN_REFRESH_INTERVAL_MS));
+        }
+        if self.history.max_size_mb == 0 {
+            problem("history.max_size_mb".into(), "must be at least 1".into());
+        }
+        for (i, host) in self.hosts.iter().enumerate() {
+            if host.url.trim().is_empty() {
+                problem(format!("hosts[{}].url", i), "must not be empty".into());
+            }
+        }
+        // Notifications are routed by rule and sink name
+        for (i, rule) in self.alerts.rules.iter().enumerate() {
+            let name = rule.display_name();
+            if let Some(first) = self.alerts.rules[..i].iter().position(|r| r.display_name() == name) {
+                problem(format!("alerts.rules[{}].name", i), format!("'{}' is already used by alerts.rules[{}]", name, first));
+            }
+        }
+        for (i, sink) in self.alerts.sinks.iter().enumerate() {
+            if let Some(first) = self.alerts.sinks[..i].iter().position(|s| s.name == sink.name) {
+                problem(format!("alerts.sinks[{}].name", i), format!("'{}' is already used by alerts.sinks[{}]", sink.name, first));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            bail!("{}", problems.join("\n"))
+        }
+    }
+
+    /// The settings as TOML, with tokens replaced so the output can be shared
+    pub fn to_masked_toml(&self) -> Result<String> {
+        const MASK: &str = "********";
+        let mut config = self.clone();
+        if let Some(token) = &mut config.remote.token {
+            *token = MASK.to_string();
+        }
+        for token in config.hosts.iter_mut().filter_map(|h| h.token.as_mut()) {
+            *token = MASK.to_string();
+        }
+        Ok(toml::to_string_pretty(&config)?)
+    }
+}
+
+/// Polls a configuration file for edits
+pub struct ConfigWatcher {
+    path: PathBuf,
+    /// Modification time and size at the last check
+    stamp: Option<(SystemTime, u64)>,
+    /// Content at the last check, or why it could not be read
+    last: std::result::Result<String, String>,
+}
+
+impl ConfigWatcher {
+    /// Watch `path`, taking its current content as already applied
+    pub fn new(path: PathBuf) -> Self {
+        let stamp = Self::stamp(&path);
+        let last = std::fs::read_to_string(&path).map_err(|e| e.to_string());
+        Self { path, stamp, last }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some((metadata.modified().ok()?, metadata.len()))
+    }
+
+    /// `None` while the file is unchanged, otherwise the settings it now
+    /// holds, or why they cannot be used
+    pub fn poll(&mut self) -> Option<Result<Config>> {
+        let stamp = Self::stamp(&self.path);
+        if stamp == self.stamp && stamp.is_some() {
+            return None;
+        }
+        self.stamp = stamp;
+
+        // Saving without changes, or a file that stays missing, is not an edit
+        let current = std::fs::read_to_string(&self.path).map_err(|e| e.to_string());
+        if current == self.last {
+            return None;
+        }
+        self.last = current.clone();
+
+        Some(match current {
+            Ok(content) => Config::from_sources(&content, std::env::vars())
+                .with_context(|| format!("invalid configuration in {}", self.path.display()))
+                .map(|config| {
+                    config.warn_deprecated(&self.path);
+                    config
+                }),
+            Err(e) => Err(anyhow::anyhow!("cannot read {}: {}", self.path.display(), e)),
+        })
+    }
+}
+
+/// One `SYSTRIX_<SECTION>_<KEY>` variable
+struct EnvOverride {
+    variable: String,
+    section: &'static str,
+    key: String,
+    value: toml::Value,
+}
+
+/// The overrides among `vars`; variables that name no known section are
+/// ignored, so unrelated ones like those passed to alert commands pass through
+fn env_overrides(vars: impl IntoIterator<Item = (String, String)>) -> Result<Vec<EnvOverride>> {
+    // Decides whether a value is read as text or as a TOML literal
+    let defaults = toml::Table::try_from(Config::default())?;
+    let mut overrides: Vec<EnvOverride> = vars.into_iter()
+        .filter_map(|(variable, raw)| {
+            let name = variable.strip_prefix(ENV_PREFIX)?.to_ascii_lowercase();
+            let section = ENV_SECTIONS.iter()
+                .find(|section| name.strip_prefix(**section).is_some_and(|rest| rest.starts_with('_')))?;
+            let key = name[section.len() + 1..].to_string();
+            let value = match defaults.get(*section).and_then(|s| s.get(&key)) {
+                // Text settings and unset optional ones, like tokens and paths
+                Some(toml::Value::String(_)) | None => toml::Value::String(raw),
+                _ => toml::from_str::<toml::Table>(&format!("value = {}", raw))
+                    .ok()
+                    .and_then(|mut t| t.remove("value"))
+                    .unwrap_or(toml::Value::String(raw)),
+            };
+            Some(EnvOverride { variable, section, key, value })
+        })
+        .collect();
+    overrides.sort_by(|a, b| a.variable.cmp(&b.variable));
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hosts() {
+        let config = Config::parse(r#"
+[general]
+refresh_interval_ms = 500
+
+[[hosts]]
+name = "db-1"
+url = "http://db-1:8080"
+token = "secret"
+
+[[hosts]]
+url = "web-1:8080"
+"#).unwrap();
+
+        assert_eq!(config.hosts.len(), 2);
+        assert_eq!(config.hosts[0].display_name(), "db-1");
+        assert_eq!(config.hosts[0].token.as_deref(), Some("secret"));
+        assert_eq!(config.hosts[1].display_name(), "web-1:8080");
+        assert_eq!(config.hosts[1].token, None);
+
+        assert!(Config::parse("[[hosts]]\nname = \"no url\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_history() {
+        assert_eq!(Config::parse("").unwrap().history, HistoryConfig::default());
+
+        let config = Config::parse(r#"
+[history]
+enabled = true
+path = "/var/lib/systrix"
+retention = "2d"
+max_size_mb = 64
+"#).unwrap();
+        assert!(config.history.enabled);
+        assert_eq!(config.history.interval, Duration::from_secs(10));
+        let store = config.history.store_config().unwrap();
+        assert_eq!(store.dir, PathBuf::from("/var/lib/systrix"));
+        assert_eq!(store.retention, Duration::from_secs(2 * 24 * 3600));
+        assert_eq!(store.max_bytes, 64 * 1024 * 1024);
+
+        assert!(Config::parse("[history]\nretention = \"a while\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_alert_rules() {
+        let config = Config::parse(r#"
+[[alerts.rules]]
+name = "root disk"
+expr = 'disk["/"].usage > 85 for 5m'
+severity = "critical"
+hysteresis = 2
+
+[[alerts.rules]]
+expr = "cpu.global > 90"
+"#).unwrap();
+
+        let rules = &config.alerts.rules;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].display_name(), "root disk");
+        assert_eq!(rules[0].severity, crate::alerts::Severity::Critical);
+        assert_eq!(rules[0].expr.duration, Duration::from_secs(300));
+        assert_eq!(rules[1].display_name(), "cpu.global > 90");
+
+        let error = Config::parse("[[alerts.rules]]\nexpr = \"cpu.global >> 90\"").unwrap_err();
+        assert!(format!("{:#}", error).contains("invalid threshold"), "{:#}", error);
+    }
+
+    #[test]
+    fn test_default_file_matches_defaults() {
+        let config = Config::from_sources(DEFAULT_FILE, Vec::new()).unwrap();
+        let defaults = Config::default();
+        assert_eq!(config.general, defaults.general);
+        assert_eq!(config.tui, defaults.tui);
+        assert_eq!(config.monitoring, defaults.monitoring);
+        assert_eq!(config.remote, defaults.remote);
+        assert_eq!(config.process_management, defaults.process_management);
+        assert_eq!(config.export, defaults.export);
+        assert_eq!(config.history, defaults.history);
+        assert_eq!(config.alerts.rules.len(), 3);
+
+        // What `config show` prints reads back as the same settings
+        let shown = Config::parse(&config.to_masked_toml().unwrap()).unwrap();
+        assert_eq!(shown, config);
+    }
+
+    #[test]
+    fn test_legacy_default_file_loads() {
+        // The default.toml shipped before the history and alert sections
+        let content = include_str!("../tests/fixtures/config/legacy_default.toml");
+        let config = Config::from_sources(content, Vec::new()).unwrap();
+        assert_eq!(config.monitoring.cache_ttl_seconds, Some(5));
+        assert_eq!(config.monitoring.enable_smart, Some(false));
+        assert_eq!(config.deprecated_keys(), ["monitoring.cache_ttl_seconds", "monitoring.enable_smart"]);
+        assert!(Config::parse(DEFAULT_FILE).unwrap().deprecated_keys().is_empty());
+    }
+
+    #[test]
+    fn test_env_overrides_and_validation() {
+        let vars = |pairs: &[(&str, &str)]| pairs.iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<Vec<_>>();
+        let file = "[general]\ntheme = \"dracula\"\n";
+
+        let config = Config::from_sources(file, vars(&[
+            ("SYSTRIX_GENERAL_REFRESH_INTERVAL_MS", "1000"),
+            ("SYSTRIX_REMOTE_TOKEN", "12345"),
+            ("SYSTRIX_PROCESS_MANAGEMENT_PROTECTED_PIDS", "[1, 42]"),
+            ("SYSTRIX_HISTORY_RETENTION", "2d"),
+            ("SYSTRIX_ALERT_RULE", "not a setting"),
+        ])).unwrap();
+        assert_eq!(config.general.refresh_interval_ms, 1000);
+        assert_eq!(config.general.theme, ThemeName::Dracula);
+        assert_eq!(config.remote.token.as_deref(), Some("12345"));
+        assert!(config.process_management.is_protected(42));
+        assert!(!config.process_management.is_protected(43));
+        assert_eq!(config.history.retention, Duration::from_secs(2 * 24 * 3600));
+        assert!(config.to_masked_toml().unwrap().contains("token = \"********\""));
+
+        let error = |pairs: &[(&str, &str)]| format!("{:#}", Config::from_sources(file, vars(pairs)).unwrap_err());
+        let message = error(&[("SYSTRIX_TUI_PROCESS_LIMIT", "lots")]);
+        assert!(message.contains("SYSTRIX_TUI_PROCESS_LIMIT") && message.contains("tui.process_limit"), "{}", message);
+        assert!(error(&[("SYSTRIX_GENERAL_COLOR", "red")]).contains("unknown field `color`"));
+        assert!(error(&[("SYSTRIX_GENERAL_REFRESH_INTERVAL_MS", "50")]).contains("general.refresh_interval_ms: must be at least 100"));
+        assert!(error(&[("SYSTRIX_REMOTE_ENABLED", "true")]).contains("remote.token: required"));
+
+        let duplicate = Config::from_sources("[[alerts.rules]]\nname = \"cpu\"\nexpr = \"cpu.global > 90\"\n\n[[alerts.rules]]\nname = \"cpu\"\nexpr = \"cpu.global > 95\"", Vec::new());
+        assert!(format!("{:#}", duplicate.unwrap_err()).contains("alerts.rules[1].name: 'cpu' is already used by alerts.rules[0]"));
+        assert!(Config::parse("[general]\ntheme = \"solarized\"").is_err());
+    }
+
+    #[test]
+    fn test_key_bindings() {
+        let config = Config::parse("[tui.keys]\nkill = \"x\"\nsearch = \"f\"").unwrap();
+        assert_eq!(config.tui.keys.kill, 'x');
+        assert_eq!(config.tui.keys.search, 'f');
+        assert_eq!(config.tui.keys.quit, 'q');
+
+        let error = |file: &str| format!("{:#}", Config::from_sources(file, Vec::new()).unwrap_err());
+        assert!(error("[tui.keys]\nkill = \"q\"").contains("tui.keys.kill: 'q' is already bound to quit"));
+        assert!(error("[tui.keys]\nexport = \"1\"").contains("tui.keys.export: '1' cannot be bound"));
+        assert!(error("[tui.keys]\nkill = \"Q\"").contains("tui.keys.kill: 'Q' is already bound to quit"));
+        assert!(error("[tui.keys]\ntheme = \"o\"").contains("tui.keys.theme: 'o' cannot be bound"));
+        assert!(error("[tui.keys]\nquit = \"quit\"").contains("expected a character"));
+    }
+
+    #[test]
+    fn test_watcher_reports_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[general]\ntheme = \"dark\"\n").unwrap();
+
+        let mut watcher = ConfigWatcher::new(path.clone());
+        assert!(watcher.poll().is_none());
+
+        std::fs::write(&path, "[general]\ntheme = \"light\"\n").unwrap();
+        assert_eq!(watcher.poll().unwrap().unwrap().general.theme, ThemeName::Light);
+        assert!(watcher.poll().is_none());
+
+        // Rewriting the same content is not an edit
+        std::fs::write(&path, "[general]\ntheme = \"light\"\n").unwrap();
+        assert!(watcher.poll().is_none());
+
+        std::fs::write(&path, "[general]\nrefresh_interval_ms = 10\n").unwrap();
+        let message = format!("{:#}", watcher.poll().unwrap().unwrap_err());
+        assert!(message.contains("invalid configuration in") && message.contains("must be at least 100"), "{}", message);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(format!("{:#}", watcher.poll().unwrap().unwrap_err()).contains("cannot read"));
+        assert!(watcher.poll().is_none());
+    }
+}
@@ -3,65 +3,61 @@
 
 use anyhow::Result;
 use chrono::Local;
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::Write;
 
-use crate::monitor::{BatteryInfo, CpuSnapshot, DiskInfo, DiskSnapshot, MemorySnapshot, NetworkSnapshot, ProcessInfo};
+use crate::monitor::SystemSnapshot;
 
 /// Export format
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ExportFormat {
     Csv,
     Json,
     Html,
 }
 
-/// Export current system snapshot to file
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
+/// Export current system snapshot to file; without `include_timestamps`
+/// the content does not say when it was generated
 pub fn export_snapshot(
-    cpu: &Option<CpuSnapshot>,
-    memory: &Option<MemorySnapshot>,
-    disk: &Option<DiskSnapshot>,
-    network: &Option<NetworkSnapshot>,
-    battery: &Option<BatteryInfo>,
-    processes: &[ProcessInfo],
-    disk_list: &[DiskInfo],
+    snapshot: &SystemSnapshot,
     format: ExportFormat,
     path: Option<&str>,
+    include_timestamps: bool,
 ) -> Result<String> {
     let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-    let default_filename = format!(
-        "systrix_export_{}.{}",
-        timestamp,
-        match format {
-            ExportFormat::Csv => "csv",
-            ExportFormat::Json => "json",
-            ExportFormat::Html => "html",
-        }
-    );
+    let default_filename = format!("systrix_export_{}.{}", timestamp, format.extension());
     let filename = path.unwrap_or(&default_filename);
+    let generated = include_timestamps.then(|| Local::now().format("%Y-%m-%d %H:%M:%S").to_string());
+    let generated = generated.as_deref();
 
     match format {
-        ExportFormat::Csv => export_csv(cpu, memory, disk, network, battery, processes, disk_list, filename),
-        ExportFormat::Json => export_json(cpu, memory, disk, network, battery, processes, disk_list, filename),
-        ExportFormat::Html => export_html(cpu, memory, disk, network, battery, processes, disk_list, filename),
+        ExportFormat::Csv => export_csv(snapshot, filename, generated),
+        ExportFormat::Json => export_json(snapshot, filename, generated),
+        ExportFormat::Html => export_html(snapshot, filename, generated),
     }
 }
 
-fn export_csv(
-    cpu: &Option<CpuSnapshot>,
-    memory: &Option<MemorySnapshot>,
-    disk: &Option<DiskSnapshot>,
-    network: &Option<NetworkSnapshot>,
-    battery: &Option<BatteryInfo>,
-    processes: &[ProcessInfo],
-    disk_list: &[DiskInfo],
-    filename: &str,
-) -> Result<String> {
+fn export_csv(snapshot: &SystemSnapshot, filename: &str, generated: Option<&str>) -> Result<String> {
+    let SystemSnapshot { cpu, memory, disk, network, battery, batteries, sensors, pressure, gpus, processes, disk_list, disk_io, .. } = snapshot;
     let mut file = File::create(filename)?;
     
     // Write header
     writeln!(file, "Systrix System Monitor Export")?;
-    writeln!(file, "Timestamp,{}", Local::now().format("%Y-%m-%d %H:%M:%S"))?;
+    if let Some(generated) = generated {
+        writeln!(file, "Timestamp,{}", generated)?;
+    }
     writeln!(file)?;
     
     // System Information
@@ -91,6 +87,20 @@ fn export_csv(
         writeln!(file, "Used (bytes),{}", mem_data.used)?;
         writeln!(file, "Available (bytes),{}", mem_data.available)?;
         writeln!(file, "Usage (%),{:.2}", mem_data.usage_percent)?;
+        if let Some(detail) = &mem_data.detail {
+            writeln!(file, "Buffers (bytes),{}", detail.buffers)?;
+            writeln!(file, "Cached (bytes),{}", detail.cached)?;
+            writeln!(file, "Shared (bytes),{}", detail.shared)?;
+            writeln!(file, "Slab Reclaimable (bytes),{}", detail.slab_reclaimable)?;
+            writeln!(file, "Slab Unreclaimable (bytes),{}", detail.slab_unreclaimable)?;
+            writeln!(file, "Dirty (bytes),{}", detail.dirty)?;
+            writeln!(file, "Writeback (bytes),{}", detail.writeback)?;
+            writeln!(file, "HugePages Total,{}", detail.hugepages_total)?;
+            writeln!(file, "HugePages Free,{}", detail.hugepages_free)?;
+            writeln!(file, "HugePage Size (bytes),{}", detail.hugepage_size)?;
+            writeln!(file, "Committed AS (bytes),{}", detail.committed_as)?;
+            writeln!(file, "Commit Limit (bytes),{}", detail.commit_limit)?;
+        }
         writeln!(file)?;
     }
     
@@ -124,27 +134,72 @@ fn export_csv(
         writeln!(file)?;
     }
     
+    // Pressure stall information
+    if let Some(pressure) = pressure {
+        writeln!(file, "=== PRESSURE ===")?;
+        writeln!(file, "Resource,Kind,Avg10 (%),Avg60 (%),Avg300 (%),Total (us)")?;
+        for name in ["cpu", "memory", "io"] {
+            let Some(resource) = pressure.resource(name) else {
+                continue;
+            };
+            for (kind, line) in [("some", Some(resource.some)), ("full", resource.full)] {
+                if let Some(line) = line {
+                    writeln!(file, "{},{},{:.2},{:.2},{:.2},{}", name, kind, line.avg10, line.avg60, line.avg300, line.total_us)?;
+                }
+            }
+        }
+        writeln!(file)?;
+    }
+    
+    // Disk I/O
+    if !disk_io.is_empty() {
+        writeln!(file, "=== DISK I/O ===")?;
+        writeln!(file, "Device,Read (bytes/s),Write (bytes/s),Read IOPS,Write IOPS,Await (ms),Queue Depth,Utilization (%),Mount Points")?;
+        for device in disk_io {
+            writeln!(
+                file,
+                "{},{},{},{:.2},{:.2},{:.2},{:.2},{:.1},{}",
+                device.device,
+                device.read_bytes_per_sec,
+                device.write_bytes_per_sec,
+                device.read_iops,
+                device.write_iops,
+                device.await_ms,
+                device.queue_depth,
+                device.utilization,
+                device.mount_points.join(" ")
+            )?;
+        }
+        writeln!(file)?;
+    }
+    
     // Network
     if let Some(net_data) = network {
         writeln!(file, "=== NETWORK ===")?;
         writeln!(file, "Total RX (bytes),{}", net_data.total_rx)?;
         writeln!(file, "Total TX (bytes),{}", net_data.total_tx)?;
+        writeln!(file, "Total RX Rate (bytes/s),{}", net_data.total_rx_rate)?;
+        writeln!(file, "Total TX Rate (bytes/s),{}", net_data.total_tx_rate)?;
         
         if !net_data.interfaces.is_empty() {
             writeln!(file)?;
             writeln!(file, "=== NETWORK INTERFACES ===")?;
-            writeln!(file, "Name,RX (bytes),TX (bytes),RX Rate (bytes/s),TX Rate (bytes/s),Packets RX,Packets TX")?;
+            writeln!(file, "Name,RX (bytes),TX (bytes),RX Rate (bytes/s),TX Rate (bytes/s),Packets RX,Packets TX,Packets RX (/s),Packets TX (/s),Errors RX (/s),Errors TX (/s)")?;
             for iface in &net_data.interfaces {
                 writeln!(
                     file,
-                    "{},{},{},{},{},{},{}",
+                    "{},{},{},{},{},{},{},{:.2},{:.2},{:.2},{:.2}",
                     iface.name,
                     iface.received,
                     iface.transmitted,
                     iface.rx_rate,
                     iface.tx_rate,
                     iface.packets_received,
-                    iface.packets_transmitted
+                    iface.packets_transmitted,
+                    iface.packets_rx_rate,
+                    iface.packets_tx_rate,
+                    iface.errors_rx_rate,
+                    iface.errors_tx_rate
                 )?;
             }
         }
@@ -167,23 +222,101 @@ fn export_csv(
         }
     }
     
+    if batteries.len() > 1 {
+        writeln!(file, "=== BATTERIES ===")?;
+        writeln!(file, "Name,Percentage,Status,Health (%),Energy Now (Wh),Energy Full (Wh),Power (W),Vendor")?;
+        for bat in batteries {
+            writeln!(
+                file,
+                "{},{:.0},{},{:.0},{},{},{},{}",
+                bat.name,
+                bat.percentage,
+                bat.status,
+                bat.health,
+                bat.energy_now.map(|v| format!("{:.2}", v)).unwrap_or_default(),
+                bat.energy_full.map(|v| format!("{:.2}", v)).unwrap_or_default(),
+                bat.power_now.map(|v| format!("{:.2}", v)).unwrap_or_default(),
+                bat.vendor
+            )?;
+        }
+        writeln!(file)?;
+    }
+    
+    // Sensors
+    if let Some(sensors_data) = sensors {
+        if !sensors_data.temperatures.is_empty() || !sensors_data.fans.is_empty() {
+            writeln!(file, "=== SENSORS ===")?;
+            writeln!(file, "Sensor,Current,Max,Critical,Unit")?;
+            for temp in &sensors_data.temperatures {
+                writeln!(
+                    file,
+                    "{},{:.1},{},{},C",
+                    temp.label,
+                    temp.current,
+                    temp.max.map(|v| format!("{:.1}", v)).unwrap_or_default(),
+                    temp.critical.map(|v| format!("{:.1}", v)).unwrap_or_default()
+                )?;
+            }
+            for fan in &sensors_data.fans {
+                writeln!(
+                    file,
+                    "{},{},{},,RPM",
+                    fan.label,
+                    fan.rpm,
+                    fan.max_rpm.map(|v| v.to_string()).unwrap_or_default()
+                )?;
+            }
+            writeln!(file)?;
+        }
+    }
+    
+    // GPUs
+    if !gpus.is_empty() {
+        writeln!(file, "=== GPU ===")?;
+        writeln!(file, "Index,Name,Vendor,Driver,Bus ID,Utilization (%),Memory Used (bytes),Memory Total (bytes),Core Clock (MHz),Memory Clock (MHz),Temperature (C),Power (W)")?;
+        for gpu in gpus {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{},{},{},{},{}",
+                gpu.index,
+                gpu.name,
+                gpu.vendor,
+                gpu.driver,
+                gpu.bus_id.as_deref().unwrap_or_default(),
+                gpu.utilization.map(|v| format!("{:.1}", v)).unwrap_or_default(),
+                gpu.memory_used.map(|v| v.to_string()).unwrap_or_default(),
+                gpu.memory_total.map(|v| v.to_string()).unwrap_or_default(),
+                gpu.core_clock_mhz.map(|v| v.to_string()).unwrap_or_default(),
+                gpu.memory_clock_mhz.map(|v| v.to_string()).unwrap_or_default(),
+                gpu.temperature.map(|v| format!("{:.1}", v)).unwrap_or_default(),
+                gpu.power_watts.map(|v| format!("{:.1}", v)).unwrap_or_default()
+            )?;
+        }
+        writeln!(file)?;
+    }
+    
     // Processes
     if !processes.is_empty() {
         writeln!(file, "=== PROCESSES ===")?;
-        writeln!(file, "PID,Name,User,CPU (%),Memory (%),Disk Read (bytes),Disk Write (bytes),Threads,Status,Executable")?;
+        writeln!(file, "PID,PPID,Name,User,UID,CPU (%),Memory (%),Disk Read (bytes),Disk Write (bytes),Disk Read (bytes/s),Disk Write (bytes/s),Threads,Status,Container,Executable")?;
         for proc in processes {
             writeln!(
                 file,
-                "{},{},{},{:.2},{:.2},{},{},{},{},{}",
+                "{},{},{},{},{},{:.2},{:.2},{},{},{},{},{},{},{},{}",
                 proc.pid,
+                proc.ppid.map(|ppid| ppid.to_string()).unwrap_or_default(),
                 proc.name,
                 proc.user,
+                proc.uid.map(|uid| uid.to_string()).unwrap_or_default(),
                 proc.cpu_usage,
                 proc.memory_usage,
                 proc.disk_read,
                 proc.disk_write,
+                proc.disk_read_rate,
+                proc.disk_write_rate,
                 proc.threads,
                 proc.status,
+                proc.container.as_ref().map(|c| c.to_string()).unwrap_or_default(),
                 proc.exe_path
             )?;
         }
@@ -192,20 +325,11 @@ fn export_csv(
     Ok(filename.to_string())
 }
 
-fn export_json(
-    cpu: &Option<CpuSnapshot>,
-    memory: &Option<MemorySnapshot>,
-    disk: &Option<DiskSnapshot>,
-    network: &Option<NetworkSnapshot>,
-    battery: &Option<BatteryInfo>,
-    processes: &[ProcessInfo],
-    disk_list: &[DiskInfo],
-    filename: &str,
-) -> Result<String> {
+fn export_json(snapshot: &SystemSnapshot, filename: &str, generated: Option<&str>) -> Result<String> {
+    let SystemSnapshot { cpu, memory, disk, network, battery, batteries, sensors, pressure, gpus, processes, disk_list, disk_io, .. } = snapshot;
     use serde_json::json;
     
-    let data = json!({
-        "timestamp": Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+    let mut data = json!({
         "system": cpu.as_ref().map(|c| json!({
             "device": c.hostname,
             "os": c.os_name,
@@ -224,6 +348,21 @@ fn export_json(
             "used_bytes": m.used,
             "available_bytes": m.available,
             "usage_percent": m.usage_percent,
+            "detail": m.detail.as_ref().map(|d| json!({
+                "applications_bytes": d.applications(),
+                "buffers_bytes": d.buffers,
+                "cached_bytes": d.cached,
+                "shared_bytes": d.shared,
+                "slab_reclaimable_bytes": d.slab_reclaimable,
+                "slab_unreclaimable_bytes": d.slab_unreclaimable,
+                "dirty_bytes": d.dirty,
+                "writeback_bytes": d.writeback,
+                "hugepages_total": d.hugepages_total,
+                "hugepages_free": d.hugepages_free,
+                "hugepage_size_bytes": d.hugepage_size,
+                "committed_as_bytes": d.committed_as,
+                "commit_limit_bytes": d.commit_limit,
+            })),
         })),
         "disk": disk.as_ref().map(|d| json!({
             "total_bytes": d.total,
@@ -240,9 +379,24 @@ fn export_json(
             "available_bytes": d.available,
             "usage_percent": d.usage_percent,
         })).collect::<Vec<_>>(),
+        "disk_io": disk_io.iter().map(|d| json!({
+            "device": d.device,
+            "read_bytes_per_sec": d.read_bytes_per_sec,
+            "write_bytes_per_sec": d.write_bytes_per_sec,
+            "read_iops": d.read_iops,
+            "write_iops": d.write_iops,
+            "await_ms": d.await_ms,
+            "queue_depth": d.queue_depth,
+            "utilization_percent": d.utilization,
+            "total_read_bytes": d.total_read,
+            "total_written_bytes": d.total_written,
+            "mount_points": d.mount_points,
+        })).collect::<Vec<_>>(),
         "network": network.as_ref().map(|n| json!({
             "total_rx_bytes": n.total_rx,
             "total_tx_bytes": n.total_tx,
+            "total_rx_rate_bytes_per_sec": n.total_rx_rate,
+            "total_tx_rate_bytes_per_sec": n.total_tx_rate,
             "interfaces": n.interfaces.iter().map(|i| json!({
                 "name": i.name,
                 "received_bytes": i.received,
@@ -251,11 +405,18 @@ fn export_json(
                 "tx_rate_bytes_per_sec": i.tx_rate,
                 "packets_received": i.packets_received,
                 "packets_transmitted": i.packets_transmitted,
+                "packets_rx_per_sec": i.packets_rx_rate,
+                "packets_tx_per_sec": i.packets_tx_rate,
+                "errors_received": i.errors_received,
+                "errors_transmitted": i.errors_transmitted,
+                "errors_rx_per_sec": i.errors_rx_rate,
+                "errors_tx_per_sec": i.errors_tx_rate,
             })).collect::<Vec<_>>(),
         })),
         "battery": battery.as_ref().and_then(|b| {
             if b.is_present {
                 Some(json!({
+                    "name": b.name,
                     "percentage": b.percentage,
                     "status": b.status,
                     "is_charging": b.is_charging,
@@ -269,19 +430,77 @@ fn export_json(
                 None
             }
         }),
+        "batteries": batteries.iter().map(|b| json!({
+            "name": b.name,
+            "percentage": b.percentage,
+            "status": b.status,
+            "is_charging": b.is_charging,
+            "time_remaining_seconds": b.time_remaining,
+            "health_percent": b.health,
+            "energy_now_wh": b.energy_now,
+            "energy_full_wh": b.energy_full,
+            "energy_full_design_wh": b.energy_full_design,
+            "power_w": b.power_now,
+            "technology": b.technology,
+            "vendor": b.vendor,
+        })).collect::<Vec<_>>(),
+        "sensors": sensors.as_ref().map(|s| json!({
+            "temperatures": s.temperatures.iter().map(|t| json!({
+                "label": t.label,
+                "current_celsius": t.current,
+                "max_celsius": t.max,
+                "critical_celsius": t.critical,
+                "status": t.status(),
+            })).collect::<Vec<_>>(),
+            "fans": s.fans.iter().map(|f| json!({
+                "label": f.label,
+                "rpm": f.rpm,
+                "min_rpm": f.min_rpm,
+                "max_rpm": f.max_rpm,
+            })).collect::<Vec<_>>(),
+        })),
+        "pressure": pressure,
+        "gpus": gpus.iter().map(|g| json!({
+            "index": g.index,
+            "name": g.name,
+            "vendor": g.vendor,
+            "driver": g.driver,
+            "source": g.source,
+            "bus_id": g.bus_id,
+            "utilization_percent": g.utilization,
+            "memory_used_bytes": g.memory_used,
+            "memory_total_bytes": g.memory_total,
+            "core_clock_mhz": g.core_clock_mhz,
+            "memory_clock_mhz": g.memory_clock_mhz,
+            "temperature_celsius": g.temperature,
+            "power_watts": g.power_watts,
+        })).collect::<Vec<_>>(),
         "processes": processes.iter().map(|p| json!({
             "pid": p.pid,
+            "ppid": p.ppid,
             "name": p.name,
             "user": p.user,
+            "uid": p.uid,
+            "effective_user": p.effective_user,
+            "euid": p.euid,
+            "effective_group": p.effective_group,
+            "egid": p.egid,
             "cpu_percent": p.cpu_usage,
             "memory_percent": p.memory_usage,
             "disk_read_bytes": p.disk_read,
             "disk_write_bytes": p.disk_write,
+            "disk_read_bytes_per_sec": p.disk_read_rate,
+            "disk_write_bytes_per_sec": p.disk_write_rate,
             "threads": p.threads,
             "status": p.status,
             "executable": p.exe_path,
+            "cgroup": p.cgroup,
+            "container": p.container,
         })).collect::<Vec<_>>(),
     });
+    if let Some(generated) = generated {
+        data["timestamp"] = json!(generated);
+    }
     
     let mut file = File::create(filename)?;
     let json_string = serde_json::to_string_pretty(&data)?;
@@ -290,18 +509,9 @@ fn export_json(
     Ok(filename.to_string())
 }
 
-fn export_html(
-    cpu: &Option<CpuSnapshot>,
-    memory: &Option<MemorySnapshot>,
-    disk: &Option<DiskSnapshot>,
-    network: &Option<NetworkSnapshot>,
-    battery: &Option<BatteryInfo>,
-    processes: &[ProcessInfo],
-    disk_list: &[DiskInfo],
-    filename: &str,
-) -> Result<String> {
+fn export_html(snapshot: &SystemSnapshot, filename: &str, generated: Option<&str>) -> Result<String> {
+    let SystemSnapshot { cpu, memory, disk, network, battery, batteries, sensors, gpus, processes, disk_list, disk_io, .. } = snapshot;
     let mut file = File::create(filename)?;
-    let timestamp = Local::now().format("%Y-%m-%d %H:%M:%S");
     
     // Start HTML
     writeln!(file, "<!DOCTYPE html>")?;
@@ -309,7 +519,10 @@ fn export_html(
     writeln!(file, "<head>")?;
     writeln!(file, "    <meta charset=\"UTF-8\">")?;
     writeln!(file, "    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">")?;
-    writeln!(file, "    <title>Systrix System Report - {}</title>", timestamp)?;
+    match generated {
+        Some(generated) => writeln!(file, "    <title>Systrix System Report - {}</title>", generated)?,
+        None => writeln!(file, "    <title>Systrix System Report</title>")?,
+    }
     writeln!(file, "    <style>")?;
     
     // CSS
@@ -323,7 +536,9 @@ fn export_html(
     writeln!(file, "    <div class=\"container\">")?;
     writeln!(file, "        <header>")?;
     writeln!(file, "            <h1>🖥️ Systrix System Report</h1>")?;
-    writeln!(file, "            <p class=\"timestamp\">Generated: {}</p>", timestamp)?;
+    if let Some(generated) = generated {
+        writeln!(file, "            <p class=\"timestamp\">Generated: {}</p>", generated)?;
+    }
     if let Some(cpu_data) = cpu {
         writeln!(file, "            <p class=\"device-name\">Device: {}</p>", cpu_data.hostname)?;
     }
@@ -403,6 +618,11 @@ fn export_html(
                 writeln!(file, "                <p class=\"detail\">Time: {}</p>", crate::monitor::battery::format_time_remaining(time))?;
             }
             writeln!(file, "                <p class=\"detail\">Health: {:.0}%</p>", bat_data.health)?;
+            if batteries.len() > 1 {
+                for bat in batteries {
+                    writeln!(file, "                <p class=\"detail\">{}: {:.0}% ({})</p>", bat.name, bat.percentage, bat.status)?;
+                }
+            }
             writeln!(file, "            </div>")?;
         }
     }
@@ -425,6 +645,29 @@ fn export_html(
         writeln!(file, "            </div>")?;
     }
     
+    // Memory Breakdown
+    if let Some(detail) = memory.as_ref().and_then(|m| m.detail.as_ref()) {
+        writeln!(file, "            <div class=\"card\">")?;
+        writeln!(file, "                <h2>🧠 Memory Breakdown</h2>")?;
+        writeln!(file, "                <table>")?;
+        for (label, bytes) in detail.breakdown() {
+            writeln!(file, "                    <tr><td>{}</td><td>{}</td></tr>", label, crate::utils::format_bytes(bytes))?;
+        }
+        writeln!(file, "                    <tr><td>Shared</td><td>{}</td></tr>", crate::utils::format_bytes(detail.shared))?;
+        writeln!(file, "                    <tr><td>Dirty / Writeback</td><td>{} / {}</td></tr>",
+            crate::utils::format_bytes(detail.dirty),
+            crate::utils::format_bytes(detail.writeback))?;
+        writeln!(file, "                    <tr><td>Committed</td><td>{} of {} ({:.0}%)</td></tr>",
+            crate::utils::format_bytes(detail.committed_as),
+            crate::utils::format_bytes(detail.commit_limit),
+            detail.commit_percent())?;
+        if detail.hugepages_total > 0 {
+            writeln!(file, "                    <tr><td>Huge Pages</td><td>{} of {} free</td></tr>", detail.hugepages_free, detail.hugepages_total)?;
+        }
+        writeln!(file, "                </table>")?;
+        writeln!(file, "            </div>")?;
+    }
+    
     // Network Info
     if let Some(net_data) = network {
         writeln!(file, "            <div class=\"card\">")?;
@@ -436,18 +679,69 @@ fn export_html(
         if !net_data.interfaces.is_empty() {
             writeln!(file, "                <h3>Interfaces</h3>")?;
             writeln!(file, "                <table class=\"interfaces-table\">")?;
-            writeln!(file, "                    <tr><th>Name</th><th>RX</th><th>TX</th></tr>")?;
+            writeln!(file, "                    <tr><th>Name</th><th>RX</th><th>TX</th><th>RX Rate</th><th>TX Rate</th></tr>")?;
             for iface in &net_data.interfaces {
-                writeln!(file, "                    <tr><td>{}</td><td>{}</td><td>{}</td></tr>", 
+                writeln!(file, "                    <tr><td>{}</td><td>{}</td><td>{}</td><td>{}/s</td><td>{}/s</td></tr>", 
                     iface.name,
                     crate::utils::format_bytes(iface.received),
-                    crate::utils::format_bytes(iface.transmitted))?;
+                    crate::utils::format_bytes(iface.transmitted),
+                    crate::utils::format_bytes(iface.rx_rate),
+                    crate::utils::format_bytes(iface.tx_rate))?;
             }
             writeln!(file, "                </table>")?;
         }
         writeln!(file, "            </div>")?;
     }
     
+    // Sensors
+    if let Some(sensors_data) = sensors {
+        if !sensors_data.temperatures.is_empty() || !sensors_data.fans.is_empty() {
+            writeln!(file, "            <div class=\"card\">")?;
+            writeln!(file, "                <h2>🌡️ Sensors</h2>")?;
+            writeln!(file, "                <table>")?;
+            writeln!(file, "                    <tr><th>Sensor</th><th>Current</th><th>Critical</th></tr>")?;
+            for temp in &sensors_data.temperatures {
+                let temp_color = match temp.status() {
+                    crate::monitor::sensors::SensorStatus::Critical => "red",
+                    crate::monitor::sensors::SensorStatus::Hot => "yellow",
+                    crate::monitor::sensors::SensorStatus::Normal => "",
+                };
+                writeln!(file, "                    <tr><td>{}</td><td class=\"{}\"><strong>{:.1}°C</strong></td><td>{}</td></tr>",
+                    temp.label,
+                    temp_color,
+                    temp.current,
+                    temp.critical.map(|c| format!("{:.0}°C", c)).unwrap_or_else(|| "-".to_string()))?;
+            }
+            for fan in &sensors_data.fans {
+                writeln!(file, "                    <tr><td>{}</td><td>{} RPM</td><td>-</td></tr>", fan.label, fan.rpm)?;
+            }
+            writeln!(file, "                </table>")?;
+            writeln!(file, "            </div>")?;
+        }
+    }
+    
+    // GPUs
+    if !gpus.is_empty() {
+        writeln!(file, "            <div class=\"card\">")?;
+        writeln!(file, "                <h2>🎮 GPU</h2>")?;
+        writeln!(file, "                <table>")?;
+        writeln!(file, "                    <tr><th>Name</th><th>Utilization</th><th>Memory</th><th>Clock</th><th>Temp</th></tr>")?;
+        for gpu in gpus {
+            let util = gpu.utilization.unwrap_or(0.0);
+            let gpu_color = if util > 80.0 { "red" } else if util > 60.0 { "yellow" } else { "green" };
+            let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+            writeln!(file, "                    <tr><td>{}</td><td class=\"{}\"><strong>{}</strong></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                gpu.name,
+                gpu_color,
+                or_dash(gpu.utilization.map(|u| format!("{:.0}%", u))),
+                or_dash(gpu.memory_used.zip(gpu.memory_total).map(|(used, total)| format!("{} / {}", crate::utils::format_bytes(used), crate::utils::format_bytes(total)))),
+                or_dash(gpu.core_clock_mhz.map(|mhz| format!("{} MHz", mhz))),
+                or_dash(gpu.temperature.map(|t| format!("{:.0}°C", t))))?;
+        }
+        writeln!(file, "                </table>")?;
+        writeln!(file, "            </div>")?;
+    }
+    
     // Disk Partitions
     if !disk_list.is_empty() {
         writeln!(file, "            <div class=\"card\">")?;
@@ -469,6 +763,28 @@ fn export_html(
         writeln!(file, "            </div>")?;
     }
     
+    // Disk I/O
+    if !disk_io.is_empty() {
+        writeln!(file, "            <div class=\"card\">")?;
+        writeln!(file, "                <h2>⏱️ Disk I/O</h2>")?;
+        writeln!(file, "                <table class=\"disk-table\">")?;
+        writeln!(file, "                    <tr><th>Device</th><th>Read/s</th><th>Write/s</th><th>IOPS</th><th>Await</th><th>Util</th><th>Mounts</th></tr>")?;
+        for device in disk_io {
+            let util_color = if device.utilization > 90.0 { "red" } else if device.utilization > 60.0 { "yellow" } else { "green" };
+            writeln!(file, "                    <tr><td>{}</td><td>{}/s</td><td>{}/s</td><td>{:.0}</td><td>{:.1} ms</td><td class=\"{}\"><strong>{:.0}%</strong></td><td>{}</td></tr>",
+                device.device,
+                crate::utils::format_bytes(device.read_bytes_per_sec),
+                crate::utils::format_bytes(device.write_bytes_per_sec),
+                device.read_iops + device.write_iops,
+                device.await_ms,
+                util_color,
+                device.utilization,
+                device.mount_points.join(", "))?;
+        }
+        writeln!(file, "                </table>")?;
+        writeln!(file, "            </div>")?;
+    }
+    
     writeln!(file, "        </div>")?;
     
     // Processes Table
@@ -487,8 +803,10 @@ fn export_html(
         writeln!(file, "                            <th onclick=\"sortTable(2)\">User ▼</th>")?;
         writeln!(file, "                            <th onclick=\"sortTable(3)\">CPU % ▼</th>")?;
         writeln!(file, "                            <th onclick=\"sortTable(4)\">Memory % ▼</th>")?;
-        writeln!(file, "                            <th onclick=\"sortTable(5)\">Threads ▼</th>")?;
-        writeln!(file, "                            <th onclick=\"sortTable(6)\">Status ▼</th>")?;
+        writeln!(file, "                            <th onclick=\"sortTable(5)\">Read/s ▼</th>")?;
+        writeln!(file, "                            <th onclick=\"sortTable(6)\">Write/s ▼</th>")?;
+        writeln!(file, "                            <th onclick=\"sortTable(7)\">Threads ▼</th>")?;
+        writeln!(file, "                            <th onclick=\"sortTable(8)\">Status ▼</th>")?;
         writeln!(file, "                        </tr>")?;
         writeln!(file, "                    </thead>")?;
         writeln!(file, "                    <tbody>")?;
@@ -503,6 +821,8 @@ fn export_html(
             writeln!(file, "                            <td>{}</td>", proc.user)?;
             writeln!(file, "                            <td class=\"{}\"><strong>{:.1}%</strong></td>", cpu_class, proc.cpu_usage)?;
             writeln!(file, "                            <td class=\"{}\"><strong>{:.1}%</strong></td>", mem_class, proc.memory_usage)?;
+            writeln!(file, "                            <td>{}/s</td>", crate::utils::format_bytes(proc.disk_read_rate))?;
+            writeln!(file, "                            <td>{}/s</td>", crate::utils::format_bytes(proc.disk_write_rate))?;
             writeln!(file, "                            <td>{}</td>", proc.threads)?;
             writeln!(file, "                            <td>{}</td>", proc.status)?;
             writeln!(file, "                        </tr>")?;
@@ -517,7 +837,9 @@ fn export_html(
     // Footer
     writeln!(file, "        <footer>")?;
     writeln!(file, "            <p>Generated by <strong>Systrix v{}</strong> - System Monitor</p>", env!("CARGO_PKG_VERSION"))?;
-    writeln!(file, "            <p>Report generated at {}</p>", timestamp)?;
+    if let Some(generated) = generated {
+        writeln!(file, "            <p>Report generated at {}</p>", generated)?;
+    }
     writeln!(file, "        </footer>")?;
     writeln!(file, "    </div>")?;
     
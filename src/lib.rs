@@ -4,6 +4,8 @@
 //! This library provides system monitoring capabilities that can be used
 //! by both the CLI and TUI interfaces, as well as by external applications.
 
+pub mod alerts;
+pub mod config;
 pub mod export;
 pub mod monitor;
 pub mod plugins;
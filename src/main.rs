@@ -10,30 +10,42 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod cli;
 mod app;
-mod export;
-mod monitor;
-mod utils;
-mod plugins;
 
 #[cfg(feature = "tui")]
 mod tui;
 
+// The monitoring itself lives in the library; `crate::monitor` and the like
+// refer to it from the modules above
+use systrix::{alerts, config, export, monitor, utils};
+
 #[cfg(feature = "remote")]
-mod remote_agent;
+use systrix::remote_agent;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing/logging
-    tracing_subscriber::registry()
+    // Parse CLI arguments
+    let cli = cli::Cli::parse();
+
+    // Initialize tracing/logging; RUST_LOG wins over the configured level,
+    // and a broken config file is reported by the command that reads it
+    let log_level = config::Config::load_or_default(cli.config.as_deref())
+        .map(|config| config.general.log_level)
+        .unwrap_or_default();
+    // Logs go to stderr, which keeps printed results apart from them
+    let registry = tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "systrix=info".into()),
-        )
-        .with(tracing_subscriber::fmt::layer())
+                .unwrap_or_else(|_| format!("systrix={}", log_level).into()),
+        );
+    #[cfg(feature = "tui")]
+    registry
+        .with(tracing_subscriber::fmt::layer().with_writer(app::log_writer))
+        .with(app::FooterLog)
+        .init();
+    #[cfg(not(feature = "tui"))]
+    registry
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
         .init();
-
-    // Parse CLI arguments
-    let cli = cli::Cli::parse();
 
     // Execute the appropriate command
     cli::execute(cli).await
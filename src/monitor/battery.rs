@@ -3,9 +3,12 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BatteryInfo {
+    /// Device name such as `BAT0`; combined views join the names with `+`
+    pub name: String,
     pub is_present: bool,
     pub percentage: f32,
     pub is_charging: bool,
@@ -15,11 +18,20 @@ pub struct BatteryInfo {
     pub status: String,
     pub technology: String,
     pub vendor: String,
+    /// Remaining energy in Wh
+    pub energy_now: Option<f64>,
+    /// Energy when full in Wh
+    pub energy_full: Option<f64>,
+    /// Energy when full as designed, in Wh
+    pub energy_full_design: Option<f64>,
+    /// Charge or discharge rate in W
+    pub power_now: Option<f64>,
 }
 
 impl Default for BatteryInfo {
     fn default() -> Self {
         Self {
+            name: String::new(),
             is_present: false,
             percentage: 0.0,
             is_charging: false,
@@ -29,29 +41,33 @@ impl Default for BatteryInfo {
             status: "Unknown".to_string(),
             technology: "Unknown".to_string(),
             vendor: "Unknown".to_string(),
+            energy_now: None,
+            energy_full: None,
+            energy_full_design: None,
+            power_now: None,
         }
     }
 }
 
-pub async fn get_battery_info() -> Result<BatteryInfo> {
-    #[cfg(target_os = "windows")]
-    {
-        get_battery_info_windows().await
-    }
-    
+/// Every battery in the system; on Linux they are read below `sysfs_root`
+pub async fn get_batteries(sysfs_root: &Path) -> Result<Vec<BatteryInfo>> {
     #[cfg(target_os = "linux")]
     {
-        get_battery_info_linux().await
+        Ok(read_power_supply(sysfs_root))
     }
     
-    #[cfg(target_os = "macos")]
+    #[cfg(not(target_os = "linux"))]
     {
-        get_battery_info_macos().await
-    }
-    
-    #[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
-    {
-        Ok(BatteryInfo::default())
+        let _ = sysfs_root;
+        
+        #[cfg(target_os = "windows")]
+        let battery = get_battery_info_windows().await?;
+        #[cfg(target_os = "macos")]
+        let battery = get_battery_info_macos().await?;
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let battery = BatteryInfo::default();
+        
+        Ok(if battery.is_present { vec![battery] } else { Vec::new() })
     }
 }
 
@@ -121,6 +137,7 @@ async fn get_battery_info_windows() -> Result<BatteryInfo> {
                 .to_string();
                 
                 return Ok(BatteryInfo {
+                    name: "Battery".to_string(),
                     is_present: true,
                     percentage,
                     is_charging,
@@ -130,6 +147,7 @@ async fn get_battery_info_windows() -> Result<BatteryInfo> {
                     status,
                     technology: "Li-ion".to_string(), // Assume Li-ion
                     vendor: "Unknown".to_string(),
+                    ..BatteryInfo::default()
                 });
             }
         }
@@ -139,82 +157,161 @@ async fn get_battery_info_windows() -> Result<BatteryInfo> {
     Ok(BatteryInfo::default())
 }
 
-#[cfg(target_os = "linux")]
-async fn get_battery_info_linux() -> Result<BatteryInfo> {
-    use std::fs;
-    use std::path::Path;
-    
-    let battery_path = Path::new("/sys/class/power_supply/BAT0");
-    
-    if !battery_path.exists() {
-        // Try BAT1
-        let battery_path = Path::new("/sys/class/power_supply/BAT1");
-        if !battery_path.exists() {
-            return Ok(BatteryInfo::default());
-        }
-    }
+/// Every battery of type `Battery` below `<sysfs_root>/class/power_supply`.
+///
+/// Handles both energy based (`energy_*`/`power_now`, µWh and µW) and charge
+/// based (`charge_*`/`current_now`, µAh and µA) devices. Charge readings are
+/// converted to energy using the reported voltage when there is one.
+pub fn read_power_supply(sysfs_root: &Path) -> Vec<BatteryInfo> {
+    let Ok(entries) = std::fs::read_dir(sysfs_root.join("class/power_supply")) else {
+        return Vec::new();
+    };
+    let mut supplies: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    supplies.sort();
     
+    supplies.iter().filter_map(|supply| read_battery(supply)).collect()
+}
+
+fn read_battery(supply: &Path) -> Option<BatteryInfo> {
     let read_file = |name: &str| -> Option<String> {
-        fs::read_to_string(battery_path.join(name)).ok()
+        std::fs::read_to_string(supply.join(name)).ok()
             .map(|s| s.trim().to_string())
     };
+    let read_number = |name: &str| read_file(name).and_then(|s| s.parse::<f64>().ok());
     
-    let capacity = read_file("capacity")
-        .and_then(|s| s.parse::<f32>().ok())
-        .unwrap_or(0.0);
+    if read_file("type").as_deref() != Some("Battery") || read_file("present").as_deref() == Some("0") {
+        return None;
+    }
+    // Batteries of peripherals such as wireless mice do not power the system
+    if read_file("scope").as_deref() == Some("Device") {
+        return None;
+    }
     
     let status = read_file("status").unwrap_or_else(|| "Unknown".to_string());
     let is_charging = status == "Charging";
-    let is_plugged = status == "Charging" || status == "Full";
+    let is_plugged = matches!(status.as_str(), "Charging" | "Full" | "Not charging");
     
-    let technology = read_file("technology").unwrap_or_else(|| "Unknown".to_string());
-    let vendor = read_file("manufacturer").unwrap_or_else(|| "Unknown".to_string());
+    // (now, full, full_design, rate) in the device's own units
+    let energy = (read_number("energy_now"), read_number("energy_full"), read_number("energy_full_design"), read_number("power_now"));
+    let charge = (read_number("charge_now"), read_number("charge_full"), read_number("charge_full_design"), read_number("current_now"));
+    let uses_charge = energy.0.is_none() && charge.0.is_some();
+    let (now, full, design, rate) = if uses_charge { charge } else { energy };
+    // Some drivers report a negative current while discharging
+    let rate = rate.map(f64::abs);
     
-    // Calculate time remaining if available
-    let time_remaining = if let (Some(energy_now), Some(power_now)) = 
-        (read_file("energy_now"), read_file("power_now")) {
-        if let (Ok(energy), Ok(power)) = (energy_now.parse::<f64>(), power_now.parse::<f64>()) {
-            if power > 0.0 {
-                Some(((energy / power) * 3600.0) as u64)
-            } else {
-                None
-            }
-        } else {
-            None
-        }
-    } else {
-        None
+    // µWh and µW, or µAh and µA times µV, to Wh and W
+    let voltage = read_number("voltage_now").or_else(|| read_number("voltage_min_design"));
+    let scale = |value: Option<f64>| match (uses_charge, value) {
+        (false, Some(value)) => Some(value / 1_000_000.0),
+        (true, Some(value)) => voltage.map(|volts| value * volts / 1_000_000_000_000.0),
+        _ => None,
     };
     
-    // Calculate health
-    let health = if let (Some(energy_full), Some(energy_design)) = 
-        (read_file("energy_full"), read_file("energy_full_design")) {
-        if let (Ok(full), Ok(design)) = (energy_full.parse::<f64>(), energy_design.parse::<f64>()) {
-            if design > 0.0 {
-                ((full / design) * 100.0) as f32
-            } else {
-                100.0
-            }
-        } else {
-            100.0
-        }
-    } else {
-        100.0
+    let percentage = read_number("capacity")
+        .or_else(|| match (now, full) {
+            (Some(now), Some(full)) if full > 0.0 => Some(now / full * 100.0),
+            _ => None,
+        })
+        .unwrap_or(0.0) as f32;
+    
+    // Time to empty while discharging, time to full while charging
+    let time_remaining = match (now, full, rate) {
+        (Some(now), Some(full), Some(rate)) if is_charging && rate > 0.0 => Some(((full - now).max(0.0) / rate * 3600.0) as u64),
+        (Some(now), _, Some(rate)) if !is_plugged && rate > 0.0 => Some((now / rate * 3600.0) as u64),
+        _ => None,
     };
     
-    Ok(BatteryInfo {
+    let health = match (full, design) {
+        (Some(full), Some(design)) if design > 0.0 => (full / design * 100.0) as f32,
+        _ => 100.0,
+    };
+    
+    Some(BatteryInfo {
+        name: supply.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
         is_present: true,
-        percentage: capacity,
+        percentage,
         is_charging,
         is_plugged,
         time_remaining,
         health,
         status,
-        technology,
-        vendor,
+        technology: read_file("technology").unwrap_or_else(|| "Unknown".to_string()),
+        vendor: read_file("manufacturer").unwrap_or_else(|| "Unknown".to_string()),
+        energy_now: scale(now),
+        energy_full: scale(full),
+        energy_full_design: scale(design),
+        power_now: scale(rate),
     })
 }
 
+/// Merge several batteries into the view of one.
+///
+/// Charge and health are weighted by energy when every battery reports it,
+/// so a small secondary battery does not skew the total.
+pub fn combine(batteries: &[BatteryInfo]) -> BatteryInfo {
+    match batteries {
+        [] => return BatteryInfo::default(),
+        [battery] => return battery.clone(),
+        _ => {}
+    }
+    
+    let sum = |field: fn(&BatteryInfo) -> Option<f64>| -> Option<f64> {
+        batteries.iter().map(field).sum()
+    };
+    let energy_now = sum(|b| b.energy_now);
+    let energy_full = sum(|b| b.energy_full);
+    let energy_full_design = sum(|b| b.energy_full_design);
+    let power_now = sum(|b| b.power_now);
+    let mean = |field: fn(&BatteryInfo) -> f32| batteries.iter().map(field).sum::<f32>() / batteries.len() as f32;
+    
+    let is_charging = batteries.iter().any(|b| b.is_charging);
+    let is_plugged = batteries.iter().any(|b| b.is_plugged);
+    let status = if is_charging {
+        "Charging".to_string()
+    } else if batteries.iter().all(|b| b.status == "Full") {
+        "Full".to_string()
+    } else if let Some(b) = batteries.iter().find(|b| b.status == "Discharging") {
+        b.status.clone()
+    } else {
+        batteries[0].status.clone()
+    };
+    
+    let percentage = match (energy_now, energy_full) {
+        (Some(now), Some(full)) if full > 0.0 => (now / full * 100.0) as f32,
+        _ => mean(|b| b.percentage),
+    };
+    let health = match (energy_full, energy_full_design) {
+        (Some(full), Some(design)) if design > 0.0 => (full / design * 100.0) as f32,
+        _ => mean(|b| b.health),
+    };
+    let time_remaining = match (energy_now, energy_full, power_now) {
+        (Some(now), Some(full), Some(power)) if is_charging && power > 0.0 => Some(((full - now).max(0.0) / power * 3600.0) as u64),
+        (Some(now), _, Some(power)) if !is_plugged && power > 0.0 => Some((now / power * 3600.0) as u64),
+        _ => None,
+    };
+    
+    let mut vendors: Vec<&str> = batteries.iter().map(|b| b.vendor.as_str()).collect();
+    vendors.sort();
+    vendors.dedup();
+    
+    BatteryInfo {
+        name: batteries.iter().map(|b| b.name.as_str()).collect::<Vec<_>>().join("+"),
+        is_present: true,
+        percentage,
+        is_charging,
+        is_plugged,
+        time_remaining,
+        health,
+        status,
+        technology: batteries[0].technology.clone(),
+        vendor: vendors.join(", "),
+        energy_now,
+        energy_full,
+        energy_full_design,
+        power_now,
+    }
+}
+
 #[cfg(target_os = "macos")]
 async fn get_battery_info_macos() -> Result<BatteryInfo> {
     use std::process::Command;
@@ -231,6 +328,7 @@ async fn get_battery_info_macos() -> Result<BatteryInfo> {
     for line in output_str.lines() {
         if line.contains("InternalBattery") {
             battery_info.is_present = true;
+            battery_info.name = "InternalBattery-0".to_string();
             
             // Extract percentage
             if let Some(pct_start) = line.find(char::is_numeric) {
@@ -330,3 +428,32 @@ pub fn get_battery_color(percentage: f32, is_charging: bool) -> ratatui::style::
         _ => Color::Red,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn battery(name: &str, percentage: f32, status: &str) -> BatteryInfo {
+        BatteryInfo {
+            name: name.to_string(),
+            is_present: true,
+            percentage,
+            is_charging: status == "Charging",
+            is_plugged: status == "Charging" || status == "Full",
+            status: status.to_string(),
+            ..BatteryInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_combine_without_energy_averages() {
+        assert!(!combine(&[]).is_present);
+        assert_eq!(combine(&[battery("BAT0", 40.0, "Full")]).name, "BAT0");
+        
+        let combined = combine(&[battery("BAT0", 100.0, "Full"), battery("BAT1", 50.0, "Charging")]);
+        assert_eq!(combined.percentage, 75.0);
+        assert_eq!(combined.status, "Charging");
+        assert!(combined.is_plugged);
+        assert_eq!(combined.time_remaining, None);
+    }
+}
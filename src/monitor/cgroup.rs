@@ -0,0 +1,369 @@
+// src/monitor/cgroup.rs
+//! Control group and container detection.
+//!
+//! Processes are mapped to their cgroup v2 path through `/proc/<pid>/cgroup`,
+//! and the path is used to recognise the container or systemd unit the
+//! process runs in. Resource usage is read from the cgroup v2 hierarchy.
+//! Both the procfs and the cgroupfs root are parameters so that fixtures can
+//! stand in for the live system.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::pressure::{self, PressureSnapshot};
+use super::ProcessInfo;
+
+/// Runtime that created a cgroup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContainerRuntime {
+    Docker,
+    Containerd,
+    Podman,
+    /// A systemd service, scope or slice rather than a real container
+    Systemd,
+}
+
+impl fmt::Display for ContainerRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ContainerRuntime::Docker => "docker",
+            ContainerRuntime::Containerd => "containerd",
+            ContainerRuntime::Podman => "podman",
+            ContainerRuntime::Systemd => "systemd",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Container or systemd unit a process belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Container {
+    pub runtime: ContainerRuntime,
+    /// Full container ID, or the unit name for systemd
+    pub id: String,
+}
+
+impl Container {
+    /// Container IDs shortened the way `docker ps` shows them
+    pub fn short_id(&self) -> &str {
+        match self.runtime {
+            ContainerRuntime::Systemd => &self.id,
+            _ => &self.id[..self.id.len().min(12)],
+        }
+    }
+}
+
+impl fmt::Display for Container {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.runtime, self.short_id())
+    }
+}
+
+/// `cpu.stat` of a cgroup, in microseconds
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CgroupCpuStat {
+    pub usage_usec: u64,
+    pub user_usec: u64,
+    pub system_usec: u64,
+    pub nr_periods: u64,
+    pub nr_throttled: u64,
+    pub throttled_usec: u64,
+}
+
+/// `io.stat` of a cgroup, summed over all devices
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CgroupIoStat {
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+    pub read_ios: u64,
+    pub write_ios: u64,
+}
+
+/// Resource usage of one cgroup. Controllers that are not enabled for the
+/// cgroup are left empty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CgroupStats {
+    pub path: String,
+    pub container: Option<Container>,
+    /// Number of processes found in the cgroup
+    pub processes: usize,
+    pub cpu: Option<CgroupCpuStat>,
+    pub memory_current: Option<u64>,
+    pub io: Option<CgroupIoStat>,
+    /// Pressure stall information from the `*.pressure` files
+    pub pressure: Option<PressureSnapshot>,
+}
+
+/// Processes aggregated by the container they run in
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProcessGroup {
+    /// `None` for processes outside of any container or unit
+    pub container: Option<Container>,
+    pub pids: Vec<u32>,
+    pub cpu_usage: f32,
+    pub memory_usage: f32,
+    pub disk_read_rate: u64,
+    pub disk_write_rate: u64,
+    pub threads: usize,
+}
+
+impl ProcessGroup {
+    pub fn name(&self) -> String {
+        group_name(self.container.as_ref())
+    }
+}
+
+/// Display name of the group a process with this container belongs to
+pub fn group_name(container: Option<&Container>) -> String {
+    container.map(|c| c.to_string()).unwrap_or_else(|| "host".to_string())
+}
+
+/// Extract the cgroup v2 path from the contents of `/proc/<pid>/cgroup`.
+///
+/// On hybrid hierarchies without a unified entry the systemd v1 hierarchy
+/// is used instead, since it mirrors the same unit layout.
+pub fn parse_cgroup_path(content: &str) -> Option<String> {
+    let mut fallback = None;
+
+    for line in content.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        if id == "0" && controllers.is_empty() {
+            return Some(path.to_string());
+        }
+        if controllers == "name=systemd" {
+            fallback = Some(path.to_string());
+        }
+    }
+
+    fallback
+}
+
+/// Recognise the container or systemd unit from a cgroup path
+pub fn container_from_path(path: &str) -> Option<Container> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    // Containers may be nested in slices, so look at the deepest segment first
+    for (i, segment) in segments.iter().enumerate().rev() {
+        let scope = segment.strip_suffix(".scope").unwrap_or(segment);
+        let parent = if i > 0 { segments[i - 1] } else { "" };
+
+        let container = if let Some(id) = scope.strip_prefix("docker-") {
+            Some((ContainerRuntime::Docker, id))
+        } else if let Some(id) = scope.strip_prefix("cri-containerd-") {
+            Some((ContainerRuntime::Containerd, id))
+        } else if let Some(id) = scope.strip_prefix("libpod-").filter(|id| !id.starts_with("conmon-")) {
+            Some((ContainerRuntime::Podman, id))
+        } else if is_container_id(segment) {
+            // cgroupfs driver layouts: /docker/<id>, /kubepods/<qos>/pod<uid>/<id>
+            let runtime = match parent {
+                "docker" => ContainerRuntime::Docker,
+                p if p.starts_with("libpod") => ContainerRuntime::Podman,
+                _ => ContainerRuntime::Containerd,
+            };
+            Some((runtime, *segment))
+        } else {
+            None
+        };
+
+        if let Some((runtime, id)) = container.filter(|(_, id)| is_container_id(id)) {
+            return Some(Container { runtime, id: id.to_string() });
+        }
+    }
+
+    segments.last()
+        .filter(|unit| unit.ends_with(".service") || unit.ends_with(".scope") || unit.ends_with(".slice"))
+        .map(|unit| Container {
+            runtime: ContainerRuntime::Systemd,
+            id: unit.to_string(),
+        })
+}
+
+fn is_container_id(id: &str) -> bool {
+    id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Read the cgroup path of `pid` from a procfs mounted at `proc_root`
+pub fn read_process_cgroup(proc_root: &Path, pid: u32) -> Option<String> {
+    let content = std::fs::read_to_string(proc_root.join(pid.to_string()).join("cgroup")).ok()?;
+    parse_cgroup_path(&content)
+}
+
+/// Locate the cgroup v2 hierarchy below `cgroup_root`, which is either
+/// the unified hierarchy itself or a hybrid mount with a `unified` child.
+pub fn unified_root(cgroup_root: &Path) -> PathBuf {
+    let hybrid = cgroup_root.join("unified");
+    if !cgroup_root.join("cgroup.controllers").exists() && hybrid.is_dir() {
+        hybrid
+    } else {
+        cgroup_root.to_path_buf()
+    }
+}
+
+pub fn parse_cpu_stat(content: &str) -> CgroupCpuStat {
+    let mut stat = CgroupCpuStat::default();
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(' ') else {
+            continue;
+        };
+        let value = value.trim().parse().unwrap_or(0);
+        match key {
+            "usage_usec" => stat.usage_usec = value,
+            "user_usec" => stat.user_usec = value,
+            "system_usec" => stat.system_usec = value,
+            "nr_periods" => stat.nr_periods = value,
+            "nr_throttled" => stat.nr_throttled = value,
+            "throttled_usec" => stat.throttled_usec = value,
+            _ => {}
+        }
+    }
+
+    stat
+}
+
+pub fn parse_io_stat(content: &str) -> CgroupIoStat {
+    let mut stat = CgroupIoStat::default();
+
+    // One line per device: "8:0 rbytes=1 wbytes=2 rios=3 wios=4 ..."
+    for field in content.split_whitespace() {
+        let Some((key, value)) = field.split_once('=') else {
+            continue;
+        };
+        let value: u64 = value.parse().unwrap_or(0);
+        match key {
+            "rbytes" => stat.read_bytes += value,
+            "wbytes" => stat.write_bytes += value,
+            "rios" => stat.read_ios += value,
+            "wios" => stat.write_ios += value,
+            _ => {}
+        }
+    }
+
+    stat
+}
+
+/// Read the resource usage of the cgroup at `path` below `cgroup_root`
+pub fn read_cgroup_stats(cgroup_root: &Path, path: &str) -> CgroupStats {
+    let dir = unified_root(cgroup_root).join(path.trim_start_matches('/'));
+    let read = |name: &str| std::fs::read_to_string(dir.join(name)).ok();
+
+    CgroupStats {
+        path: path.to_string(),
+        container: container_from_path(path),
+        processes: 0,
+        cpu: read("cpu.stat").map(|content| parse_cpu_stat(&content)),
+        memory_current: read("memory.current").and_then(|content| content.trim().parse().ok()),
+        io: read("io.stat").map(|content| parse_io_stat(&content)),
+        pressure: pressure::read_cgroup_pressure(&dir),
+    }
+}
+
+/// Every cgroup that currently holds a process, with its resource usage
+pub fn list_cgroups(proc_root: &Path, cgroup_root: &Path) -> Result<Vec<CgroupStats>> {
+    let mut processes: BTreeMap<String, usize> = BTreeMap::new();
+
+    for entry in std::fs::read_dir(proc_root)? {
+        let entry = entry?;
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        // Processes may exit while the directory is being walked
+        if let Some(path) = read_process_cgroup(proc_root, pid) {
+            *processes.entry(path).or_default() += 1;
+        }
+    }
+
+    Ok(processes.into_iter()
+        .map(|(path, count)| CgroupStats {
+            processes: count,
+            ..read_cgroup_stats(cgroup_root, &path)
+        })
+        .collect())
+}
+
+/// Aggregate a process list by container, busiest group first
+pub fn group_by_container(processes: &[ProcessInfo]) -> Vec<ProcessGroup> {
+    let mut groups: BTreeMap<Option<Container>, ProcessGroup> = BTreeMap::new();
+
+    for process in processes {
+        let group = groups.entry(process.container.clone()).or_insert_with(|| ProcessGroup {
+            container: process.container.clone(),
+            pids: Vec::new(),
+            cpu_usage: 0.0,
+            memory_usage: 0.0,
+            disk_read_rate: 0,
+            disk_write_rate: 0,
+            threads: 0,
+        });
+        group.pids.push(process.pid);
+        group.cpu_usage += process.cpu_usage;
+        group.memory_usage += process.memory_usage;
+        group.disk_read_rate += process.disk_read_rate;
+        group.disk_write_rate += process.disk_write_rate;
+        group.threads += process.threads;
+    }
+
+    let mut groups: Vec<ProcessGroup> = groups.into_values().collect();
+    groups.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage).then_with(|| a.name().cmp(&b.name())));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "4f8a1c2e9b7d6a5f3e2d1c0b9a8f7e6d5c4b3a2f1e0d9c8b7a6f5e4d3c2b1a09";
+
+    #[test]
+    fn test_parse_cgroup_path_prefers_unified_entry() {
+        let content = "12:memory:/docker/abc\n1:name=systemd:/system.slice/sshd.service\n0::/system.slice/sshd.service\n";
+        assert_eq!(parse_cgroup_path(content).as_deref(), Some("/system.slice/sshd.service"));
+
+        let v1_only = "4:memory:/user.slice\n1:name=systemd:/user.slice/user-1000.slice/session-2.scope\n";
+        assert_eq!(parse_cgroup_path(v1_only).as_deref(), Some("/user.slice/user-1000.slice/session-2.scope"));
+    }
+
+    #[test]
+    fn test_container_from_path() {
+        let cases = [
+            (format!("/system.slice/docker-{}.scope", ID), ContainerRuntime::Docker),
+            (format!("/docker/{}", ID), ContainerRuntime::Docker),
+            (format!("/kubepods.slice/kubepods-besteffort.slice/kubepods-besteffort-pod1.slice/cri-containerd-{}.scope", ID), ContainerRuntime::Containerd),
+            (format!("/kubepods/burstable/pod7/{}", ID), ContainerRuntime::Containerd),
+            (format!("/machine.slice/libpod-{}.scope/container", ID), ContainerRuntime::Podman),
+        ];
+
+        for (path, runtime) in cases {
+            let container = container_from_path(&path).unwrap();
+            assert_eq!(container.runtime, runtime, "{}", path);
+            assert_eq!(container.id, ID);
+        }
+    }
+
+    #[test]
+    fn test_container_from_systemd_unit() {
+        let container = container_from_path("/system.slice/nginx.service").unwrap();
+        assert_eq!(container.runtime, ContainerRuntime::Systemd);
+        assert_eq!(container.to_string(), "systemd:nginx.service");
+
+        assert_eq!(container_from_path("/"), None);
+    }
+
+    #[test]
+    fn test_parse_stats() {
+        let cpu = parse_cpu_stat("usage_usec 2500\nuser_usec 2000\nsystem_usec 500\nnr_periods 10\nnr_throttled 2\nthrottled_usec 300\n");
+        assert_eq!(cpu.usage_usec, 2500);
+        assert_eq!(cpu.nr_throttled, 2);
+        assert_eq!(cpu.throttled_usec, 300);
+
+        let io = parse_io_stat("8:0 rbytes=100 wbytes=200 rios=1 wios=2 dbytes=0 dios=0\n259:0 rbytes=50 wbytes=0 rios=1 wios=0\n");
+        assert_eq!(io, CgroupIoStat { read_bytes: 150, write_bytes: 200, read_ios: 2, write_ios: 2 });
+    }
+}
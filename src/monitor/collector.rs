@@ -0,0 +1,327 @@
+// src/monitor/collector.rs
+//! Background collection of monitoring snapshots.
+//!
+//! Each metric is sampled by its own tokio task on its own schedule and the
+//! results are merged into a single [`SystemSnapshot`] published through a
+//! watch channel. Consumers read the latest snapshot without ever waiting on
+//! the (sometimes slow) sampling itself.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use super::{BatteryInfo, CpuSnapshot, DiskInfo, DiskIoStats, DiskSnapshot, GpuInfo, MemorySnapshot, MonitorBackend, NetworkSnapshot, PressureSnapshot, ProcessInfo, ProcessSort, SensorsSnapshot, SocketInfo, SortOrder};
+
+/// Latest known state of every collected metric
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SystemSnapshot {
+    pub cpu: Option<CpuSnapshot>,
+    pub memory: Option<MemorySnapshot>,
+    pub disk: Option<DiskSnapshot>,
+    pub disk_list: Vec<DiskInfo>,
+    pub disk_io: Vec<DiskIoStats>,
+    pub network: Option<NetworkSnapshot>,
+    /// All batteries combined into one
+    pub battery: Option<BatteryInfo>,
+    pub batteries: Vec<BatteryInfo>,
+    pub sensors: Option<SensorsSnapshot>,
+    pub pressure: Option<PressureSnapshot>,
+    pub gpus: Vec<GpuInfo>,
+    pub sockets: Vec<SocketInfo>,
+    pub processes: Vec<ProcessInfo>,
+}
+
+impl SystemSnapshot {
+    /// Look up a numeric metric by its dotted name.
+    ///
+    /// Supported names are `cpu.global`, `cpu.core.<n>`, `memory.percent`,
+    /// `swap.percent`, `disk.percent`, `network.rx_rate`, `network.tx_rate`,
+    /// `battery.percent` and `psi.<cpu|memory|io>.<some|full>.<avg10|avg60|avg300|total>`.
+    /// Returns `None` when the metric is unknown or has not been collected.
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        match name {
+            "cpu.global" => self.cpu.as_ref().map(|c| c.global_usage as f64),
+            "memory.percent" => self.memory.as_ref().map(|m| m.usage_percent as f64),
+            "swap.percent" => self.memory.as_ref().map(|m| m.swap_usage_percent as f64),
+            "disk.percent" => self.disk.as_ref().map(|d| d.usage_percent as f64),
+            "network.rx_rate" => self.network.as_ref().map(|n| n.total_rx_rate as f64),
+            "network.tx_rate" => self.network.as_ref().map(|n| n.total_tx_rate as f64),
+            "battery.percent" => self.battery.as_ref().filter(|b| b.is_present).map(|b| b.percentage as f64),
+            _ => match name.strip_prefix("cpu.core.") {
+                Some(core) => self.cpu.as_ref()?.per_core_usage.get(core.parse::<usize>().ok()?).map(|&u| u as f64),
+                None => self.pressure.as_ref()?.metric(name.strip_prefix("psi.")?),
+            },
+        }
+    }
+}
+
+/// Sampling schedule of the collector tasks
+#[derive(Debug, Clone)]
+pub struct CollectorConfig {
+    pub cpu_interval: Duration,
+    pub memory_interval: Duration,
+    pub disk_interval: Duration,
+    pub disk_io_interval: Duration,
+    pub network_interval: Duration,
+    pub battery_interval: Duration,
+    pub sensors_interval: Duration,
+    pub pressure_interval: Duration,
+    pub gpu_interval: Duration,
+    pub sockets_interval: Duration,
+    pub process_interval: Duration,
+    pub process_limit: usize,
+}
+
+impl CollectorConfig {
+    /// Derive a schedule from a single refresh interval.
+    ///
+    /// Fast-moving metrics follow the refresh interval, while disk capacity,
+    /// sensors, sockets and battery state are sampled less often.
+    pub fn with_refresh_interval(refresh_interval_ms: u64) -> Self {
+        let base = Duration::from_millis(refresh_interval_ms.max(100));
+        Self {
+            cpu_interval: base,
+            memory_interval: base,
+            disk_interval: base * 10,
+            disk_io_interval: base,
+            network_interval: base,
+            battery_interval: base * 20,
+            sensors_interval: base * 4,
+            pressure_interval: base * 2,
+            gpu_interval: base * 2,
+            sockets_interval: base * 4,
+            process_interval: base * 2,
+            process_limit: 100,
+        }
+    }
+
+    /// Sample every metric at the same interval, for backends where reading
+    /// is cheap, such as a recording being played back
+    pub fn uniform(refresh_interval_ms: u64) -> Self {
+        let base = Duration::from_millis(refresh_interval_ms.max(100));
+        Self {
+            cpu_interval: base,
+            memory_interval: base,
+            disk_interval: base,
+            disk_io_interval: base,
+            network_interval: base,
+            battery_interval: base,
+            sensors_interval: base,
+            pressure_interval: base,
+            gpu_interval: base,
+            sockets_interval: base,
+            process_interval: base,
+            process_limit: 100,
+        }
+    }
+
+    pub fn process_limit(mut self, limit: usize) -> Self {
+        self.process_limit = limit;
+        self
+    }
+}
+
+impl Default for CollectorConfig {
+    fn default() -> Self {
+        Self::with_refresh_interval(500)
+    }
+}
+
+/// Handle to the running collector tasks.
+///
+/// Dropping the collector stops all of its tasks.
+pub struct Collector {
+    backend: Arc<dyn MonitorBackend>,
+    sender: Arc<watch::Sender<SystemSnapshot>>,
+    receiver: watch::Receiver<SystemSnapshot>,
+    attempted: Arc<AtomicUsize>,
+    /// Replaced as a whole when the schedule changes
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Collector {
+    /// Start one background task per metric
+    pub fn spawn(backend: Arc<dyn MonitorBackend>, config: CollectorConfig) -> Self {
+        let (sender, receiver) = watch::channel(SystemSnapshot::default());
+        let collector = Self {
+            backend,
+            sender: Arc::new(sender),
+            receiver,
+            attempted: Arc::new(AtomicUsize::new(0)),
+            tasks: Mutex::new(Vec::new()),
+        };
+        collector.start(config);
+        collector
+    }
+
+    /// Run on a new schedule; the last published snapshot stays visible
+    /// until the restarted tasks replace its parts
+    pub fn restart(&self, config: CollectorConfig) {
+        for task in self.tasks.lock().unwrap().drain(..) {
+            task.abort();
+        }
+        self.attempted.store(0, Ordering::SeqCst);
+        self.start(config);
+    }
+
+    fn start(&self, config: CollectorConfig) {
+        let backend = self.backend.clone();
+        let b = backend.clone();
+        self.spawn_task("cpu", config.cpu_interval, false, move || {
+            let b = b.clone();
+            async move { b.cpu_snapshot().await }
+        }, |snapshot, cpu| snapshot.cpu = Some(cpu));
+
+        let b = backend.clone();
+        self.spawn_task("memory", config.memory_interval, false, move || {
+            let b = b.clone();
+            async move { b.memory_snapshot().await }
+        }, |snapshot, memory| snapshot.memory = Some(memory));
+
+        let b = backend.clone();
+        self.spawn_task("disk", config.disk_interval, false, move || {
+            let b = b.clone();
+            async move { Ok((b.disk_snapshot().await?, b.disk_list().await?)) }
+        }, |snapshot, (disk, disk_list)| {
+            snapshot.disk = Some(disk);
+            snapshot.disk_list = disk_list;
+        });
+
+        // Disk I/O and network rates need a baseline sample before the first published one
+        let b = backend.clone();
+        self.spawn_task("disk_io", config.disk_io_interval, true, move || {
+            let b = b.clone();
+            async move { b.disk_io().await }
+        }, |snapshot, disk_io| snapshot.disk_io = disk_io);
+
+        let b = backend.clone();
+        self.spawn_task("network", config.network_interval, true, move || {
+            let b = b.clone();
+            async move { b.network_snapshot().await }
+        }, |snapshot, network| snapshot.network = Some(network));
+
+        let b = backend.clone();
+        self.spawn_task("battery", config.battery_interval, false, move || {
+            let b = b.clone();
+            async move { b.batteries().await }
+        }, |snapshot, batteries| {
+            snapshot.battery = Some(super::battery::combine(&batteries));
+            snapshot.batteries = batteries;
+        });
+
+        let b = backend.clone();
+        self.spawn_task("sensors", config.sensors_interval, false, move || {
+            let b = b.clone();
+            async move { b.sensors_snapshot().await }
+        }, |snapshot, sensors| snapshot.sensors = Some(sensors));
+
+        let b = backend.clone();
+        self.spawn_task("pressure", config.pressure_interval, false, move || {
+            let b = b.clone();
+            async move { b.pressure().await }
+        }, |snapshot, pressure| snapshot.pressure = Some(pressure));
+
+        let b = backend.clone();
+        self.spawn_task("gpu", config.gpu_interval, false, move || {
+            let b = b.clone();
+            async move { b.gpu_list().await }
+        }, |snapshot, gpus| snapshot.gpus = gpus);
+
+        let b = backend.clone();
+        self.spawn_task("sockets", config.sockets_interval, false, move || {
+            let b = b.clone();
+            async move { b.sockets().await }
+        }, |snapshot, sockets| snapshot.sockets = sockets);
+
+        let b = backend;
+        let limit = config.process_limit;
+        self.spawn_task("process", config.process_interval, false, move || {
+            let b = b.clone();
+            async move { b.process_list(None, ProcessSort::Cpu, SortOrder::Desc, limit).await }
+        }, |snapshot, processes| snapshot.processes = processes);
+    }
+
+    fn spawn_task<T, F, Fut, A>(&self, name: &'static str, interval: Duration, warm_up: bool, fetch: F, apply: A)
+    where
+        T: Send + 'static,
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T>> + Send,
+        A: Fn(&mut SystemSnapshot, T) + Send + 'static,
+    {
+        let sender = self.sender.clone();
+        let attempted = self.attempted.clone();
+
+        self.tasks.lock().unwrap().push(tokio::spawn(async move {
+            if warm_up {
+                let _ = fetch().await;
+                tokio::time::sleep(interval.min(Duration::from_secs(1))).await;
+            }
+
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let mut first = true;
+
+            loop {
+                ticker.tick().await;
+
+                let result = fetch().await;
+                if let Err(e) = &result {
+                    tracing::debug!("{} collector failed: {}", name, e);
+                }
+
+                // A failed first attempt still counts, so one broken source
+                // cannot keep `wait_ready` hanging forever
+                if first {
+                    attempted.fetch_add(1, Ordering::SeqCst);
+                    first = false;
+                }
+
+                sender.send_modify(|snapshot| {
+                    if let Ok(value) = result {
+                        apply(snapshot, value);
+                    }
+                });
+            }
+        }));
+    }
+
+    /// Get a receiver that is notified whenever any metric is updated
+    pub fn subscribe(&self) -> watch::Receiver<SystemSnapshot> {
+        self.receiver.clone()
+    }
+
+    /// Clone of the most recently published snapshot
+    pub fn latest(&self) -> SystemSnapshot {
+        self.receiver.borrow().clone()
+    }
+
+    /// Wait until every collector task has run at least once
+    pub async fn wait_ready(&mut self) -> SystemSnapshot {
+        while self.attempted.load(Ordering::SeqCst) < self.tasks.lock().unwrap().len() {
+            if self.receiver.changed().await.is_err() {
+                break;
+            }
+        }
+        self.latest()
+    }
+}
+
+impl Drop for Collector {
+    fn drop(&mut self) {
+        for task in self.tasks.get_mut().unwrap().iter() {
+            task.abort();
+        }
+    }
+}
+
+/// Collect one complete snapshot and stop the collector tasks again
+pub async fn collect_once(backend: Arc<dyn MonitorBackend>, config: CollectorConfig) -> SystemSnapshot {
+    let mut collector = Collector::spawn(backend, config);
+    collector.wait_ready().await
+}
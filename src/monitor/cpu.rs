@@ -4,9 +4,13 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use sysinfo::{CpuRefreshKind, System};
 use tokio::sync::Mutex;
 
+/// A previous CPU refresh older than this is not used as the usage baseline
+const BASELINE_MAX_AGE: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CpuSnapshot {
     pub model: String,
@@ -21,16 +25,23 @@ pub struct CpuSnapshot {
     pub hostname: String,
 }
 
-pub async fn get_cpu_snapshot(system: &Arc<Mutex<System>>) -> Result<CpuSnapshot> {
-    let mut sys = system.lock().await;
+pub async fn get_cpu_snapshot(
+    system: &Arc<Mutex<System>>,
+    last_refresh: &Mutex<Option<Instant>>,
+) -> Result<CpuSnapshot> {
+    let mut last_refresh = last_refresh.lock().await;
     
-    // Refresh CPU info
-    sys.refresh_cpu_specifics(CpuRefreshKind::everything());
-    sys.refresh_memory();
+    // CPU usage is computed between two refreshes. Periodic callers reuse the
+    // previous refresh; one-shot callers take a baseline first and wait
+    // without holding the system lock.
+    if last_refresh.map_or(true, |at| at.elapsed() > BASELINE_MAX_AGE) {
+        system.lock().await.refresh_cpu_specifics(CpuRefreshKind::everything());
+        tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+    }
     
-    // Wait a bit for CPU usage calculation
-    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+    let mut sys = system.lock().await;
     sys.refresh_cpu_specifics(CpuRefreshKind::everything());
+    *last_refresh = Some(Instant::now());
     
     let cpus = sys.cpus();
     let global_usage = sys.global_cpu_info().cpu_usage();
@@ -53,7 +64,7 @@ pub async fn get_cpu_snapshot(system: &Arc<Mutex<System>>) -> Result<CpuSnapshot
     let os_name = format!(
         "{} {}",
         System::name().unwrap_or_else(|| "Unknown".to_string()),
-        System::os_version().unwrap_or_else(|| "".to_string())
+        System::os_version().unwrap_or_default()
     );
     
     let hostname = System::host_name().unwrap_or_else(|| "Unknown".to_string());
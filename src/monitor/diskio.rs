@@ -0,0 +1,212 @@
+// src/monitor/diskio.rs
+//! Block device I/O statistics from `/proc/diskstats`.
+//!
+//! The kernel exposes cumulative counters per device; [`DiskIoTracker`]
+//! compares two samples to derive throughput, IOPS, latency, queue depth and
+//! utilization the same way `iostat -x` does.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
+use super::DiskInfo;
+
+/// `/proc/diskstats` always counts in 512-byte sectors, whatever the device
+const SECTOR_SIZE: u64 = 512;
+
+/// Cumulative counters of one block device
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiskCounters {
+    pub reads: u64,
+    pub sectors_read: u64,
+    pub read_ms: u64,
+    pub writes: u64,
+    pub sectors_written: u64,
+    pub write_ms: u64,
+    pub in_flight: u64,
+    /// Time the device had I/O in flight
+    pub io_ms: u64,
+    /// I/O time weighted by the number of requests in flight
+    pub weighted_io_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskIoStats {
+    pub device: String,
+    pub read_bytes_per_sec: u64,
+    pub write_bytes_per_sec: u64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    /// Average time an I/O took to complete, queueing included, in ms
+    pub await_ms: f64,
+    /// Average number of requests waiting or in service
+    pub queue_depth: f64,
+    /// Share of time the device was busy, 0-100%
+    pub utilization: f64,
+    pub total_read: u64,
+    pub total_written: u64,
+    /// Mount points of filesystems on this device
+    pub mount_points: Vec<String>,
+}
+
+/// Parse `/proc/diskstats`, skipping devices that have never done any I/O
+pub fn parse_diskstats(content: &str) -> Vec<(String, DiskCounters)> {
+    content.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 14 {
+                return None;
+            }
+            let value = |i: usize| fields[i].parse::<u64>().unwrap_or(0);
+            let counters = DiskCounters {
+                reads: value(3),
+                sectors_read: value(5),
+                read_ms: value(6),
+                writes: value(7),
+                sectors_written: value(9),
+                write_ms: value(10),
+                in_flight: value(11),
+                io_ms: value(12),
+                weighted_io_ms: value(13),
+            };
+            (counters.reads + counters.writes > 0).then(|| (fields[2].to_string(), counters))
+        })
+        .collect()
+}
+
+/// Turns successive diskstats samples into per-second statistics.
+///
+/// As with network rates, the first sample of a device reports zero rates
+/// and counter resets are treated as no activity.
+#[derive(Debug, Default)]
+pub struct DiskIoTracker {
+    previous: HashMap<String, DiskCounters>,
+    last_sample: Option<Instant>,
+}
+
+impl DiskIoTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, counters: Vec<(String, DiskCounters)>, now: Instant) -> Vec<DiskIoStats> {
+        let elapsed_ms = self
+            .last_sample
+            .map(|last| now.saturating_duration_since(last).as_secs_f64() * 1000.0)
+            .unwrap_or(0.0);
+        let per_second = |delta: u64| if elapsed_ms > 0.0 { delta as f64 * 1000.0 / elapsed_ms } else { 0.0 };
+        let per_ms = |delta: u64| if elapsed_ms > 0.0 { delta as f64 / elapsed_ms } else { 0.0 };
+
+        let mut stats: Vec<DiskIoStats> = counters.iter()
+            .map(|(device, current)| {
+                let previous = self.previous.get(device).copied().unwrap_or(*current);
+                let delta = |f: fn(&DiskCounters) -> u64| f(current).saturating_sub(f(&previous));
+
+                let ios = delta(|c| c.reads) + delta(|c| c.writes);
+                let await_ms = if ios > 0 {
+                    (delta(|c| c.read_ms) + delta(|c| c.write_ms)) as f64 / ios as f64
+                } else {
+                    0.0
+                };
+
+                DiskIoStats {
+                    device: device.clone(),
+                    read_bytes_per_sec: per_second(delta(|c| c.sectors_read) * SECTOR_SIZE) as u64,
+                    write_bytes_per_sec: per_second(delta(|c| c.sectors_written) * SECTOR_SIZE) as u64,
+                    read_iops: per_second(delta(|c| c.reads)),
+                    write_iops: per_second(delta(|c| c.writes)),
+                    await_ms,
+                    queue_depth: per_ms(delta(|c| c.weighted_io_ms)),
+                    utilization: (per_ms(delta(|c| c.io_ms)) * 100.0).min(100.0),
+                    total_read: current.sectors_read * SECTOR_SIZE,
+                    total_written: current.sectors_written * SECTOR_SIZE,
+                    mount_points: Vec::new(),
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| a.device.cmp(&b.device));
+
+        self.previous = counters.into_iter().collect();
+        self.last_sample = Some(now);
+
+        stats
+    }
+}
+
+/// Device-mapper names (`vg-root`) to their kernel names (`dm-0`)
+pub fn read_dm_names(sysfs_root: &Path) -> HashMap<String, String> {
+    std::fs::read_dir(sysfs_root.join("class/block"))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let device = e.file_name().to_string_lossy().to_string();
+            let name = std::fs::read_to_string(e.path().join("dm/name")).ok()?;
+            Some((name.trim().to_string(), device))
+        })
+        .collect()
+}
+
+/// Attach the mount points in `disks` to the device each one lives on
+pub fn map_mounts(stats: &mut [DiskIoStats], disks: &[DiskInfo], dm_names: &HashMap<String, String>) {
+    for disk in disks {
+        let device = match disk.name.strip_prefix("/dev/mapper/") {
+            Some(mapped) => dm_names.get(mapped).cloned(),
+            None => disk.name.strip_prefix("/dev/").map(str::to_string),
+        };
+        let Some(device) = device else {
+            continue;
+        };
+        if let Some(entry) = stats.iter_mut().find(|s| s.device == device) {
+            entry.mount_points.push(disk.mount_point.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const DISKSTATS: &str = "\
+   7       0 loop0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0
+   8       0 sda 1000 0 8000 2000 500 0 4000 3000 2 1500 5000 0 0 0 0
+   8       1 sda1 900 0 7200 1800 500 0 4000 3000 0 1400 4800 0 0 0 0
+";
+
+    #[test]
+    fn test_parse_diskstats_skips_idle_devices() {
+        let devices = parse_diskstats(DISKSTATS);
+        let names: Vec<_> = devices.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["sda", "sda1"]);
+        assert_eq!(devices[0].1.sectors_read, 8000);
+        assert_eq!(devices[0].1.weighted_io_ms, 5000);
+    }
+
+    #[test]
+    fn test_tracker_rates() {
+        let mut tracker = DiskIoTracker::new();
+        let start = Instant::now();
+        let first = tracker.update(parse_diskstats(DISKSTATS), start);
+        assert_eq!(first[0].read_bytes_per_sec, 0);
+
+        // One second later: 100 reads of 8 sectors taking 300ms, device busy 500ms
+        let mut counters = parse_diskstats(DISKSTATS);
+        let sda = &mut counters[0].1;
+        sda.reads += 100;
+        sda.sectors_read += 800;
+        sda.read_ms += 300;
+        sda.io_ms += 500;
+        sda.weighted_io_ms += 1500;
+        let stats = tracker.update(counters, start + Duration::from_secs(1));
+
+        let sda = &stats[0];
+        assert_eq!(sda.read_bytes_per_sec, 800 * 512);
+        assert_eq!(sda.read_iops, 100.0);
+        assert_eq!(sda.write_iops, 0.0);
+        assert_eq!(sda.await_ms, 3.0);
+        assert_eq!(sda.queue_depth, 1.5);
+        assert_eq!(sda.utilization, 50.0);
+    }
+}
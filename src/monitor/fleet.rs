@@ -0,0 +1,260 @@
+// src/monitor/fleet.rs
+//! Watching several remote agents at once (optional feature).
+//!
+//! A [`Fleet`] runs one lightweight [`Collector`] per configured agent and
+//! condenses each host's latest snapshot into a [`HostRow`] for the fleet
+//! dashboard. Opening a host hands out its [`RemoteBackend`], so the normal
+//! panels can be driven from it.
+
+use anyhow::Result;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::remote::{ConnectionStatus, RemoteBackend};
+use super::sensors::SensorStatus;
+use super::{Collector, CollectorConfig, SortOrder, SystemSnapshot};
+use crate::config::HostConfig;
+
+/// Usage at or above which a host is flagged, in percent
+const WARNING_PERCENT: f64 = 85.0;
+const CRITICAL_PERCENT: f64 = 95.0;
+
+/// Alert state of a host, ordered from healthy to unreachable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HostHealth {
+    Ok,
+    /// CPU, memory or disk at 85% or more, or a sensor close to critical
+    Warning,
+    /// CPU, memory or disk at 95% or more, or a sensor at its critical temperature
+    Critical,
+    /// The agent is not answering
+    Down,
+}
+
+impl HostHealth {
+    pub fn of(snapshot: &SystemSnapshot, status: &ConnectionStatus) -> Self {
+        if matches!(status, ConnectionStatus::Lost { .. }) {
+            return HostHealth::Down;
+        }
+
+        let peak = ["cpu.global", "memory.percent", "disk.percent"].iter()
+            .filter_map(|name| snapshot.metric(name))
+            .fold(0.0, f64::max);
+        let sensors = snapshot.sensors.iter()
+            .flat_map(|s| s.temperatures.iter().map(|t| t.status()))
+            .max_by_key(|status| *status as u8)
+            .unwrap_or(SensorStatus::Normal);
+
+        if peak >= CRITICAL_PERCENT || sensors == SensorStatus::Critical {
+            HostHealth::Critical
+        } else if peak >= WARNING_PERCENT || sensors == SensorStatus::Hot {
+            HostHealth::Warning
+        } else {
+            HostHealth::Ok
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            HostHealth::Ok => "OK",
+            HostHealth::Warning => "WARN",
+            HostHealth::Critical => "CRIT",
+            HostHealth::Down => "DOWN",
+        }
+    }
+}
+
+/// Summary of one host for the fleet table
+#[derive(Debug, Clone)]
+pub struct HostRow {
+    /// Position of the host in the configuration
+    pub index: usize,
+    pub name: String,
+    pub status: ConnectionStatus,
+    pub cpu: Option<f64>,
+    pub memory: Option<f64>,
+    pub disk: Option<f64>,
+    /// One minute load average
+    pub load: Option<f64>,
+    pub health: HostHealth,
+}
+
+/// Column the fleet table is ordered by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FleetSort {
+    Name,
+    Cpu,
+    Memory,
+    Disk,
+    Load,
+    /// Worst alert state first
+    #[default]
+    Health,
+}
+
+impl FleetSort {
+    /// The next column, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            FleetSort::Name => FleetSort::Cpu,
+            FleetSort::Cpu => FleetSort::Memory,
+            FleetSort::Memory => FleetSort::Disk,
+            FleetSort::Disk => FleetSort::Load,
+            FleetSort::Load => FleetSort::Health,
+            FleetSort::Health => FleetSort::Name,
+        }
+    }
+
+    /// Natural direction: names ascending, everything else worst first
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            FleetSort::Name => SortOrder::Asc,
+            _ => SortOrder::Desc,
+        }
+    }
+}
+
+/// Sort rows in place; hosts without a value for the column go last
+pub fn sort_rows(rows: &mut [HostRow], sort: FleetSort, order: SortOrder) {
+    rows.sort_by(|a, b| {
+        let value = |row: &HostRow| match sort {
+            FleetSort::Cpu => row.cpu,
+            FleetSort::Memory => row.memory,
+            FleetSort::Disk => row.disk,
+            FleetSort::Load => row.load,
+            FleetSort::Name | FleetSort::Health => None,
+        };
+        let ordering = match sort {
+            FleetSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            FleetSort::Health => a.health.cmp(&b.health),
+            _ => match (value(a), value(b)) {
+                (Some(x), Some(y)) => x.total_cmp(&y),
+                (x, y) => {
+                    // Missing values stay at the end whatever the direction
+                    let missing = y.is_some().cmp(&x.is_some());
+                    return missing.then_with(|| a.index.cmp(&b.index));
+                }
+            },
+        };
+        let ordering = match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        };
+        ordering.then_with(|| a.index.cmp(&b.index))
+    });
+}
+
+/// One configured agent and the collector polling it
+pub struct FleetHost {
+    pub name: String,
+    pub remote: Arc<RemoteBackend>,
+    collector: Collector,
+}
+
+/// Every agent from the `[[hosts]]` section, polled in the background
+pub struct Fleet {
+    hosts: Vec<FleetHost>,
+}
+
+impl Fleet {
+    pub fn spawn(hosts: &[HostConfig], refresh_interval_ms: u64) -> Result<Self> {
+        let interval = Duration::from_millis(refresh_interval_ms.max(100));
+        let hosts = hosts.iter()
+            .map(|host| {
+                let remote = Arc::new(RemoteBackend::new(&host.url, host.token.clone())?.cache_for(interval / 2));
+                // Only the summary is shown, so processes are not kept
+                let collector = Collector::spawn(remote.clone(), CollectorConfig::uniform(refresh_interval_ms).process_limit(0));
+                Ok(FleetHost {
+                    name: host.display_name().to_string(),
+                    remote,
+                    collector,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { hosts })
+    }
+
+    pub fn hosts(&self) -> &[FleetHost] {
+        &self.hosts
+    }
+
+    /// Current summary of every host, in configuration order
+    pub fn rows(&self) -> Vec<HostRow> {
+        self.hosts.iter()
+            .enumerate()
+            .map(|(index, host)| {
+                let snapshot = host.collector.latest();
+                let status = host.remote.status();
+                HostRow {
+                    index,
+                    name: host.name.clone(),
+                    cpu: snapshot.metric("cpu.global"),
+                    memory: snapshot.metric("memory.percent"),
+                    disk: snapshot.metric("disk.percent"),
+                    load: snapshot.cpu.as_ref().map(|c| c.load_average.0),
+                    health: HostHealth::of(&snapshot, &status),
+                    status,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::DiskSnapshot;
+
+    fn row(index: usize, name: &str, cpu: Option<f64>, health: HostHealth) -> HostRow {
+        HostRow {
+            index,
+            name: name.to_string(),
+            status: ConnectionStatus::Connected,
+            cpu,
+            memory: None,
+            disk: None,
+            load: None,
+            health,
+        }
+    }
+
+    #[test]
+    fn test_health_from_snapshot() {
+        let disk = |usage_percent| DiskSnapshot { total: 100, used: 0, available: 0, usage_percent };
+        let mut snapshot = SystemSnapshot {
+            disk: Some(disk(50.0)),
+            ..Default::default()
+        };
+        assert_eq!(HostHealth::of(&snapshot, &ConnectionStatus::Connected), HostHealth::Ok);
+
+        snapshot.disk = Some(disk(90.0));
+        assert_eq!(HostHealth::of(&snapshot, &ConnectionStatus::Connected), HostHealth::Warning);
+
+        snapshot.disk = Some(disk(99.0));
+        assert_eq!(HostHealth::of(&snapshot, &ConnectionStatus::Connected), HostHealth::Critical);
+
+        let lost = ConnectionStatus::Lost { error: "timeout".to_string(), failures: 1, retry_in: Duration::ZERO };
+        assert_eq!(HostHealth::of(&snapshot, &lost), HostHealth::Down);
+    }
+
+    #[test]
+    fn test_sort_rows_keeps_missing_values_last() {
+        let mut rows = vec![
+            row(0, "web-2", Some(10.0), HostHealth::Ok),
+            row(1, "db-1", None, HostHealth::Down),
+            row(2, "Web-1", Some(80.0), HostHealth::Warning),
+        ];
+
+        sort_rows(&mut rows, FleetSort::Cpu, SortOrder::Desc);
+        assert_eq!(rows.iter().map(|r| r.index).collect::<Vec<_>>(), vec![2, 0, 1]);
+
+        sort_rows(&mut rows, FleetSort::Cpu, SortOrder::Asc);
+        assert_eq!(rows.iter().map(|r| r.index).collect::<Vec<_>>(), vec![0, 2, 1]);
+
+        sort_rows(&mut rows, FleetSort::Name, SortOrder::Asc);
+        assert_eq!(rows.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), vec!["db-1", "Web-1", "web-2"]);
+
+        sort_rows(&mut rows, FleetSort::Health, SortOrder::Desc);
+        assert_eq!(rows.iter().map(|r| r.health).collect::<Vec<_>>(), vec![HostHealth::Down, HostHealth::Warning, HostHealth::Ok]);
+    }
+}
@@ -0,0 +1,343 @@
+// src/monitor/gpu.rs
+//! GPU monitoring.
+//!
+//! GPUs are read through the [`GpuBackend`] trait. The DRM backend reads the
+//! amdgpu and i915 attributes below a configurable sysfs root and works
+//! without vendor libraries; the NVML backend talks to the NVIDIA driver and
+//! is only built with the `gpu` feature.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GpuVendor {
+    Amd,
+    Intel,
+    Nvidia,
+    Unknown,
+}
+
+impl GpuVendor {
+    /// Vendor from a PCI vendor id such as `0x1002`
+    pub fn from_pci_id(id: &str) -> Self {
+        match id.trim().trim_start_matches("0x") {
+            "1002" => GpuVendor::Amd,
+            "8086" => GpuVendor::Intel,
+            "10de" => GpuVendor::Nvidia,
+            _ => GpuVendor::Unknown,
+        }
+    }
+}
+
+impl std::fmt::Display for GpuVendor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            GpuVendor::Amd => "AMD",
+            GpuVendor::Intel => "Intel",
+            GpuVendor::Nvidia => "NVIDIA",
+            GpuVendor::Unknown => "Unknown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// One GPU as reported by a [`GpuBackend`]; values a source cannot read are `None`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GpuInfo {
+    pub index: usize,
+    pub name: String,
+    pub vendor: GpuVendor,
+    pub driver: String,
+    /// Backend that produced the reading (`drm` or `nvml`)
+    pub source: String,
+    pub bus_id: Option<String>,
+    /// Busy percentage of the graphics engine
+    pub utilization: Option<f32>,
+    pub memory_used: Option<u64>,
+    pub memory_total: Option<u64>,
+    pub core_clock_mhz: Option<u32>,
+    pub memory_clock_mhz: Option<u32>,
+    /// Temperature in °C
+    pub temperature: Option<f32>,
+    pub power_watts: Option<f32>,
+}
+
+impl GpuInfo {
+    pub fn memory_percent(&self) -> Option<f32> {
+        match (self.memory_used, self.memory_total) {
+            (Some(used), Some(total)) if total > 0 => Some(used as f32 / total as f32 * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Source of GPU readings
+#[async_trait]
+pub trait GpuBackend: Send + Sync {
+    async fn gpus(&self) -> Result<Vec<GpuInfo>>;
+}
+
+/// Reads GPUs from `<sysfs_root>/class/drm/card*`
+pub struct DrmGpuBackend {
+    sysfs_root: PathBuf,
+    skip_nvidia: bool,
+    /// Last RC6 residency per card, used to derive i915 busy percent
+    rc6: Mutex<HashMap<String, (Instant, u64)>>,
+}
+
+impl DrmGpuBackend {
+    pub fn new(sysfs_root: impl Into<PathBuf>) -> Self {
+        Self {
+            sysfs_root: sysfs_root.into(),
+            skip_nvidia: false,
+            rc6: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Leave NVIDIA cards to the NVML backend
+    pub fn skip_nvidia(mut self, skip: bool) -> Self {
+        self.skip_nvidia = skip;
+        self
+    }
+
+    fn read_card(&self, card: &Path) -> Option<(GpuInfo, Option<u64>)> {
+        let device = card.join("device");
+        let read = |path: PathBuf| std::fs::read_to_string(path).ok().map(|value| value.trim().to_string());
+        let read_number = |path: PathBuf| read(path).and_then(|value| value.parse::<u64>().ok());
+
+        let vendor = GpuVendor::from_pci_id(&read(device.join("vendor"))?);
+        if self.skip_nvidia && vendor == GpuVendor::Nvidia {
+            return None;
+        }
+
+        let uevent = read(device.join("uevent")).unwrap_or_default();
+        let uevent_value = |key: &str| uevent.lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .map(str::to_string);
+        let driver = uevent_value("DRIVER").unwrap_or_else(|| "unknown".to_string());
+        let name = read(device.join("product_name"))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("{} GPU ({})", vendor, driver));
+
+        // amdgpu reports clocks as DPM tables, i915 as plain frequencies on the card
+        let core_clock_mhz = read(device.join("pp_dpm_sclk"))
+            .and_then(|table| parse_dpm_clock(&table))
+            .or_else(|| read_number(card.join("gt_act_freq_mhz")).map(|mhz| mhz as u32))
+            .or_else(|| read_number(card.join("gt_cur_freq_mhz")).map(|mhz| mhz as u32));
+        let memory_clock_mhz = read(device.join("pp_dpm_mclk")).and_then(|table| parse_dpm_clock(&table));
+
+        let hwmon = std::fs::read_dir(device.join("hwmon"))
+            .ok()
+            .and_then(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).min());
+        let temperature = hwmon.as_ref()
+            .and_then(|dir| read_number(dir.join("temp1_input")))
+            .map(|millis| millis as f32 / 1000.0);
+        let power_watts = hwmon.as_ref()
+            .and_then(|dir| read_number(dir.join("power1_average")).or_else(|| read_number(dir.join("power1_input"))))
+            .map(|micro| micro as f32 / 1_000_000.0);
+
+        let rc6 = read_number(card.join("gt/gt0/rc6_residency_ms"))
+            .or_else(|| read_number(card.join("power/rc6_residency_ms")));
+
+        let info = GpuInfo {
+            index: 0,
+            name,
+            vendor,
+            driver,
+            source: "drm".to_string(),
+            bus_id: uevent_value("PCI_SLOT_NAME"),
+            utilization: read_number(device.join("gpu_busy_percent")).map(|busy| busy as f32),
+            memory_used: read_number(device.join("mem_info_vram_used")),
+            memory_total: read_number(device.join("mem_info_vram_total")),
+            core_clock_mhz,
+            memory_clock_mhz,
+            temperature,
+            power_watts,
+        };
+        Some((info, rc6))
+    }
+}
+
+#[async_trait]
+impl GpuBackend for DrmGpuBackend {
+    async fn gpus(&self) -> Result<Vec<GpuInfo>> {
+        let Ok(entries) = std::fs::read_dir(self.sysfs_root.join("class/drm")) else {
+            return Ok(Vec::new());
+        };
+        // Connectors such as card0-DP-1 live next to the cards
+        let mut cards: Vec<_> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                name.strip_prefix("card").is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+            })
+            .map(|e| e.path())
+            .collect();
+        cards.sort();
+
+        let now = Instant::now();
+        let mut rc6_samples = self.rc6.lock().await;
+        let mut gpus = Vec::new();
+        for card in cards {
+            let Some((mut gpu, rc6)) = self.read_card(&card) else {
+                continue;
+            };
+            if let Some(rc6) = rc6 {
+                let key = card.to_string_lossy().to_string();
+                if gpu.utilization.is_none() {
+                    gpu.utilization = rc6_samples.get(&key)
+                        .and_then(|&(at, previous)| busy_from_rc6(previous, rc6, now.duration_since(at).as_millis() as u64));
+                }
+                rc6_samples.insert(key, (now, rc6));
+            }
+            gpu.index = gpus.len();
+            gpus.push(gpu);
+        }
+
+        Ok(gpus)
+    }
+}
+
+/// Active clock of an amdgpu DPM table, the level marked with `*`
+pub fn parse_dpm_clock(table: &str) -> Option<u32> {
+    table.lines()
+        .find(|line| line.trim_end().ends_with('*'))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|clock| clock.to_lowercase().strip_suffix("mhz").and_then(|mhz| mhz.parse().ok()))
+}
+
+/// Busy percent from the time the GPU spent in its RC6 sleep state between
+/// two samples `elapsed_ms` apart
+pub fn busy_from_rc6(previous_ms: u64, current_ms: u64, elapsed_ms: u64) -> Option<f32> {
+    if elapsed_ms == 0 || current_ms < previous_ms {
+        return None;
+    }
+    let idle = (current_ms - previous_ms) as f32 / elapsed_ms as f32;
+    Some(((1.0 - idle) * 100.0).clamp(0.0, 100.0))
+}
+
+/// NVIDIA GPUs through the NVML library shipped with the driver
+#[cfg(feature = "gpu")]
+pub struct NvmlGpuBackend {
+    nvml: nvml_wrapper::Nvml,
+}
+
+#[cfg(feature = "gpu")]
+impl NvmlGpuBackend {
+    /// Fails when the NVIDIA driver or its NVML library is missing
+    pub fn new() -> Result<Self> {
+        Ok(Self { nvml: nvml_wrapper::Nvml::init()? })
+    }
+}
+
+#[cfg(feature = "gpu")]
+#[async_trait]
+impl GpuBackend for NvmlGpuBackend {
+    async fn gpus(&self) -> Result<Vec<GpuInfo>> {
+        use nvml_wrapper::enum_wrappers::device::{Clock, TemperatureSensor};
+
+        let mut gpus = Vec::new();
+        for index in 0..self.nvml.device_count()? {
+            let device = self.nvml.device_by_index(index)?;
+            let memory = device.memory_info().ok();
+            gpus.push(GpuInfo {
+                index: index as usize,
+                name: device.name()?,
+                vendor: GpuVendor::Nvidia,
+                driver: "nvidia".to_string(),
+                source: "nvml".to_string(),
+                bus_id: device.pci_info().ok().map(|pci| pci.bus_id),
+                utilization: device.utilization_rates().ok().map(|u| u.gpu as f32),
+                memory_used: memory.as_ref().map(|m| m.used),
+                memory_total: memory.as_ref().map(|m| m.total),
+                core_clock_mhz: device.clock_info(Clock::Graphics).ok(),
+                memory_clock_mhz: device.clock_info(Clock::Memory).ok(),
+                temperature: device.temperature(TemperatureSensor::Gpu).ok().map(|t| t as f32),
+                power_watts: device.power_usage().ok().map(|milli| milli as f32 / 1000.0),
+            });
+        }
+
+        Ok(gpus)
+    }
+}
+
+/// Several backends reported as one list
+pub struct CombinedGpuBackend {
+    backends: Vec<Box<dyn GpuBackend>>,
+}
+
+#[async_trait]
+impl GpuBackend for CombinedGpuBackend {
+    async fn gpus(&self) -> Result<Vec<GpuInfo>> {
+        let mut gpus = Vec::new();
+        let mut error = None;
+        for backend in &self.backends {
+            match backend.gpus().await {
+                Ok(list) => gpus.extend(list),
+                Err(e) => error = Some(e),
+            }
+        }
+
+        match error {
+            Some(e) if gpus.is_empty() => Err(e),
+            _ => {
+                for (index, gpu) in gpus.iter_mut().enumerate() {
+                    gpu.index = index;
+                }
+                Ok(gpus)
+            }
+        }
+    }
+}
+
+/// Every GPU source available on this machine.
+///
+/// With the `gpu` feature NVML handles NVIDIA cards when the driver is
+/// present; everything else is read from DRM sysfs below `sysfs_root`.
+pub fn detect(sysfs_root: &Path) -> Box<dyn GpuBackend> {
+    let mut backends: Vec<Box<dyn GpuBackend>> = Vec::new();
+
+    #[cfg(feature = "gpu")]
+    let nvml_found = match NvmlGpuBackend::new() {
+        Ok(nvml) => {
+            backends.push(Box::new(nvml));
+            true
+        }
+        Err(e) => {
+            tracing::debug!("NVML unavailable: {}", e);
+            false
+        }
+    };
+    #[cfg(not(feature = "gpu"))]
+    let nvml_found = false;
+
+    backends.push(Box::new(DrmGpuBackend::new(sysfs_root).skip_nvidia(nvml_found)));
+    Box::new(CombinedGpuBackend { backends })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dpm_clock() {
+        let table = "0: 500Mhz\n1: 1350Mhz *\n2: 1800Mhz\n";
+        assert_eq!(parse_dpm_clock(table), Some(1350));
+        assert_eq!(parse_dpm_clock("0: 500Mhz\n"), None);
+    }
+
+    #[test]
+    fn test_busy_from_rc6() {
+        // 250ms asleep out of 1000ms means 75% busy
+        assert_eq!(busy_from_rc6(1000, 1250, 1000), Some(75.0));
+        assert_eq!(busy_from_rc6(1000, 1000, 1000), Some(100.0));
+        assert_eq!(busy_from_rc6(1000, 900, 1000), None);
+        assert_eq!(busy_from_rc6(1000, 1100, 0), None);
+    }
+}
@@ -0,0 +1,288 @@
+// src/monitor/history.rs
+//! Bounded, time-indexed history of the headline metrics.
+//!
+//! [`MetricsHistory`] keeps the most recent [`MetricsSample`]s in a ring
+//! buffer. Recent samples are read as they are; longer windows are
+//! downsampled into evenly sized time buckets whose values are averaged, so
+//! a graph or a trend report gets a fixed number of points whatever the span.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use super::SystemSnapshot;
+
+/// Samples kept by [`MetricsHistory::default`]: one hour at one sample per second
+pub const DEFAULT_CAPACITY: usize = 3600;
+
+/// Headline metrics at one point in time
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MetricsSample {
+    pub timestamp: DateTime<Utc>,
+    /// Global CPU usage in percent
+    pub cpu: Option<f32>,
+    /// Usage of every logical core in percent
+    pub cpu_cores: Vec<f32>,
+    pub memory_percent: Option<f32>,
+    pub swap_percent: Option<f32>,
+    pub disk_percent: Option<f32>,
+    /// Bytes per second over all interfaces
+    pub network_rx_rate: Option<u64>,
+    pub network_tx_rate: Option<u64>,
+    /// Charge of all batteries combined, in percent
+    pub battery_percent: Option<f32>,
+}
+
+impl MetricsSample {
+    pub fn from_snapshot(snapshot: &SystemSnapshot, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            timestamp,
+            cpu: snapshot.cpu.as_ref().map(|c| c.global_usage),
+            cpu_cores: snapshot.cpu.as_ref().map(|c| c.per_core_usage.clone()).unwrap_or_default(),
+            memory_percent: snapshot.memory.as_ref().map(|m| m.usage_percent),
+            swap_percent: snapshot.memory.as_ref().map(|m| m.swap_usage_percent),
+            disk_percent: snapshot.disk.as_ref().map(|d| d.usage_percent),
+            network_rx_rate: snapshot.network.as_ref().map(|n| n.total_rx_rate),
+            network_tx_rate: snapshot.network.as_ref().map(|n| n.total_tx_rate),
+            battery_percent: snapshot.battery.as_ref().filter(|b| b.is_present).map(|b| b.percentage),
+        }
+    }
+
+    /// Look up a value by its dotted name.
+    ///
+    /// Supported names are `cpu.global`, `cpu.core.<n>`, `memory.percent`,
+    /// `swap.percent`, `disk.percent`, `network.rx_rate`, `network.tx_rate`
+    /// and `battery.percent`, as in [`SystemSnapshot::metric`].
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        match name {
+            "cpu.global" => self.cpu.map(f64::from),
+            "memory.percent" => self.memory_percent.map(f64::from),
+            "swap.percent" => self.swap_percent.map(f64::from),
+            "disk.percent" => self.disk_percent.map(f64::from),
+            "network.rx_rate" => self.network_rx_rate.map(|r| r as f64),
+            "network.tx_rate" => self.network_tx_rate.map(|r| r as f64),
+            "battery.percent" => self.battery_percent.map(f64::from),
+            _ => {
+                let core: usize = name.strip_prefix("cpu.core.")?.parse().ok()?;
+                self.cpu_cores.get(core).map(|&usage| f64::from(usage))
+            }
+        }
+    }
+
+    /// Average of several samples, stamped with the time of the last one
+    fn average(samples: &[MetricsSample]) -> Self {
+        fn mean<T: Copy>(values: impl Iterator<Item = Option<T>>, to_f64: impl Fn(T) -> f64) -> Option<f64> {
+            let (sum, count) = values.flatten().fold((0.0, 0usize), |(sum, count), v| (sum + to_f64(v), count + 1));
+            (count > 0).then(|| sum / count as f64)
+        }
+        let percent = |field: fn(&MetricsSample) -> Option<f32>| {
+            mean(samples.iter().map(field), f64::from).map(|v| v as f32)
+        };
+        let rate = |field: fn(&MetricsSample) -> Option<u64>| {
+            mean(samples.iter().map(field), |v| v as f64).map(|v| v.round() as u64)
+        };
+
+        let cores = samples.iter().map(|s| s.cpu_cores.len()).max().unwrap_or(0);
+        let cpu_cores = (0..cores)
+            .filter_map(|core| mean(samples.iter().map(|s| s.cpu_cores.get(core).copied()), f64::from))
+            .map(|v| v as f32)
+            .collect();
+
+        Self {
+            timestamp: samples.last().map(|s| s.timestamp).unwrap_or_default(),
+            cpu: percent(|s| s.cpu),
+            cpu_cores,
+            memory_percent: percent(|s| s.memory_percent),
+            swap_percent: percent(|s| s.swap_percent),
+            disk_percent: percent(|s| s.disk_percent),
+            network_rx_rate: rate(|s| s.network_rx_rate),
+            network_tx_rate: rate(|s| s.network_tx_rate),
+            battery_percent: percent(|s| s.battery_percent),
+        }
+    }
+}
+
+/// Ring buffer of the most recent samples, oldest first
+#[derive(Debug, Clone)]
+pub struct MetricsHistory {
+    samples: VecDeque<MetricsSample>,
+    capacity: usize,
+}
+
+impl Default for MetricsHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl MetricsHistory {
+    /// Keep at most `capacity` samples; the oldest are dropped first
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            samples: VecDeque::with_capacity(capacity.min(DEFAULT_CAPACITY)),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Add a sample; one older than the newest kept sample is ignored
+    pub fn push(&mut self, sample: MetricsSample) {
+        if self.samples.back().is_some_and(|last| sample.timestamp < last.timestamp) {
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Add the headline metrics of a snapshot taken now
+    pub fn record(&mut self, snapshot: &SystemSnapshot) {
+        self.push(MetricsSample::from_snapshot(snapshot, Utc::now()));
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    pub fn latest(&self) -> Option<&MetricsSample> {
+        self.samples.back()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &MetricsSample> + ExactSizeIterator {
+        self.samples.iter()
+    }
+
+    /// Samples taken at or after `from` and before `to`
+    pub fn range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> impl Iterator<Item = &MetricsSample> {
+        let start = self.samples.partition_point(|s| s.timestamp < from);
+        let end = self.samples.partition_point(|s| s.timestamp < to).max(start);
+        self.samples.range(start..end)
+    }
+
+    /// The last `window` of history (ending at the newest sample) in at most
+    /// `max_points` samples.
+    ///
+    /// When more samples than that fall into the window, it is split into
+    /// `max_points` equal time buckets and the samples in each are averaged.
+    /// Buckets without samples are left out rather than filled in.
+    pub fn window(&self, window: Duration, max_points: usize) -> Vec<MetricsSample> {
+        let Some(end) = self.latest().map(|s| s.timestamp) else {
+            return Vec::new();
+        };
+        let span = chrono::Duration::from_std(window).unwrap_or(chrono::Duration::MAX);
+        let start = end.checked_sub_signed(span).unwrap_or(DateTime::<Utc>::MIN_UTC);
+        let first = self.samples.partition_point(|s| s.timestamp <= start);
+        let samples: Vec<MetricsSample> = self.samples.range(first..).cloned().collect();
+
+        if samples.len() <= max_points || max_points == 0 {
+            return if max_points == 0 { Vec::new() } else { samples };
+        }
+
+        // Bucket i covers (start + i * width, start + (i + 1) * width]
+        let width = (end - start).num_milliseconds().max(1) as f64 / max_points as f64;
+        let bucket = |s: &MetricsSample| {
+            let offset = (s.timestamp - start).num_milliseconds() as f64;
+            ((offset / width).ceil() as usize).saturating_sub(1).min(max_points - 1)
+        };
+
+        // Samples are time ordered, so each bucket is one consecutive run
+        let mut buckets: Vec<(usize, Vec<MetricsSample>)> = Vec::new();
+        for sample in samples {
+            let index = bucket(&sample);
+            match buckets.last_mut() {
+                Some((last, run)) if *last == index => run.push(sample),
+                _ => buckets.push((index, vec![sample])),
+            }
+        }
+        buckets.iter().map(|(_, run)| MetricsSample::average(run)).collect()
+    }
+
+    /// Mean of one metric over the last `window`
+    pub fn average(&self, name: &str, window: Duration) -> Option<f64> {
+        self.window(window, 1).first()?.metric(name)
+    }
+
+    /// One metric (see [`MetricsSample::metric`]) over the last `window`,
+    /// downsampled like [`MetricsHistory::window`], for graphs
+    pub fn series(&self, name: &str, window: Duration, max_points: usize) -> Vec<(DateTime<Utc>, f64)> {
+        self.window(window, max_points)
+            .iter()
+            .filter_map(|s| Some((s.timestamp, s.metric(name)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(start: DateTime<Utc>, second: i64, cpu: f32) -> MetricsSample {
+        MetricsSample {
+            timestamp: start + chrono::Duration::seconds(second),
+            cpu: Some(cpu),
+            cpu_cores: vec![cpu, cpu / 2.0],
+            network_rx_rate: Some(second as u64 * 100),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ring_buffer_drops_oldest_and_stays_ordered() {
+        let start = Utc::now();
+        let mut history = MetricsHistory::new(3);
+        for second in 0..5 {
+            history.push(sample(start, second, second as f32));
+        }
+        history.push(sample(start, 1, 99.0));
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.iter().map(|s| s.cpu.unwrap()).collect::<Vec<_>>(), vec![2.0, 3.0, 4.0]);
+        assert_eq!(history.latest().unwrap().metric("cpu.core.1"), Some(2.0));
+
+        let from = start + chrono::Duration::seconds(3);
+        assert_eq!(history.range(from, from + chrono::Duration::seconds(10)).count(), 2);
+    }
+
+    #[test]
+    fn test_window_downsamples_into_buckets() {
+        let start = Utc::now();
+        let mut history = MetricsHistory::default();
+        for second in 0..=60 {
+            history.push(sample(start, second, second as f32));
+        }
+
+        // Short windows come back sample by sample
+        let recent = history.window(Duration::from_secs(5), 10);
+        assert_eq!(recent.len(), 5);
+        assert_eq!(recent[0].cpu, Some(56.0));
+
+        // A minute in 6 points averages 10 seconds each
+        let points = history.window(Duration::from_secs(60), 6);
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0].cpu, Some(5.5));
+        assert_eq!(points[0].cpu_cores, vec![5.5, 2.75]);
+        assert_eq!(points[0].network_rx_rate, Some(550));
+        assert_eq!(points[5].cpu, Some(55.5));
+        assert_eq!(points[5].timestamp, history.latest().unwrap().timestamp);
+
+        assert_eq!(history.average("cpu.global", Duration::from_secs(10)), Some(55.5));
+
+        let series = history.series("cpu.global", Duration::from_secs(60), 3);
+        assert_eq!(series.iter().map(|(_, v)| *v).collect::<Vec<_>>(), vec![10.5, 30.5, 50.5]);
+        assert!(history.series("memory.percent", Duration::from_secs(60), 3).is_empty());
+    }
+}
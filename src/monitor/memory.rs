@@ -3,6 +3,7 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 use std::sync::Arc;
 use sysinfo::System;
 use tokio::sync::Mutex;
@@ -16,6 +17,134 @@ pub struct MemorySnapshot {
     pub swap_total: u64,
     pub swap_used: u64,
     pub swap_usage_percent: f32,
+    /// Kernel breakdown of memory use, where available (Linux)
+    pub detail: Option<MemoryDetail>,
+}
+
+/// Fields of `/proc/meminfo`, in bytes unless noted
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryDetail {
+    pub total: u64,
+    pub free: u64,
+    /// Estimate of memory available for new allocations without swapping
+    pub available: u64,
+    pub buffers: u64,
+    /// Page cache, including shared memory
+    pub cached: u64,
+    /// tmpfs and shared memory segments
+    pub shared: u64,
+    pub slab_reclaimable: u64,
+    pub slab_unreclaimable: u64,
+    /// Modified pages waiting to be written to disk
+    pub dirty: u64,
+    /// Pages being written to disk right now
+    pub writeback: u64,
+    /// Number of huge pages in the pool
+    pub hugepages_total: u64,
+    /// Number of huge pages not yet allocated
+    pub hugepages_free: u64,
+    pub hugepage_size: u64,
+    /// Memory the kernel has promised to processes
+    pub committed_as: u64,
+    /// Allocation limit when strict overcommit is enabled
+    pub commit_limit: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+}
+
+impl MemoryDetail {
+    /// Memory used by processes rather than by the kernel for caching
+    pub fn applications(&self) -> u64 {
+        self.total
+            .saturating_sub(self.free)
+            .saturating_sub(self.buffers)
+            .saturating_sub(self.cached)
+            .saturating_sub(self.slab_reclaimable)
+            .saturating_sub(self.slab_unreclaimable)
+    }
+
+    /// Total memory split into parts that add up to `total`
+    pub fn breakdown(&self) -> [(&'static str, u64); 5] {
+        [
+            ("Applications", self.applications()),
+            ("Buffers", self.buffers),
+            ("Cache", self.cached),
+            ("Slab", self.slab_reclaimable + self.slab_unreclaimable),
+            ("Free", self.free),
+        ]
+    }
+
+    pub fn commit_percent(&self) -> f32 {
+        if self.commit_limit > 0 {
+            self.committed_as as f32 / self.commit_limit as f32 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Parse the contents of `/proc/meminfo`
+pub fn parse_meminfo(content: &str) -> MemoryDetail {
+    let mut detail = MemoryDetail::default();
+    
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut parts = value.split_whitespace();
+        let Some(number) = parts.next().and_then(|n| n.parse::<u64>().ok()) else {
+            continue;
+        };
+        // Sizes are in kB; huge page counts have no unit
+        let bytes = if parts.next() == Some("kB") { number * 1024 } else { number };
+        
+        let field = match key {
+            "MemTotal" => &mut detail.total,
+            "MemFree" => &mut detail.free,
+            "MemAvailable" => &mut detail.available,
+            "Buffers" => &mut detail.buffers,
+            "Cached" => &mut detail.cached,
+            "Shmem" => &mut detail.shared,
+            "SReclaimable" => &mut detail.slab_reclaimable,
+            "SUnreclaim" => &mut detail.slab_unreclaimable,
+            "Dirty" => &mut detail.dirty,
+            "Writeback" => &mut detail.writeback,
+            "HugePages_Total" => &mut detail.hugepages_total,
+            "HugePages_Free" => &mut detail.hugepages_free,
+            "Hugepagesize" => &mut detail.hugepage_size,
+            "Committed_AS" => &mut detail.committed_as,
+            "CommitLimit" => &mut detail.commit_limit,
+            "SwapTotal" => &mut detail.swap_total,
+            "SwapFree" => &mut detail.swap_free,
+            _ => continue,
+        };
+        *field = bytes;
+    }
+    
+    detail
+}
+
+pub fn read_memory_detail(proc_root: &Path) -> Result<MemoryDetail> {
+    Ok(parse_meminfo(&std::fs::read_to_string(proc_root.join("meminfo"))?))
+}
+
+/// Build a snapshot from `/proc/meminfo` alone, counting used memory the way
+/// sysinfo does: everything that is not available
+pub fn snapshot_from_detail(detail: MemoryDetail) -> MemorySnapshot {
+    let percent = |part: u64, whole: u64| if whole > 0 { part as f32 / whole as f32 * 100.0 } else { 0.0 };
+    let used = detail.total.saturating_sub(detail.available);
+    let swap_used = detail.swap_total.saturating_sub(detail.swap_free);
+    
+    MemorySnapshot {
+        total: detail.total,
+        used,
+        available: detail.available,
+        usage_percent: percent(used, detail.total),
+        swap_total: detail.swap_total,
+        swap_used,
+        swap_usage_percent: percent(swap_used, detail.swap_total),
+        detail: Some(detail),
+    }
 }
 
 pub async fn get_memory_snapshot(system: &Arc<Mutex<System>>) -> Result<MemorySnapshot> {
@@ -39,6 +168,11 @@ pub async fn get_memory_snapshot(system: &Arc<Mutex<System>>) -> Result<MemorySn
         0.0
     };
     
+    #[cfg(target_os = "linux")]
+    let detail = read_memory_detail(Path::new("/proc")).ok();
+    #[cfg(not(target_os = "linux"))]
+    let detail = None;
+    
     Ok(MemorySnapshot {
         total,
         used,
@@ -47,5 +181,49 @@ pub async fn get_memory_snapshot(system: &Arc<Mutex<System>>) -> Result<MemorySn
         swap_total,
         swap_used,
         swap_usage_percent,
+        detail,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMINFO: &str = "\
+MemTotal:        1000 kB
+MemFree:          200 kB
+MemAvailable:     600 kB
+Buffers:           50 kB
+Cached:           300 kB
+Shmem:             40 kB
+SReclaimable:      60 kB
+SUnreclaim:        20 kB
+Dirty:             12 kB
+Writeback:          0 kB
+CommitLimit:     1500 kB
+Committed_AS:     900 kB
+HugePages_Total:    4
+HugePages_Free:     1
+Hugepagesize:    2048 kB
+";
+
+    #[test]
+    fn test_parse_meminfo() {
+        let detail = parse_meminfo(MEMINFO);
+        assert_eq!(detail.total, 1000 * 1024);
+        assert_eq!(detail.shared, 40 * 1024);
+        assert_eq!(detail.dirty, 12 * 1024);
+        assert_eq!(detail.hugepages_total, 4);
+        assert_eq!(detail.hugepage_size, 2048 * 1024);
+        assert!((detail.commit_percent() - 60.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_breakdown_adds_up_to_total() {
+        let detail = parse_meminfo(MEMINFO);
+        assert_eq!(detail.applications(), 370 * 1024);
+        
+        let sum: u64 = detail.breakdown().iter().map(|(_, bytes)| bytes).sum();
+        assert_eq!(sum, detail.total);
+    }
+}
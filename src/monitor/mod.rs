@@ -2,21 +2,51 @@
 //! System monitoring backends and data structures.
 
 pub mod battery;
+pub mod cgroup;
+pub mod collector;
 pub mod cpu;
 pub mod memory;
 pub mod disk;
+pub mod diskio;
+#[cfg(feature = "remote")]
+pub mod fleet;
+pub mod gpu;
+pub mod history;
 pub mod network;
+pub mod pressure;
 pub mod process;
+pub mod process_detail;
+pub mod procfs;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod replay;
+pub mod sensors;
+pub mod sockets;
+pub mod store;
 
 use anyhow::Result;
 use async_trait::async_trait;
 
 pub use battery::BatteryInfo;
+pub use cgroup::CgroupStats;
+pub use collector::{Collector, CollectorConfig, SystemSnapshot};
 pub use cpu::CpuSnapshot;
 pub use memory::MemorySnapshot;
 pub use disk::{DiskSnapshot, DiskInfo};
+pub use diskio::DiskIoStats;
+pub use gpu::{GpuBackend, GpuInfo};
+pub use history::{MetricsHistory, MetricsSample};
 pub use network::NetworkSnapshot;
-pub use process::ProcessInfo;
+pub use pressure::PressureSnapshot;
+pub use process::{ProcessInfo, ProcessSort, SortOrder};
+pub use process_detail::ProcessDetail;
+pub use procfs::ProcfsBackend;
+#[cfg(feature = "remote")]
+pub use remote::RemoteBackend;
+pub use replay::ReplayBackend;
+pub use sensors::SensorsSnapshot;
+pub use sockets::SocketInfo;
+pub use store::MetricsStore;
 
 /// Trait for system monitoring backends
 #[async_trait]
@@ -25,8 +55,16 @@ pub trait MonitorBackend: Send + Sync {
     async fn memory_snapshot(&self) -> Result<MemorySnapshot>;
     async fn disk_snapshot(&self) -> Result<DiskSnapshot>;
     async fn disk_list(&self) -> Result<Vec<DiskInfo>>;
+    async fn disk_io(&self) -> Result<Vec<DiskIoStats>>;
     async fn network_snapshot(&self) -> Result<NetworkSnapshot>;
-    async fn process_list(&self, filter: Option<&str>, sort: &str, limit: usize) -> Result<Vec<ProcessInfo>>;
+    async fn sensors_snapshot(&self) -> Result<SensorsSnapshot>;
+    async fn pressure(&self) -> Result<PressureSnapshot>;
+    async fn batteries(&self) -> Result<Vec<BatteryInfo>>;
+    async fn gpu_list(&self) -> Result<Vec<GpuInfo>>;
+    async fn sockets(&self) -> Result<Vec<SocketInfo>>;
+    async fn process_list(&self, filter: Option<&str>, sort: ProcessSort, order: SortOrder, limit: usize) -> Result<Vec<ProcessInfo>>;
+    async fn process_detail(&self, pid: u32) -> Result<ProcessDetail>;
+    async fn cgroups(&self) -> Result<Vec<CgroupStats>>;
 }
 
 /// Process management operations
@@ -40,14 +78,43 @@ pub trait ProcessManager: Send + Sync {
 /// Real implementation using sysinfo crate
 pub struct SysinfoBackend {
     system: std::sync::Arc<tokio::sync::Mutex<sysinfo::System>>,
+    network: std::sync::Arc<tokio::sync::Mutex<network::NetworkSampler>>,
+    cpu_refreshed_at: tokio::sync::Mutex<Option<std::time::Instant>>,
+    users: tokio::sync::Mutex<process::UserCache>,
+    process_io: tokio::sync::Mutex<process::ProcessIoTracker>,
+    disk_io: tokio::sync::Mutex<diskio::DiskIoTracker>,
+    components: tokio::sync::Mutex<sysinfo::Components>,
+    sysfs_root: std::path::PathBuf,
+    /// Detected on first use; `None` when GPUs are not monitored
+    gpu: Option<std::sync::OnceLock<Box<dyn GpuBackend>>>,
 }
 
 impl SysinfoBackend {
     pub fn new() -> Self {
         Self {
             system: std::sync::Arc::new(tokio::sync::Mutex::new(sysinfo::System::new_all())),
+            network: std::sync::Arc::new(tokio::sync::Mutex::new(network::NetworkSampler::new())),
+            cpu_refreshed_at: tokio::sync::Mutex::new(None),
+            users: tokio::sync::Mutex::new(process::UserCache::new()),
+            process_io: tokio::sync::Mutex::new(process::ProcessIoTracker::new()),
+            disk_io: tokio::sync::Mutex::new(diskio::DiskIoTracker::new()),
+            components: tokio::sync::Mutex::new(sysinfo::Components::new()),
+            sysfs_root: std::path::PathBuf::from("/sys"),
+            gpu: Some(std::sync::OnceLock::new()),
         }
     }
+
+    /// Read sysfs based sensors, batteries and GPUs below `root` instead of `/sys`
+    pub fn with_sysfs_root(mut self, root: impl Into<std::path::PathBuf>) -> Self {
+        self.sysfs_root = root.into();
+        self
+    }
+
+    /// Report no GPUs, without probing for them
+    pub fn without_gpu(mut self) -> Self {
+        self.gpu = None;
+        self
+    }
 }
 
 impl Default for SysinfoBackend {
@@ -59,7 +126,7 @@ impl Default for SysinfoBackend {
 #[async_trait]
 impl MonitorBackend for SysinfoBackend {
     async fn cpu_snapshot(&self) -> Result<CpuSnapshot> {
-        cpu::get_cpu_snapshot(&self.system).await
+        cpu::get_cpu_snapshot(&self.system, &self.cpu_refreshed_at).await
     }
 
     async fn memory_snapshot(&self) -> Result<MemorySnapshot> {
@@ -74,12 +141,88 @@ impl MonitorBackend for SysinfoBackend {
         disk::get_disk_list(&self.system).await
     }
 
+    async fn disk_io(&self) -> Result<Vec<DiskIoStats>> {
+        #[cfg(target_os = "linux")]
+        {
+            let content = std::fs::read_to_string("/proc/diskstats")?;
+            let mut stats = self.disk_io.lock().await
+                .update(diskio::parse_diskstats(&content), std::time::Instant::now());
+            let disks = disk::get_disk_list(&self.system).await?;
+            diskio::map_mounts(&mut stats, &disks, &diskio::read_dm_names(&self.sysfs_root));
+            Ok(stats)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            anyhow::bail!("disk I/O statistics are only available on Linux")
+        }
+    }
+
     async fn network_snapshot(&self) -> Result<NetworkSnapshot> {
-        network::get_network_snapshot(&self.system).await
+        network::get_network_snapshot(&self.network).await
+    }
+
+    async fn sensors_snapshot(&self) -> Result<SensorsSnapshot> {
+        sensors::get_sensors_snapshot(&self.components, &self.sysfs_root).await
+    }
+
+    async fn pressure(&self) -> Result<PressureSnapshot> {
+        #[cfg(target_os = "linux")]
+        {
+            pressure::read_system_pressure(std::path::Path::new("/proc"))
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            anyhow::bail!("pressure stall information is only available on Linux")
+        }
+    }
+
+    async fn batteries(&self) -> Result<Vec<BatteryInfo>> {
+        battery::get_batteries(&self.sysfs_root).await
     }
 
-    async fn process_list(&self, filter: Option<&str>, sort: &str, limit: usize) -> Result<Vec<ProcessInfo>> {
-        process::get_process_list(&self.system, filter, sort, limit).await
+    async fn gpu_list(&self) -> Result<Vec<GpuInfo>> {
+        match &self.gpu {
+            Some(gpu) => gpu.get_or_init(|| gpu::detect(&self.sysfs_root)).gpus().await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn sockets(&self) -> Result<Vec<SocketInfo>> {
+        #[cfg(target_os = "linux")]
+        {
+            sockets::list_sockets(std::path::Path::new("/proc"))
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            anyhow::bail!("socket listing is only available on Linux")
+        }
+    }
+
+    async fn process_list(&self, filter: Option<&str>, sort: ProcessSort, order: SortOrder, limit: usize) -> Result<Vec<ProcessInfo>> {
+        process::get_process_list(&self.system, &self.users, &self.process_io, filter, sort, order, limit).await
+    }
+
+    async fn process_detail(&self, pid: u32) -> Result<ProcessDetail> {
+        #[cfg(target_os = "linux")]
+        {
+            process_detail::read_process_detail(std::path::Path::new("/proc"), pid)
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let mut sys = self.system.lock().await;
+            process_detail::sysinfo_process_detail(&mut sys, pid)
+        }
+    }
+
+    async fn cgroups(&self) -> Result<Vec<CgroupStats>> {
+        #[cfg(target_os = "linux")]
+        {
+            cgroup::list_cgroups(std::path::Path::new("/proc"), std::path::Path::new("/sys/fs/cgroup"))
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            anyhow::bail!("cgroups are only available on Linux")
+        }
     }
 }
 
@@ -3,8 +3,10 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
-use sysinfo::{Networks, System};
+use std::time::Instant;
+use sysinfo::Networks;
 use tokio::sync::Mutex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +14,8 @@ pub struct NetworkSnapshot {
     pub interfaces: Vec<NetworkInterface>,
     pub total_rx: u64,
     pub total_tx: u64,
+    pub total_rx_rate: u64,
+    pub total_tx_rate: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,40 +27,223 @@ pub struct NetworkInterface {
     pub tx_rate: u64,
     pub packets_received: u64,
     pub packets_transmitted: u64,
+    pub packets_rx_rate: f64,
+    pub packets_tx_rate: f64,
     pub errors_received: u64,
     pub errors_transmitted: u64,
+    pub errors_rx_rate: f64,
+    pub errors_tx_rate: f64,
 }
 
-pub async fn get_network_snapshot(_system: &Arc<Mutex<System>>) -> Result<NetworkSnapshot> {
-    let networks = Networks::new_with_refreshed_list();
-    
-    let mut interfaces = Vec::new();
-    let mut total_rx = 0u64;
-    let mut total_tx = 0u64;
-    
-    for (name, data) in networks.list() {
-        let received = data.total_received();
-        let transmitted = data.total_transmitted();
-        
-        total_rx += received;
-        total_tx += transmitted;
-        
-        interfaces.push(NetworkInterface {
-            name: name.clone(),
-            received,
-            transmitted,
-            rx_rate: data.received(),
-            tx_rate: data.transmitted(),
-            packets_received: data.total_packets_received(),
-            packets_transmitted: data.total_packets_transmitted(),
-            errors_received: data.total_errors_on_received(),
-            errors_transmitted: data.total_errors_on_transmitted(),
-        });
-    }
-    
-    Ok(NetworkSnapshot {
-        interfaces,
-        total_rx,
-        total_tx,
-    })
+/// Cumulative counters of a single interface at one point in time.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InterfaceCounters {
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_packets: u64,
+    pub tx_packets: u64,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+}
+
+/// Turns successive cumulative counters into per-second rates.
+///
+/// The first sample of an interface reports zero rates; every following
+/// sample reports the delta since the previous one divided by the elapsed
+/// wall-clock time. Counter resets (e.g. an interface going down and up)
+/// are treated as zero traffic rather than wrapping around.
+#[derive(Debug, Default)]
+pub struct NetworkRateTracker {
+    previous: HashMap<String, InterfaceCounters>,
+    last_sample: Option<Instant>,
+}
+
+impl NetworkRateTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a new set of counters taken at `now` and build a snapshot.
+    pub fn update(&mut self, counters: Vec<(String, InterfaceCounters)>, now: Instant) -> NetworkSnapshot {
+        let elapsed = self
+            .last_sample
+            .map(|last| now.saturating_duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+
+        let per_second = |current: u64, previous: u64| -> f64 {
+            if elapsed > 0.0 {
+                current.saturating_sub(previous) as f64 / elapsed
+            } else {
+                0.0
+            }
+        };
+
+        let mut interfaces = Vec::with_capacity(counters.len());
+        let mut total_rx = 0u64;
+        let mut total_tx = 0u64;
+        let mut total_rx_rate = 0u64;
+        let mut total_tx_rate = 0u64;
+
+        for (name, current) in &counters {
+            // Interfaces seen for the first time have no baseline yet
+            let previous = self.previous.get(name).copied().unwrap_or(*current);
+
+            let rx_rate = per_second(current.rx_bytes, previous.rx_bytes) as u64;
+            let tx_rate = per_second(current.tx_bytes, previous.tx_bytes) as u64;
+
+            total_rx += current.rx_bytes;
+            total_tx += current.tx_bytes;
+            total_rx_rate += rx_rate;
+            total_tx_rate += tx_rate;
+
+            interfaces.push(NetworkInterface {
+                name: name.clone(),
+                received: current.rx_bytes,
+                transmitted: current.tx_bytes,
+                rx_rate,
+                tx_rate,
+                packets_received: current.rx_packets,
+                packets_transmitted: current.tx_packets,
+                packets_rx_rate: per_second(current.rx_packets, previous.rx_packets),
+                packets_tx_rate: per_second(current.tx_packets, previous.tx_packets),
+                errors_received: current.rx_errors,
+                errors_transmitted: current.tx_errors,
+                errors_rx_rate: per_second(current.rx_errors, previous.rx_errors),
+                errors_tx_rate: per_second(current.tx_errors, previous.tx_errors),
+            });
+        }
+
+        interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Forget interfaces that disappeared since the last sample
+        self.previous = counters.into_iter().collect();
+        self.last_sample = Some(now);
+
+        NetworkSnapshot {
+            interfaces,
+            total_rx,
+            total_tx,
+            total_rx_rate,
+            total_tx_rate,
+        }
+    }
+}
+
+/// Persistent network sampler backed by sysinfo.
+///
+/// Keeps the `Networks` list alive between calls so that the rate tracker
+/// can compare each sample with the previous one.
+pub struct NetworkSampler {
+    networks: Networks,
+    tracker: NetworkRateTracker,
+}
+
+impl NetworkSampler {
+    pub fn new() -> Self {
+        Self {
+            networks: Networks::new_with_refreshed_list(),
+            tracker: NetworkRateTracker::new(),
+        }
+    }
+
+    pub fn sample(&mut self) -> NetworkSnapshot {
+        // Pick up interfaces that appeared or vanished since the last call
+        self.networks.refresh_list();
+
+        let counters = self
+            .networks
+            .list()
+            .iter()
+            .map(|(name, data)| {
+                (
+                    name.clone(),
+                    InterfaceCounters {
+                        rx_bytes: data.total_received(),
+                        tx_bytes: data.total_transmitted(),
+                        rx_packets: data.total_packets_received(),
+                        tx_packets: data.total_packets_transmitted(),
+                        rx_errors: data.total_errors_on_received(),
+                        tx_errors: data.total_errors_on_transmitted(),
+                    },
+                )
+            })
+            .collect();
+
+        self.tracker.update(counters, Instant::now())
+    }
+}
+
+impl Default for NetworkSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub async fn get_network_snapshot(sampler: &Arc<Mutex<NetworkSampler>>) -> Result<NetworkSnapshot> {
+    let mut sampler = sampler.lock().await;
+    Ok(sampler.sample())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn counters(rx_bytes: u64, tx_bytes: u64, rx_packets: u64, rx_errors: u64) -> InterfaceCounters {
+        InterfaceCounters {
+            rx_bytes,
+            tx_bytes,
+            rx_packets,
+            tx_packets: 0,
+            rx_errors,
+            tx_errors: 0,
+        }
+    }
+
+    #[test]
+    fn test_first_sample_has_zero_rates() {
+        let mut tracker = NetworkRateTracker::new();
+        let snapshot = tracker.update(vec![("eth0".to_string(), counters(1000, 500, 10, 0))], Instant::now());
+
+        assert_eq!(snapshot.total_rx, 1000);
+        assert_eq!(snapshot.total_tx, 500);
+        assert_eq!(snapshot.interfaces[0].rx_rate, 0);
+        assert_eq!(snapshot.interfaces[0].tx_rate, 0);
+        assert_eq!(snapshot.total_rx_rate, 0);
+    }
+
+    #[test]
+    fn test_rates_are_per_second() {
+        let mut tracker = NetworkRateTracker::new();
+        let start = Instant::now();
+        tracker.update(vec![("eth0".to_string(), counters(1000, 500, 10, 1))], start);
+
+        let snapshot = tracker.update(
+            vec![("eth0".to_string(), counters(5000, 2500, 50, 3))],
+            start + Duration::from_secs(2),
+        );
+
+        let iface = &snapshot.interfaces[0];
+        assert_eq!(iface.rx_rate, 2000);
+        assert_eq!(iface.tx_rate, 1000);
+        assert_eq!(iface.packets_rx_rate, 20.0);
+        assert_eq!(iface.errors_rx_rate, 1.0);
+        assert_eq!(snapshot.total_rx_rate, 2000);
+        assert_eq!(snapshot.total_tx_rate, 1000);
+    }
+
+    #[test]
+    fn test_counter_reset_does_not_wrap() {
+        let mut tracker = NetworkRateTracker::new();
+        let start = Instant::now();
+        tracker.update(vec![("eth0".to_string(), counters(5000, 5000, 0, 0))], start);
+
+        let snapshot = tracker.update(
+            vec![("eth0".to_string(), counters(100, 100, 0, 0))],
+            start + Duration::from_secs(1),
+        );
+
+        assert_eq!(snapshot.interfaces[0].rx_rate, 0);
+        assert_eq!(snapshot.interfaces[0].tx_rate, 0);
+    }
 }
@@ -0,0 +1,176 @@
+// src/monitor/pressure.rs
+//! Pressure Stall Information (PSI).
+//!
+//! PSI reports the share of time tasks were stalled waiting for CPU, memory
+//! or I/O. `some` counts time at least one task was stalled, `full` time all
+//! non-idle tasks were stalled at once. The same format is used system-wide
+//! in `/proc/pressure/*` and per cgroup in `*.pressure` files.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Stall averages in percent over 10s, 60s and 300s, plus total stall time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PressureLine {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+    /// Total stall time in microseconds
+    pub total_us: u64,
+}
+
+impl PressureLine {
+    /// Average over the window given in seconds (10, 60 or 300)
+    pub fn average(&self, window: u32) -> Option<f32> {
+        match window {
+            10 => Some(self.avg10),
+            60 => Some(self.avg60),
+            300 => Some(self.avg300),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pressure {
+    pub some: PressureLine,
+    /// Not reported for CPU on kernels before 5.13
+    pub full: Option<PressureLine>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PressureSnapshot {
+    pub cpu: Option<Pressure>,
+    pub memory: Option<Pressure>,
+    pub io: Option<Pressure>,
+}
+
+impl PressureSnapshot {
+    pub fn resource(&self, name: &str) -> Option<&Pressure> {
+        match name {
+            "cpu" => self.cpu.as_ref(),
+            "memory" => self.memory.as_ref(),
+            "io" => self.io.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Look up a value by name, e.g. `cpu.some.avg10` or `io.full.total`
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        let mut parts = name.split('.');
+        let pressure = self.resource(parts.next()?)?;
+        let line = match parts.next()? {
+            "some" => pressure.some,
+            "full" => pressure.full?,
+            _ => return None,
+        };
+        let value = match parts.next()? {
+            "total" => line.total_us as f64,
+            window => line.average(window.strip_prefix("avg")?.parse().ok()?)? as f64,
+        };
+        parts.next().is_none().then_some(value)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_none() && self.memory.is_none() && self.io.is_none()
+    }
+}
+
+/// Parse one PSI file
+pub fn parse_pressure(content: &str) -> Result<Pressure> {
+    let mut some = None;
+    let mut full = None;
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next();
+        let mut parsed = PressureLine::default();
+        for field in fields {
+            let Some((key, value)) = field.split_once('=') else {
+                continue;
+            };
+            match key {
+                "avg10" => parsed.avg10 = value.parse()?,
+                "avg60" => parsed.avg60 = value.parse()?,
+                "avg300" => parsed.avg300 = value.parse()?,
+                "total" => parsed.total_us = value.parse()?,
+                _ => {}
+            }
+        }
+        match kind {
+            Some("some") => some = Some(parsed),
+            Some("full") => full = Some(parsed),
+            _ => {}
+        }
+    }
+
+    Ok(Pressure {
+        some: some.context("no \"some\" line in pressure file")?,
+        full,
+    })
+}
+
+fn read_pressure(path: &Path) -> Option<Pressure> {
+    parse_pressure(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+/// System-wide pressure from `<proc_root>/pressure`; needs a kernel built with PSI
+pub fn read_system_pressure(proc_root: &Path) -> Result<PressureSnapshot> {
+    let dir = proc_root.join("pressure");
+    let snapshot = PressureSnapshot {
+        cpu: read_pressure(&dir.join("cpu")),
+        memory: read_pressure(&dir.join("memory")),
+        io: read_pressure(&dir.join("io")),
+    };
+    if snapshot.is_empty() {
+        anyhow::bail!("pressure stall information is not available in {}", dir.display());
+    }
+    Ok(snapshot)
+}
+
+/// Pressure of a single cgroup v2 directory
+pub fn read_cgroup_pressure(cgroup_dir: &Path) -> Option<PressureSnapshot> {
+    let snapshot = PressureSnapshot {
+        cpu: read_pressure(&cgroup_dir.join("cpu.pressure")),
+        memory: read_pressure(&cgroup_dir.join("memory.pressure")),
+        io: read_pressure(&cgroup_dir.join("io.pressure")),
+    };
+    (!snapshot.is_empty()).then_some(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMORY: &str = "\
+some avg10=1.50 avg60=0.80 avg300=0.25 total=123456
+full avg10=0.50 avg60=0.20 avg300=0.05 total=4567
+";
+
+    #[test]
+    fn test_parse_pressure() {
+        let pressure = parse_pressure(MEMORY).unwrap();
+        assert_eq!(pressure.some.avg10, 1.5);
+        assert_eq!(pressure.some.total_us, 123456);
+        assert_eq!(pressure.full.unwrap().avg300, 0.05);
+
+        // Older kernels only report "some" for CPU
+        let cpu = parse_pressure("some avg10=0.00 avg60=0.00 avg300=0.00 total=0\n").unwrap();
+        assert!(cpu.full.is_none());
+        assert!(parse_pressure("").is_err());
+    }
+
+    #[test]
+    fn test_metric_lookup() {
+        let snapshot = PressureSnapshot {
+            memory: Some(parse_pressure(MEMORY).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(snapshot.metric("memory.some.avg10"), Some(1.5));
+        assert_eq!(snapshot.metric("memory.full.total"), Some(4567.0));
+        assert_eq!(snapshot.metric("cpu.some.avg10"), None);
+        assert_eq!(snapshot.metric("memory.some.avg15"), None);
+        assert_eq!(snapshot.metric("memory.some"), None);
+    }
+}
@@ -2,75 +2,537 @@
 //! Process monitoring and management.
 
 use anyhow::Result;
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use sysinfo::{Pid, ProcessRefreshKind, Signal, System};
+use std::time::{Duration, Instant};
+use sysinfo::{Gid, Groups, Pid, ProcessRefreshKind, Signal, System, Uid, Users};
 use tokio::sync::Mutex;
 
+use super::cgroup::{self, Container};
+
+/// Minimum time between two reloads of the user and group databases
+const USER_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
     pub pid: u32,
+    pub ppid: Option<u32>,
     pub name: String,
+    /// Owner name, or the numeric id if it cannot be resolved
     pub user: String,
+    pub uid: Option<u32>,
+    pub effective_user: String,
+    pub euid: Option<u32>,
+    pub effective_group: String,
+    pub egid: Option<u32>,
     pub cpu_usage: f32,
     pub memory_usage: f32,
+    /// Total bytes read since the process started
     pub disk_read: u64,
+    /// Total bytes written since the process started
     pub disk_write: u64,
+    /// Bytes read per second since the previous sample
+    pub disk_read_rate: u64,
+    /// Bytes written per second since the previous sample
+    pub disk_write_rate: u64,
     pub threads: usize,
     pub status: String,
     pub start_time: u64,
     pub exe_path: String,
+    /// cgroup v2 path, where the platform has cgroups
+    pub cgroup: Option<String>,
+    /// Container or systemd unit derived from the cgroup path
+    pub container: Option<Container>,
+}
+
+/// Resolves user and group ids to names.
+///
+/// Resolved names are remembered; unknown ids trigger a reload of the
+/// account databases at most once per [`USER_REFRESH_INTERVAL`] so that
+/// accounts created after startup are eventually picked up.
+pub struct UserCache {
+    users: Users,
+    groups: Groups,
+    user_names: HashMap<Uid, String>,
+    group_names: HashMap<Gid, String>,
+    last_refresh: Instant,
+}
+
+impl UserCache {
+    pub fn new() -> Self {
+        Self {
+            users: Users::new_with_refreshed_list(),
+            groups: Groups::new_with_refreshed_list(),
+            user_names: HashMap::new(),
+            group_names: HashMap::new(),
+            last_refresh: Instant::now(),
+        }
+    }
+    
+    fn refresh_if_stale(&mut self) {
+        if self.last_refresh.elapsed() >= USER_REFRESH_INTERVAL {
+            self.users.refresh_list();
+            self.groups.refresh_list();
+            self.last_refresh = Instant::now();
+        }
+    }
+    
+    pub fn user_name(&mut self, uid: &Uid) -> String {
+        if let Some(name) = self.user_names.get(uid) {
+            return name.clone();
+        }
+        
+        if self.users.get_user_by_id(uid).is_none() {
+            self.refresh_if_stale();
+        }
+        
+        match self.users.get_user_by_id(uid) {
+            Some(user) => {
+                let name = user.name().to_string();
+                self.user_names.insert(uid.clone(), name.clone());
+                name
+            }
+            None => uid.to_string(),
+        }
+    }
+    
+    pub fn group_name(&mut self, gid: &Gid) -> String {
+        if let Some(name) = self.group_names.get(gid) {
+            return name.clone();
+        }
+        
+        let find = |groups: &Groups| groups.list().iter()
+            .find(|group| group.id() == gid)
+            .map(|group| group.name().to_string());
+        
+        let mut name = find(&self.groups);
+        if name.is_none() {
+            self.refresh_if_stale();
+            name = find(&self.groups);
+        }
+        
+        match name {
+            Some(name) => {
+                self.group_names.insert(*gid, name.clone());
+                name
+            }
+            None => gid.to_string(),
+        }
+    }
+}
+
+impl Default for UserCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Numeric representation of a platform user id, where one exists
+fn numeric_id(id: &str) -> Option<u32> {
+    id.parse().ok()
+}
+
+/// Key used to order process lists
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSort {
+    /// CPU usage
+    #[default]
+    Cpu,
+    /// Memory usage
+    Mem,
+    /// Disk read + write rate
+    Io,
+    /// Process ID
+    Pid,
+    /// Process name
+    Name,
+    /// Thread count
+    Threads,
+    /// Start time
+    #[value(name = "start")]
+    #[serde(rename = "start")]
+    StartTime,
+}
+
+/// Direction of a process list ordering
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl ProcessSort {
+    /// Natural direction for the key: biggest consumers first for resource
+    /// columns, ascending for identifiers and names.
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            ProcessSort::Cpu | ProcessSort::Mem | ProcessSort::Io | ProcessSort::Threads => SortOrder::Desc,
+            ProcessSort::Pid | ProcessSort::Name | ProcessSort::StartTime => SortOrder::Asc,
+        }
+    }
+}
+
+/// Sort processes in place by the given key and direction
+pub fn sort_processes(processes: &mut [ProcessInfo], sort: ProcessSort, order: SortOrder) {
+    processes.sort_by(|a, b| {
+        let ordering = match sort {
+            ProcessSort::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+            ProcessSort::Mem => a.memory_usage.total_cmp(&b.memory_usage),
+            ProcessSort::Io => (a.disk_read_rate + a.disk_write_rate).cmp(&(b.disk_read_rate + b.disk_write_rate))
+                .then_with(|| (a.disk_read + a.disk_write).cmp(&(b.disk_read + b.disk_write))),
+            ProcessSort::Pid => a.pid.cmp(&b.pid),
+            ProcessSort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            ProcessSort::Threads => a.threads.cmp(&b.threads),
+            ProcessSort::StartTime => a.start_time.cmp(&b.start_time),
+        }
+        // Keep the output stable between refreshes for equal keys
+        .then_with(|| a.pid.cmp(&b.pid));
+        
+        match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
+}
+
+/// Identity of a process that stays unique across PID reuse
+pub type ProcessKey = (u32, u64);
+
+/// Cumulative bytes read and written by one process
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IoCounters {
+    pub read_bytes: u64,
+    pub write_bytes: u64,
+}
+
+/// Turns cumulative per-process disk counters into per-second rates.
+///
+/// Counters are keyed by PID and start time, so a process that reuses the
+/// PID of an exited one starts from a fresh baseline instead of inheriting
+/// its predecessor's totals. Like [`NetworkRateTracker`], the first sample
+/// of a process reports zero rates.
+///
+/// [`NetworkRateTracker`]: super::network::NetworkRateTracker
+#[derive(Debug, Default)]
+pub struct ProcessIoTracker {
+    previous: HashMap<ProcessKey, IoCounters>,
+    last_sample: Option<Instant>,
+}
+
+impl ProcessIoTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Record the counters of every live process taken at `now` and return
+    /// the read and write rates in bytes per second.
+    pub fn update(&mut self, counters: HashMap<ProcessKey, IoCounters>, now: Instant) -> HashMap<ProcessKey, IoCounters> {
+        let elapsed = self.last_sample
+            .map(|last| now.saturating_duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+        
+        let per_second = |current: u64, previous: u64| -> u64 {
+            if elapsed > 0.0 {
+                (current.saturating_sub(previous) as f64 / elapsed) as u64
+            } else {
+                0
+            }
+        };
+        
+        let rates = counters.iter()
+            .map(|(key, current)| {
+                let previous = self.previous.get(key).copied().unwrap_or(*current);
+                (*key, IoCounters {
+                    read_bytes: per_second(current.read_bytes, previous.read_bytes),
+                    write_bytes: per_second(current.write_bytes, previous.write_bytes),
+                })
+            })
+            .collect();
+        
+        // Exited processes are dropped along with their counters
+        self.previous = counters;
+        self.last_sample = Some(now);
+        
+        rates
+    }
 }
 
 pub async fn get_process_list(
     system: &Arc<Mutex<System>>,
+    users: &Mutex<UserCache>,
+    io: &Mutex<ProcessIoTracker>,
     filter: Option<&str>,
-    _sort: &str,
-    _limit: usize,
+    sort: ProcessSort,
+    order: SortOrder,
+    limit: usize,
 ) -> Result<Vec<ProcessInfo>> {
     let mut sys = system.lock().await;
+    let mut users = users.lock().await;
     
     // Refresh process list
     sys.refresh_processes_specifics(ProcessRefreshKind::everything());
     
+    // Rates are tracked for every process, not just the ones that pass
+    // the filter, so that filtered and unfiltered calls share a baseline
+    let counters = sys.processes()
+        .iter()
+        .map(|(pid, process)| {
+            let usage = process.disk_usage();
+            ((pid.as_u32(), process.start_time()), IoCounters {
+                read_bytes: usage.total_read_bytes,
+                write_bytes: usage.total_written_bytes,
+            })
+        })
+        .collect();
+    let io_rates = io.lock().await.update(counters, Instant::now());
+    
+    let filter = filter.map(|f| f.to_lowercase());
     let mut processes = Vec::new();
     
     for (pid, process) in sys.processes() {
+        // Threads are already accounted for in their owner's thread count
+        if matches!(process.thread_kind(), Some(sysinfo::ThreadKind::Userland)) {
+            continue;
+        }
+        
         let name = process.name().to_string();
         
-        // Apply filter if provided
-        if let Some(filter_str) = filter {
-            if !name.to_lowercase().contains(&filter_str.to_lowercase()) {
+        let user = process.user_id()
+            .map(|uid| users.user_name(uid))
+            .unwrap_or_else(|| "unknown".to_string());
+        
+        // Apply filter if provided, matching either the name or the owner
+        if let Some(filter_str) = &filter {
+            if !name.to_lowercase().contains(filter_str) && !user.to_lowercase().contains(filter_str) {
                 continue;
             }
         }
         
-        let user = process.user_id()
-            .map(|uid| uid.to_string())
+        let effective_user = process.effective_user_id()
+            .map(|uid| users.user_name(uid))
+            .unwrap_or_else(|| user.clone());
+        let effective_group = process.effective_group_id()
+            .map(|gid| users.group_name(&gid))
             .unwrap_or_else(|| "unknown".to_string());
         
         let disk_usage = process.disk_usage();
+        let cgroup = process_cgroup(pid.as_u32());
+        let io_rate = io_rates.get(&(pid.as_u32(), process.start_time())).copied().unwrap_or_default();
         
         processes.push(ProcessInfo {
             pid: pid.as_u32(),
+            ppid: process.parent().map(|ppid| ppid.as_u32()),
             name,
             user,
+            uid: process.user_id().and_then(|uid| numeric_id(&uid.to_string())),
+            effective_user,
+            euid: process.effective_user_id().and_then(|uid| numeric_id(&uid.to_string())),
+            effective_group,
+            egid: process.effective_group_id().and_then(|gid| numeric_id(&gid.to_string())),
             cpu_usage: process.cpu_usage(),
             memory_usage: (process.memory() as f32 / sys.total_memory() as f32) * 100.0,
             disk_read: disk_usage.total_read_bytes,
             disk_write: disk_usage.total_written_bytes,
+            disk_read_rate: io_rate.read_bytes,
+            disk_write_rate: io_rate.write_bytes,
             threads: process.tasks().map(|t| t.len()).unwrap_or(0),
             status: format!("{:?}", process.status()),
             start_time: process.start_time(),
             exe_path: process.exe()
                 .map(|p| p.to_string_lossy().to_string())
                 .unwrap_or_else(|| "N/A".to_string()),
+            container: cgroup.as_deref().and_then(cgroup::container_from_path),
+            cgroup,
         });
     }
     
+    sort_processes(&mut processes, sort, order);
+    processes.truncate(limit);
+    
     Ok(processes)
 }
 
+#[cfg(target_os = "linux")]
+fn process_cgroup(pid: u32) -> Option<String> {
+    cgroup::read_process_cgroup(std::path::Path::new("/proc"), pid)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_cgroup(_pid: u32) -> Option<String> {
+    None
+}
+
+/// A process together with its descendants
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessNode {
+    pub process: ProcessInfo,
+    pub children: Vec<ProcessNode>,
+    /// CPU usage of the process and all of its descendants
+    pub subtree_cpu: f32,
+    /// Memory usage of the process and all of its descendants
+    pub subtree_memory: f32,
+}
+
+impl ProcessNode {
+    /// Number of descendants below this node
+    pub fn descendant_count(&self) -> usize {
+        self.children.iter().map(|c| 1 + c.descendant_count()).sum()
+    }
+}
+
+/// One visible line of a flattened [`ProcessTree`]
+#[derive(Debug, Clone)]
+pub struct TreeRow<'a> {
+    pub node: &'a ProcessNode,
+    pub depth: usize,
+    /// Box-drawing prefix to print before the process name
+    pub prefix: String,
+    pub collapsed: bool,
+}
+
+/// Forest of processes linked through their parent PID.
+///
+/// Processes whose parent is not part of the input list become roots, so a
+/// filtered or truncated list still yields a valid forest.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProcessTree {
+    pub roots: Vec<ProcessNode>,
+}
+
+impl ProcessTree {
+    pub fn build(processes: &[ProcessInfo]) -> Self {
+        let index: HashMap<u32, usize> = processes.iter()
+            .enumerate()
+            .map(|(i, p)| (p.pid, i))
+            .collect();
+        
+        let mut children: HashMap<u32, Vec<usize>> = HashMap::new();
+        let mut roots = Vec::new();
+        
+        for (i, process) in processes.iter().enumerate() {
+            match process.ppid {
+                Some(ppid) if ppid != process.pid && index.contains_key(&ppid) => {
+                    children.entry(ppid).or_default().push(i);
+                }
+                _ => roots.push(i),
+            }
+        }
+        
+        let mut visited = vec![false; processes.len()];
+        let mut tree_roots: Vec<ProcessNode> = roots.iter()
+            .map(|&i| Self::build_node(processes, &children, &mut visited, i))
+            .collect();
+        
+        // Parent loops can only come from inconsistent input; keep those
+        // processes visible instead of dropping them
+        for i in 0..processes.len() {
+            if !visited[i] {
+                tree_roots.push(Self::build_node(processes, &children, &mut visited, i));
+            }
+        }
+        
+        tree_roots.sort_by_key(|node| node.process.pid);
+        Self { roots: tree_roots }
+    }
+    
+    fn build_node(
+        processes: &[ProcessInfo],
+        children: &HashMap<u32, Vec<usize>>,
+        visited: &mut [bool],
+        i: usize,
+    ) -> ProcessNode {
+        visited[i] = true;
+        let process = processes[i].clone();
+        
+        let mut nodes: Vec<ProcessNode> = children.get(&process.pid)
+            .map(|kids| kids.iter()
+                .filter(|&&k| !visited[k])
+                .copied()
+                .collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|k| Self::build_node(processes, children, visited, k))
+            .collect();
+        nodes.sort_by_key(|node| node.process.pid);
+        
+        let subtree_cpu = process.cpu_usage + nodes.iter().map(|n| n.subtree_cpu).sum::<f32>();
+        let subtree_memory = process.memory_usage + nodes.iter().map(|n| n.subtree_memory).sum::<f32>();
+        
+        ProcessNode {
+            process,
+            children: nodes,
+            subtree_cpu,
+            subtree_memory,
+        }
+    }
+    
+    /// Total number of processes in the forest
+    pub fn len(&self) -> usize {
+        self.roots.iter().map(|r| 1 + r.descendant_count()).sum()
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+    
+    /// Find the node of a process by PID
+    pub fn find(&self, pid: u32) -> Option<&ProcessNode> {
+        fn search(nodes: &[ProcessNode], pid: u32) -> Option<&ProcessNode> {
+            nodes.iter().find_map(|node| {
+                if node.process.pid == pid {
+                    Some(node)
+                } else {
+                    search(&node.children, pid)
+                }
+            })
+        }
+        search(&self.roots, pid)
+    }
+    
+    /// Depth-first list of visible rows, skipping the descendants of
+    /// collapsed PIDs
+    pub fn flatten(&self, collapsed: &HashSet<u32>) -> Vec<TreeRow<'_>> {
+        fn walk<'a>(
+            nodes: &'a [ProcessNode],
+            depth: usize,
+            indent: &str,
+            collapsed: &HashSet<u32>,
+            rows: &mut Vec<TreeRow<'a>>,
+        ) {
+            for (i, node) in nodes.iter().enumerate() {
+                let last = i + 1 == nodes.len();
+                let (branch, continuation) = if depth == 0 {
+                    ("", "")
+                } else if last {
+                    ("└─ ", "   ")
+                } else {
+                    ("├─ ", "│  ")
+                };
+                let is_collapsed = collapsed.contains(&node.process.pid) && !node.children.is_empty();
+                
+                rows.push(TreeRow {
+                    node,
+                    depth,
+                    prefix: format!("{}{}", indent, branch),
+                    collapsed: is_collapsed,
+                });
+                
+                if !is_collapsed {
+                    walk(&node.children, depth + 1, &format!("{}{}", indent, continuation), collapsed, rows);
+                }
+            }
+        }
+        
+        let mut rows = Vec::new();
+        walk(&self.roots, 0, "", collapsed, &mut rows);
+        rows
+    }
+}
+
 pub async fn kill_process(
     system: &Arc<Mutex<System>>,
     pid: u32,
@@ -195,3 +657,117 @@ pub async fn resume_process(
         anyhow::bail!("Resume not supported on this platform")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(pid: u32, ppid: Option<u32>, cpu: f32, mem: f32) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            ppid,
+            name: format!("proc{}", pid),
+            user: "root".to_string(),
+            uid: Some(0),
+            effective_user: "root".to_string(),
+            euid: Some(0),
+            effective_group: "root".to_string(),
+            egid: Some(0),
+            cpu_usage: cpu,
+            memory_usage: mem,
+            disk_read: 0,
+            disk_write: 0,
+            disk_read_rate: 0,
+            disk_write_rate: 0,
+            threads: 1,
+            status: "Running".to_string(),
+            start_time: 0,
+            exe_path: "N/A".to_string(),
+            cgroup: None,
+            container: None,
+        }
+    }
+
+    fn sample_tree() -> ProcessTree {
+        ProcessTree::build(&[
+            process(1, None, 1.0, 1.0),
+            process(10, Some(1), 2.0, 2.0),
+            process(11, Some(10), 4.0, 0.5),
+            process(12, Some(1), 8.0, 0.5),
+            // Parent is not in the list, so this becomes a root
+            process(50, Some(999), 0.5, 0.5),
+        ])
+    }
+
+    #[test]
+    fn test_tree_links_children_and_sums_subtrees() {
+        let tree = sample_tree();
+
+        assert_eq!(tree.roots.iter().map(|r| r.process.pid).collect::<Vec<_>>(), vec![1, 50]);
+        assert_eq!(tree.len(), 5);
+
+        let init = tree.find(1).unwrap();
+        assert_eq!(init.descendant_count(), 3);
+        assert_eq!(init.subtree_cpu, 15.0);
+        assert_eq!(init.subtree_memory, 4.0);
+        assert_eq!(tree.find(10).unwrap().subtree_cpu, 6.0);
+    }
+
+    #[test]
+    fn test_flatten_skips_collapsed_subtrees() {
+        let tree = sample_tree();
+
+        let rows = tree.flatten(&HashSet::new());
+        assert_eq!(rows.iter().map(|r| r.node.process.pid).collect::<Vec<_>>(), vec![1, 10, 11, 12, 50]);
+        assert_eq!(rows[2].prefix, "│  └─ ");
+        assert_eq!(rows[3].prefix, "└─ ");
+
+        let collapsed: HashSet<u32> = [10].into_iter().collect();
+        let rows = tree.flatten(&collapsed);
+        assert_eq!(rows.iter().map(|r| r.node.process.pid).collect::<Vec<_>>(), vec![1, 10, 12, 50]);
+        assert!(rows[1].collapsed);
+    }
+
+    fn io(read_bytes: u64, write_bytes: u64) -> IoCounters {
+        IoCounters { read_bytes, write_bytes }
+    }
+
+    #[test]
+    fn test_io_rates_are_per_second() {
+        let mut tracker = ProcessIoTracker::new();
+        let start = Instant::now();
+
+        let first = tracker.update([((10, 100), io(1000, 0))].into_iter().collect(), start);
+        assert_eq!(first[&(10, 100)], io(0, 0));
+
+        let second = tracker.update(
+            [((10, 100), io(5000, 2000))].into_iter().collect(),
+            start + Duration::from_secs(2),
+        );
+        assert_eq!(second[&(10, 100)], io(2000, 1000));
+    }
+
+    #[test]
+    fn test_io_rates_reset_on_pid_reuse() {
+        let mut tracker = ProcessIoTracker::new();
+        let start = Instant::now();
+        tracker.update([((10, 100), io(1000, 1000))].into_iter().collect(), start);
+
+        // Same PID, different start time: a new process with its own totals
+        let rates = tracker.update(
+            [((10, 200), io(50_000, 50_000))].into_iter().collect(),
+            start + Duration::from_secs(1),
+        );
+        assert_eq!(rates[&(10, 200)], io(0, 0));
+    }
+
+    #[test]
+    fn test_parent_cycle_keeps_every_process() {
+        let tree = ProcessTree::build(&[
+            process(2, Some(3), 0.0, 0.0),
+            process(3, Some(2), 0.0, 0.0),
+        ]);
+
+        assert_eq!(tree.len(), 2);
+    }
+}
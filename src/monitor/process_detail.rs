@@ -0,0 +1,325 @@
+// src/monitor/process_detail.rs
+//! Detailed information about a single process, fetched on demand.
+//!
+//! On Linux everything is read from procfs under a configurable root, so
+//! the same code works against `/proc` and against captured fixtures. Other
+//! platforms only get what sysinfo can provide.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProcessDetail {
+    pub pid: u32,
+    pub name: String,
+    /// Full argument vector, including the program name
+    pub cmdline: Vec<String>,
+    pub exe: Option<String>,
+    pub cwd: Option<String>,
+    pub root: Option<String>,
+    pub environ: BTreeMap<String, String>,
+    pub open_files: Vec<OpenFile>,
+    pub maps: MemoryMapsSummary,
+    pub limits: Vec<ResourceLimit>,
+    /// Sections that could not be read, usually for lack of permission
+    pub unavailable: Vec<String>,
+}
+
+/// An open file descriptor and what it points to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenFile {
+    pub fd: u32,
+    /// Link target, e.g. a path, `socket:[1234]` or `pipe:[5678]`
+    pub target: String,
+}
+
+/// Aggregated view of a process's memory mappings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryMapsSummary {
+    pub regions: usize,
+    /// Total mapped virtual size in bytes
+    pub total_size: u64,
+    pub anonymous_size: u64,
+    pub file_backed_size: u64,
+    pub heap_size: u64,
+    pub stack_size: u64,
+    /// Mapped files, largest first
+    pub files: Vec<MappedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MappedFile {
+    pub path: String,
+    pub size: u64,
+    pub regions: usize,
+}
+
+/// One row of `/proc/<pid>/limits`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimit {
+    pub name: String,
+    /// Soft limit, `None` when unlimited
+    pub soft: Option<u64>,
+    /// Hard limit, `None` when unlimited
+    pub hard: Option<u64>,
+    pub units: String,
+}
+
+/// Read the detail of `pid` from a procfs mounted at `proc_root`
+pub fn read_process_detail(proc_root: &Path, pid: u32) -> Result<ProcessDetail> {
+    let dir = proc_root.join(pid.to_string());
+    if !dir.is_dir() {
+        anyhow::bail!("Process {} not found", pid);
+    }
+
+    let mut detail = ProcessDetail {
+        pid,
+        name: std::fs::read_to_string(dir.join("comm"))
+            .map(|comm| comm.trim_end().to_string())
+            .unwrap_or_default(),
+        ..Default::default()
+    };
+
+    let read_link = |name: &str| std::fs::read_link(dir.join(name))
+        .ok()
+        .map(|target| target.to_string_lossy().to_string());
+
+    detail.exe = read_link("exe");
+    detail.cwd = read_link("cwd");
+    detail.root = read_link("root");
+    for (section, value) in [("exe", &detail.exe), ("cwd", &detail.cwd), ("root", &detail.root)] {
+        if value.is_none() {
+            detail.unavailable.push(section.to_string());
+        }
+    }
+
+    match std::fs::read(dir.join("cmdline")) {
+        Ok(raw) => detail.cmdline = split_nul(&raw),
+        Err(_) => detail.unavailable.push("cmdline".to_string()),
+    }
+
+    match std::fs::read(dir.join("environ")) {
+        Ok(raw) => detail.environ = parse_environ(&raw),
+        Err(_) => detail.unavailable.push("environ".to_string()),
+    }
+
+    match read_open_files(&dir.join("fd")) {
+        Ok(files) => detail.open_files = files,
+        Err(_) => detail.unavailable.push("fd".to_string()),
+    }
+
+    match std::fs::read_to_string(dir.join("maps")) {
+        Ok(maps) => detail.maps = summarize_maps(&maps),
+        Err(_) => detail.unavailable.push("maps".to_string()),
+    }
+
+    match std::fs::read_to_string(dir.join("limits")) {
+        Ok(limits) => detail.limits = parse_limits(&limits),
+        Err(_) => detail.unavailable.push("limits".to_string()),
+    }
+
+    Ok(detail)
+}
+
+/// Build a detail from sysinfo on platforms without procfs
+#[cfg(not(target_os = "linux"))]
+pub fn sysinfo_process_detail(system: &mut sysinfo::System, pid: u32) -> Result<ProcessDetail> {
+    let sys_pid = sysinfo::Pid::from_u32(pid);
+    system.refresh_process(sys_pid);
+    let process = system.process(sys_pid)
+        .ok_or_else(|| anyhow::anyhow!("Process {} not found", pid))?;
+
+    let path = |p: Option<&Path>| p.map(|p| p.to_string_lossy().to_string());
+
+    Ok(ProcessDetail {
+        pid,
+        name: process.name().to_string(),
+        cmdline: process.cmd().to_vec(),
+        exe: path(process.exe()),
+        cwd: path(process.cwd()),
+        root: path(process.root()),
+        environ: process.environ()
+            .iter()
+            .filter_map(|var| var.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect(),
+        unavailable: vec!["fd".to_string(), "maps".to_string(), "limits".to_string()],
+        ..Default::default()
+    })
+}
+
+fn split_nul(raw: &[u8]) -> Vec<String> {
+    raw.split(|&b| b == 0)
+        .filter(|part| !part.is_empty())
+        .map(|part| String::from_utf8_lossy(part).to_string())
+        .collect()
+}
+
+fn parse_environ(raw: &[u8]) -> BTreeMap<String, String> {
+    split_nul(raw)
+        .into_iter()
+        .filter_map(|var| {
+            let (key, value) = var.split_once('=')?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+fn read_open_files(fd_dir: &Path) -> Result<Vec<OpenFile>> {
+    let mut files = Vec::new();
+
+    for entry in std::fs::read_dir(fd_dir)? {
+        let entry = entry?;
+        let Some(fd) = entry.file_name().to_str().and_then(|name| name.parse().ok()) else {
+            continue;
+        };
+        // Descriptors may be closed between listing and reading them
+        let Ok(target) = std::fs::read_link(entry.path()) else {
+            continue;
+        };
+        files.push(OpenFile {
+            fd,
+            target: target.to_string_lossy().to_string(),
+        });
+    }
+
+    files.sort_by_key(|file| file.fd);
+    Ok(files)
+}
+
+/// Summarize the contents of `/proc/<pid>/maps`
+pub fn summarize_maps(maps: &str) -> MemoryMapsSummary {
+    let mut summary = MemoryMapsSummary::default();
+    let mut files: HashMap<String, MappedFile> = HashMap::new();
+
+    for line in maps.lines() {
+        // address perms offset dev inode [pathname]; the pathname may contain spaces
+        let mut fields = line.splitn(6, ' ');
+        let Some((start, end)) = fields.next().and_then(|range| range.split_once('-')) else {
+            continue;
+        };
+        let (Ok(start), Ok(end)) = (u64::from_str_radix(start, 16), u64::from_str_radix(end, 16)) else {
+            continue;
+        };
+        let size = end.saturating_sub(start);
+        let path = fields.nth(4).unwrap_or("").trim_start();
+
+        summary.regions += 1;
+        summary.total_size += size;
+
+        match path {
+            "" => summary.anonymous_size += size,
+            "[heap]" => summary.heap_size += size,
+            p if p.starts_with("[stack") => summary.stack_size += size,
+            // Other pseudo mappings such as [vdso] or [vvar]
+            p if p.starts_with('[') => summary.anonymous_size += size,
+            p => {
+                summary.file_backed_size += size;
+                let file = files.entry(p.to_string()).or_insert_with(|| MappedFile {
+                    path: p.to_string(),
+                    size: 0,
+                    regions: 0,
+                });
+                file.size += size;
+                file.regions += 1;
+            }
+        }
+    }
+
+    summary.files = files.into_values().collect();
+    summary.files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+    summary
+}
+
+/// Parse the table in `/proc/<pid>/limits`
+pub fn parse_limits(limits: &str) -> Vec<ResourceLimit> {
+    let parse_value = |value: &str| -> Option<u64> {
+        match value {
+            "unlimited" => None,
+            v => v.parse().ok(),
+        }
+    };
+
+    limits.lines()
+        .skip(1) // header
+        .filter_map(|line| {
+            // Columns are separated by runs of spaces; names contain single ones
+            let columns: Vec<&str> = line.split("  ")
+                .map(str::trim)
+                .filter(|column| !column.is_empty())
+                .collect();
+
+            let (name, soft, hard) = (columns.first()?, columns.get(1)?, columns.get(2)?);
+            Some(ResourceLimit {
+                name: name.to_string(),
+                soft: parse_value(soft),
+                hard: parse_value(hard),
+                units: columns.get(3).unwrap_or(&"").to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_environ_splits_on_first_equals() {
+        let env = parse_environ(b"PATH=/usr/bin:/bin\0OPTS=a=b\0\0");
+
+        assert_eq!(env.len(), 2);
+        assert_eq!(env["PATH"], "/usr/bin:/bin");
+        assert_eq!(env["OPTS"], "a=b");
+    }
+
+    #[test]
+    fn test_summarize_maps() {
+        let maps = "\
+55d0c0a00000-55d0c0a02000 r--p 00000000 08:01 131 /usr/bin/cat
+55d0c0a02000-55d0c0a06000 r-xp 00002000 08:01 131 /usr/bin/cat
+55d0c1000000-55d0c1021000 rw-p 00000000 00:00 0 [heap]
+7f0000000000-7f0000001000 rw-p 00000000 00:00 0
+7ffc00000000-7ffc00021000 rw-p 00000000 00:00 0                          [stack]
+7f0000100000-7f0000104000 r--p 00000000 08:01 200                        /opt/My App/lib.so
+7f0000200000-7f0000202000 r--p 00000000 08:01 201                        /tmp/cache (deleted)
+";
+        let summary = summarize_maps(maps);
+
+        assert_eq!(summary.regions, 7);
+        assert_eq!(summary.file_backed_size, 0x6000 + 0x4000 + 0x2000);
+        assert_eq!(summary.heap_size, 0x21000);
+        assert_eq!(summary.stack_size, 0x21000);
+        assert_eq!(summary.anonymous_size, 0x1000);
+        assert_eq!(summary.total_size, 0xc000 + 0x21000 * 2 + 0x1000);
+        assert_eq!(summary.files, vec![
+            MappedFile { path: "/usr/bin/cat".to_string(), size: 0x6000, regions: 2 },
+            MappedFile { path: "/opt/My App/lib.so".to_string(), size: 0x4000, regions: 1 },
+            MappedFile { path: "/tmp/cache (deleted)".to_string(), size: 0x2000, regions: 1 },
+        ]);
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let limits = "\
+Limit                     Soft Limit           Hard Limit           Units
+Max cpu time              unlimited            unlimited            seconds
+Max open files            1024                 1048576              files
+Max realtime timeout      unlimited            unlimited            us
+";
+        let limits = parse_limits(limits);
+
+        assert_eq!(limits.len(), 3);
+        assert_eq!(limits[0].name, "Max cpu time");
+        assert_eq!(limits[0].soft, None);
+        assert_eq!(limits[1], ResourceLimit {
+            name: "Max open files".to_string(),
+            soft: Some(1024),
+            hard: Some(1048576),
+            units: "files".to_string(),
+        });
+        assert_eq!(limits[2].units, "us");
+    }
+}
@@ -0,0 +1,732 @@
+// src/monitor/procfs.rs
+//! Linux backend that reads `/proc` and `/sys` directly.
+//!
+//! Every path is resolved below a configurable root, so the same code runs
+//! against the live system (`/`) and against the fixture trees checked in
+//! under `tests/fixtures`. It does not need sysinfo and is cheaper to
+//! sample, at the cost of only supporting Linux.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use super::disk::DiskSnapshot;
+use super::network::{InterfaceCounters, NetworkRateTracker};
+use super::process::{sort_processes, IoCounters, ProcessIoTracker, ProcessKey};
+use super::{
+    battery, cgroup, diskio, gpu, memory, pressure, process_detail, sensors, sockets, BatteryInfo, CgroupStats,
+    CpuSnapshot, DiskInfo, DiskIoStats, GpuBackend, GpuInfo, MemorySnapshot, MonitorBackend, NetworkSnapshot,
+    PressureSnapshot, ProcessDetail, ProcessInfo, ProcessManager, ProcessSort, SensorsSnapshot, SocketInfo,
+    SortOrder,
+};
+
+/// Clock ticks per second in `/proc/stat` and `/proc/<pid>/stat`.
+///
+/// This is USER_HZ, which the kernel fixes at 100 for userspace on every
+/// mainstream architecture regardless of its internal tick rate.
+const USER_HZ: u64 = 100;
+
+/// Wait between the two `/proc/stat` samples of a call without a baseline
+const CPU_SAMPLE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// A previous CPU sample older than this is not used as the usage baseline
+const BASELINE_MAX_AGE: Duration = Duration::from_secs(10);
+
+/// Busy and total ticks of one line of `/proc/stat`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuTimes {
+    pub busy: u64,
+    pub total: u64,
+}
+
+impl CpuTimes {
+    /// Share of `self - previous` spent busy, in percent
+    pub fn usage_since(&self, previous: &CpuTimes) -> f32 {
+        let total = self.total.saturating_sub(previous.total);
+        if total == 0 {
+            return 0.0;
+        }
+        self.busy.saturating_sub(previous.busy) as f32 / total as f32 * 100.0
+    }
+}
+
+/// Parsed `/proc/stat`: the aggregate line first, then one line per CPU
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct KernelStat {
+    pub cpus: Vec<CpuTimes>,
+    /// Boot time in seconds since the epoch
+    pub boot_time: u64,
+}
+
+pub fn parse_stat(content: &str) -> KernelStat {
+    let mut stat = KernelStat::default();
+
+    for line in content.lines() {
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some(name) if name.starts_with("cpu") => {
+                let ticks: Vec<u64> = fields.map(|f| f.parse().unwrap_or(0)).collect();
+                // user nice system idle iowait irq softirq steal; guest time
+                // is already included in user and nice
+                let total: u64 = ticks.iter().take(8).sum();
+                let idle = ticks.get(3).copied().unwrap_or(0) + ticks.get(4).copied().unwrap_or(0);
+                stat.cpus.push(CpuTimes {
+                    busy: total.saturating_sub(idle),
+                    total,
+                });
+            }
+            Some("btime") => stat.boot_time = fields.next().and_then(|f| f.parse().ok()).unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    stat
+}
+
+/// Model, clock and physical core count from `/proc/cpuinfo`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CpuInfo {
+    pub model: String,
+    pub frequency: f64,
+    pub physical_cores: usize,
+    pub logical_cores: usize,
+}
+
+pub fn parse_cpuinfo(content: &str) -> CpuInfo {
+    let mut info = CpuInfo::default();
+    let mut cores = BTreeSet::new();
+    let mut physical_id = String::new();
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "processor" => info.logical_cores += 1,
+            "model name" if info.model.is_empty() => info.model = value.to_string(),
+            "cpu MHz" if info.frequency == 0.0 => info.frequency = value.parse().unwrap_or(0.0),
+            "physical id" => physical_id = value.to_string(),
+            "core id" => {
+                cores.insert((physical_id.clone(), value.to_string()));
+            }
+            _ => {}
+        }
+    }
+
+    // Some architectures list neither sockets nor cores
+    info.physical_cores = if cores.is_empty() { info.logical_cores } else { cores.len() };
+    info
+}
+
+/// Parse `/proc/net/dev`
+pub fn parse_net_dev(content: &str) -> Vec<(String, InterfaceCounters)> {
+    content.lines()
+        .skip(2)
+        .filter_map(|line| {
+            let (name, values) = line.split_once(':')?;
+            let values: Vec<u64> = values.split_whitespace().map(|v| v.parse().unwrap_or(0)).collect();
+            if values.len() < 11 {
+                return None;
+            }
+            Some((name.trim().to_string(), InterfaceCounters {
+                rx_bytes: values[0],
+                rx_packets: values[1],
+                rx_errors: values[2],
+                tx_bytes: values[8],
+                tx_packets: values[9],
+                tx_errors: values[10],
+            }))
+        })
+        .collect()
+}
+
+/// A mounted block device from `/proc/mounts`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mount {
+    pub device: String,
+    pub mount_point: String,
+    pub fs_type: String,
+}
+
+/// Parse `/proc/mounts`, keeping filesystems backed by a device node
+pub fn parse_mounts(content: &str) -> Vec<Mount> {
+    // Spaces and other special characters are escaped as octal, e.g. `\040`
+    let unescape = |field: &str| {
+        let mut out = String::new();
+        let mut rest = field;
+        while let Some(index) = rest.find('\\') {
+            out.push_str(&rest[..index]);
+            match rest.get(index + 1..index + 4).and_then(|octal| u8::from_str_radix(octal, 8).ok()) {
+                Some(byte) => {
+                    out.push(byte as char);
+                    rest = &rest[index + 4..];
+                }
+                None => {
+                    out.push('\\');
+                    rest = &rest[index + 1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    };
+
+    content.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let mount_point = fields.next()?;
+            let fs_type = fields.next()?;
+            device.starts_with('/').then(|| Mount {
+                device: unescape(device),
+                mount_point: unescape(mount_point),
+                fs_type: fs_type.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Fields of `/proc/<pid>/stat` used for the process list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessStat {
+    pub pid: u32,
+    pub name: String,
+    pub state: char,
+    pub ppid: u32,
+    /// User plus system time in clock ticks
+    pub cpu_ticks: u64,
+    pub threads: usize,
+    /// Start time in clock ticks after boot
+    pub start_ticks: u64,
+}
+
+pub fn parse_process_stat(content: &str) -> Option<ProcessStat> {
+    // The name is in parentheses and may itself contain spaces and parentheses
+    let open = content.find('(')?;
+    let close = content.rfind(')')?;
+    let fields: Vec<&str> = content[close + 1..].split_whitespace().collect();
+    let number = |i: usize| fields.get(i).and_then(|f| f.parse::<u64>().ok());
+
+    Some(ProcessStat {
+        pid: content[..open].trim().parse().ok()?,
+        name: content[open + 1..close].to_string(),
+        state: fields.first()?.chars().next()?,
+        ppid: number(1)? as u32,
+        cpu_ticks: number(11)? + number(12)?,
+        threads: number(17)? as usize,
+        start_ticks: number(19)?,
+    })
+}
+
+/// Name sysinfo gives the state letter, so both backends report the same status
+fn state_name(state: char) -> String {
+    match state {
+        'R' => "Run",
+        'S' => "Sleep",
+        'I' => "Idle",
+        'D' => "UninterruptibleDiskSleep",
+        'Z' => "Zombie",
+        'T' => "Stop",
+        't' => "Tracing",
+        'X' | 'x' => "Dead",
+        'K' => "Wakekill",
+        'W' => "Waking",
+        'P' => "Parked",
+        other => return format!("Unknown({})", other as u32),
+    }
+    .to_string()
+}
+
+/// Value of a `Key:\tvalue` line of `/proc/<pid>/status`
+fn status_field<'a>(status: &'a str, key: &str) -> Option<&'a str> {
+    status.lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+        .map(str::trim)
+}
+
+/// Ids to names from `/etc/passwd` or `/etc/group`
+pub fn parse_id_names(content: &str) -> HashMap<u32, String> {
+    content.lines()
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let id = fields.nth(1)?.parse().ok()?;
+            Some((id, name.to_string()))
+        })
+        .collect()
+}
+
+/// Turns cumulative per-process CPU ticks into a usage percentage of one core
+#[derive(Debug, Default)]
+struct ProcessCpuTracker {
+    previous: HashMap<ProcessKey, u64>,
+    last_sample: Option<Instant>,
+}
+
+impl ProcessCpuTracker {
+    fn update(&mut self, ticks: HashMap<ProcessKey, u64>, now: Instant) -> HashMap<ProcessKey, f32> {
+        let elapsed = self.last_sample
+            .map(|last| now.saturating_duration_since(last).as_secs_f64())
+            .unwrap_or(0.0);
+
+        let usage = ticks.iter()
+            .map(|(key, &current)| {
+                let delta = self.previous.get(key).map_or(0, |&previous| current.saturating_sub(previous));
+                let percent = if elapsed > 0.0 {
+                    (delta as f64 / USER_HZ as f64 / elapsed * 100.0) as f32
+                } else {
+                    0.0
+                };
+                (*key, percent)
+            })
+            .collect();
+
+        self.previous = ticks;
+        self.last_sample = Some(now);
+        usage
+    }
+}
+
+/// Backend reading procfs and sysfs below a root directory
+pub struct ProcfsBackend {
+    root: PathBuf,
+    proc_root: PathBuf,
+    sysfs_root: PathBuf,
+    cpu: Mutex<Option<(KernelStat, Instant)>>,
+    process_cpu: Mutex<ProcessCpuTracker>,
+    process_io: Mutex<ProcessIoTracker>,
+    network: Mutex<NetworkRateTracker>,
+    disk_io: Mutex<diskio::DiskIoTracker>,
+    /// Detected on first use; `None` when GPUs are not monitored
+    gpu: Option<std::sync::OnceLock<Box<dyn GpuBackend>>>,
+}
+
+impl ProcfsBackend {
+    /// Monitor the live system
+    pub fn new() -> Self {
+        Self::with_root("/")
+    }
+
+    /// Read `<root>/proc`, `<root>/sys` and `<root>/etc` instead of the live system
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let sysfs_root = root.join("sys");
+        Self {
+            proc_root: root.join("proc"),
+            gpu: Some(std::sync::OnceLock::new()),
+            sysfs_root,
+            root,
+            cpu: Mutex::new(None),
+            process_cpu: Mutex::new(ProcessCpuTracker::default()),
+            process_io: Mutex::new(ProcessIoTracker::new()),
+            network: Mutex::new(NetworkRateTracker::new()),
+            disk_io: Mutex::new(diskio::DiskIoTracker::new()),
+        }
+    }
+
+    /// Report no GPUs, without probing for them
+    pub fn without_gpu(mut self) -> Self {
+        self.gpu = None;
+        self
+    }
+
+    /// Whether the root is the running system rather than a captured tree.
+    ///
+    /// Filesystem capacity and signals only make sense for the live system.
+    fn is_live(&self) -> bool {
+        self.root == Path::new("/")
+    }
+
+    fn read(&self, path: &str) -> Result<String> {
+        let path = self.root.join(path);
+        std::fs::read_to_string(&path).with_context(|| format!("failed to read {}", path.display()))
+    }
+
+    fn read_trimmed(&self, path: &str) -> Option<String> {
+        self.read(path).ok().map(|value| value.trim().to_string())
+    }
+
+    fn os_name(&self) -> String {
+        let release = self.read("etc/os-release").unwrap_or_default();
+        let value = |key: &str| release.lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .map(|value| value.trim_matches('"').to_string());
+
+        match value("NAME") {
+            Some(name) => format!("{} {}", name, value("VERSION_ID").unwrap_or_default()),
+            None => format!(
+                "{} {}",
+                self.read_trimmed("proc/sys/kernel/ostype").unwrap_or_else(|| "Unknown".to_string()),
+                self.read_trimmed("proc/sys/kernel/osrelease").unwrap_or_default()
+            ),
+        }
+    }
+
+    fn disks(&self) -> Result<Vec<DiskInfo>> {
+        let mounts = parse_mounts(&self.read("proc/mounts")?);
+
+        Ok(mounts.into_iter()
+            .map(|mount| {
+                let (total, available) = if self.is_live() {
+                    filesystem_capacity(Path::new(&mount.mount_point)).unwrap_or((0, 0))
+                } else {
+                    (0, 0)
+                };
+                let used = total.saturating_sub(available);
+                DiskInfo {
+                    is_removable: self.is_removable(&mount.device),
+                    name: mount.device,
+                    mount_point: mount.mount_point,
+                    fs_type: mount.fs_type,
+                    total,
+                    used,
+                    available,
+                    usage_percent: if total > 0 { used as f32 / total as f32 * 100.0 } else { 0.0 },
+                }
+            })
+            .collect())
+    }
+
+    /// Removable flag of the disk a device node belongs to
+    fn is_removable(&self, device: &str) -> bool {
+        // Device mapper nodes are symlinks to /dev/dm-N
+        let device = std::fs::canonicalize(self.root.join(device.trim_start_matches('/')))
+            .unwrap_or_else(|_| PathBuf::from(device));
+        let Some(name) = device.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        let block = self.sysfs_root.join("class/block");
+        let removable = |name: &str| std::fs::read_to_string(block.join(name).join("removable"))
+            .is_ok_and(|value| value.trim() == "1");
+
+        // Partitions carry no flag of their own: sda1 -> sda, nvme0n1p1 -> nvme0n1
+        if block.join(name).join("partition").exists() {
+            let disk = name.trim_end_matches(|c: char| c.is_ascii_digit());
+            let disk = match disk.strip_suffix('p') {
+                Some(stripped) if stripped.ends_with(|c: char| c.is_ascii_digit()) => stripped,
+                _ => disk,
+            };
+            return removable(disk);
+        }
+        removable(name)
+    }
+}
+
+impl Default for ProcfsBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Total and available bytes of the filesystem mounted at `path`
+#[cfg(unix)]
+fn filesystem_capacity(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a valid C string and `stat` is a properly sized out parameter
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    let block_size = stat.f_frsize as u64;
+    Some((stat.f_blocks as u64 * block_size, stat.f_bavail as u64 * block_size))
+}
+
+#[cfg(not(unix))]
+fn filesystem_capacity(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+#[async_trait]
+impl MonitorBackend for ProcfsBackend {
+    async fn cpu_snapshot(&self) -> Result<CpuSnapshot> {
+        let mut previous = self.cpu.lock().await;
+
+        // Usage is the share of busy ticks between two samples
+        if previous.as_ref().map_or(true, |(_, at)| at.elapsed() > BASELINE_MAX_AGE) {
+            *previous = Some((parse_stat(&self.read("proc/stat")?), Instant::now()));
+            tokio::time::sleep(CPU_SAMPLE_INTERVAL).await;
+        }
+        let current = parse_stat(&self.read("proc/stat")?);
+        let baseline = previous.replace((current.clone(), Instant::now())).map(|(stat, _)| stat).unwrap_or_default();
+
+        let usage = |i: usize| match (current.cpus.get(i), baseline.cpus.get(i)) {
+            (Some(now), Some(before)) => now.usage_since(before),
+            _ => 0.0,
+        };
+        let info = parse_cpuinfo(&self.read("proc/cpuinfo").unwrap_or_default());
+        let loadavg = self.read("proc/loadavg")?;
+        let loads: Vec<f64> = loadavg.split_whitespace().map(|value| value.parse().unwrap_or(0.0)).collect();
+        let load = |i: usize| loads.get(i).copied().unwrap_or(0.0);
+        let uptime = self.read("proc/uptime")?
+            .split_whitespace()
+            .next()
+            .and_then(|value| value.parse::<f64>().ok())
+            .unwrap_or(0.0);
+
+        Ok(CpuSnapshot {
+            model: if info.model.is_empty() { "Unknown".to_string() } else { info.model },
+            physical_cores: info.physical_cores.max(1),
+            logical_cores: current.cpus.len().saturating_sub(1),
+            global_usage: usage(0),
+            frequency: info.frequency,
+            per_core_usage: (1..current.cpus.len()).map(usage).collect(),
+            load_average: (load(0), load(1), load(2)),
+            uptime: uptime as u64,
+            os_name: self.os_name(),
+            hostname: self.read_trimmed("proc/sys/kernel/hostname").unwrap_or_else(|| "Unknown".to_string()),
+        })
+    }
+
+    async fn memory_snapshot(&self) -> Result<MemorySnapshot> {
+        Ok(memory::snapshot_from_detail(memory::read_memory_detail(&self.proc_root)?))
+    }
+
+    async fn disk_snapshot(&self) -> Result<DiskSnapshot> {
+        let disks = self.disks()?;
+        let total: u64 = disks.iter().map(|d| d.total).sum();
+        let available: u64 = disks.iter().map(|d| d.available).sum();
+        let used = total.saturating_sub(available);
+
+        Ok(DiskSnapshot {
+            total,
+            used,
+            available,
+            usage_percent: if total > 0 { used as f32 / total as f32 * 100.0 } else { 0.0 },
+        })
+    }
+
+    async fn disk_list(&self) -> Result<Vec<DiskInfo>> {
+        self.disks()
+    }
+
+    async fn disk_io(&self) -> Result<Vec<DiskIoStats>> {
+        let counters = diskio::parse_diskstats(&self.read("proc/diskstats")?);
+        let mut stats = self.disk_io.lock().await.update(counters, Instant::now());
+        diskio::map_mounts(&mut stats, &self.disks()?, &diskio::read_dm_names(&self.sysfs_root));
+        Ok(stats)
+    }
+
+    async fn network_snapshot(&self) -> Result<NetworkSnapshot> {
+        let counters = parse_net_dev(&self.read("proc/net/dev")?);
+        Ok(self.network.lock().await.update(counters, Instant::now()))
+    }
+
+    async fn sensors_snapshot(&self) -> Result<SensorsSnapshot> {
+        let hwmon = sensors::read_hwmon(&self.sysfs_root);
+        Ok(SensorsSnapshot {
+            temperatures: hwmon.temperatures,
+            fans: hwmon.fans,
+        })
+    }
+
+    async fn pressure(&self) -> Result<PressureSnapshot> {
+        pressure::read_system_pressure(&self.proc_root)
+    }
+
+    async fn batteries(&self) -> Result<Vec<BatteryInfo>> {
+        Ok(battery::read_power_supply(&self.sysfs_root))
+    }
+
+    async fn gpu_list(&self) -> Result<Vec<GpuInfo>> {
+        match &self.gpu {
+            Some(gpu) => gpu.get_or_init(|| gpu::detect(&self.sysfs_root)).gpus().await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn sockets(&self) -> Result<Vec<SocketInfo>> {
+        sockets::list_sockets(&self.proc_root)
+    }
+
+    async fn process_list(&self, filter: Option<&str>, sort: ProcessSort, order: SortOrder, limit: usize) -> Result<Vec<ProcessInfo>> {
+        let boot_time = parse_stat(&self.read("proc/stat")?).boot_time;
+        let total_memory = memory::read_memory_detail(&self.proc_root)?.total;
+        let users = parse_id_names(&self.read("etc/passwd").unwrap_or_default());
+        let groups = parse_id_names(&self.read("etc/group").unwrap_or_default());
+        let name_of = |names: &HashMap<u32, String>, id: Option<u32>| match id {
+            Some(id) => names.get(&id).cloned().unwrap_or_else(|| id.to_string()),
+            None => "unknown".to_string(),
+        };
+
+        let mut found = Vec::new();
+        for entry in std::fs::read_dir(&self.proc_root)?.filter_map(|e| e.ok()) {
+            let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+                continue;
+            };
+            // Processes may exit while the directory is being walked
+            let dir = entry.path();
+            let Some(stat) = std::fs::read_to_string(dir.join("stat")).ok().and_then(|s| parse_process_stat(&s)) else {
+                continue;
+            };
+            let status = std::fs::read_to_string(dir.join("status")).unwrap_or_default();
+            let io = std::fs::read_to_string(dir.join("io")).unwrap_or_default();
+            let io_value = |key: &str| status_field(&io, key).and_then(|v| v.parse::<u64>().ok()).unwrap_or(0);
+            found.push((pid, stat, status, IoCounters {
+                read_bytes: io_value("read_bytes"),
+                write_bytes: io_value("write_bytes"),
+            }));
+        }
+
+        let now = Instant::now();
+        let cpu_usage = self.process_cpu.lock().await
+            .update(found.iter().map(|(pid, stat, _, _)| ((*pid, stat.start_ticks), stat.cpu_ticks)).collect(), now);
+        let io_rates = self.process_io.lock().await
+            .update(found.iter().map(|(pid, stat, _, io)| ((*pid, stat.start_ticks), *io)).collect(), now);
+
+        let filter = filter.map(|f| f.to_lowercase());
+        let mut processes = Vec::new();
+
+        for (pid, stat, status, io) in found {
+            // Real, effective, saved and filesystem ids
+            let ids = |key: &str| -> Vec<u32> {
+                status_field(&status, key)
+                    .map(|value| value.split_whitespace().filter_map(|id| id.parse().ok()).collect())
+                    .unwrap_or_default()
+            };
+            let (uids, gids) = (ids("Uid"), ids("Gid"));
+            let user = name_of(&users, uids.first().copied());
+
+            if let Some(filter_str) = &filter {
+                if !stat.name.to_lowercase().contains(filter_str) && !user.to_lowercase().contains(filter_str) {
+                    continue;
+                }
+            }
+
+            let rss = status_field(&status, "VmRSS")
+                .and_then(|value| value.split_whitespace().next()?.parse::<u64>().ok())
+                .unwrap_or(0) * 1024;
+            let key = (pid, stat.start_ticks);
+            let io_rate = io_rates.get(&key).copied().unwrap_or_default();
+            let cgroup = cgroup::read_process_cgroup(&self.proc_root, pid);
+
+            processes.push(ProcessInfo {
+                pid,
+                ppid: Some(stat.ppid).filter(|&ppid| ppid != 0),
+                name: stat.name.clone(),
+                user: user.clone(),
+                uid: uids.first().copied(),
+                effective_user: name_of(&users, uids.get(1).copied()),
+                euid: uids.get(1).copied(),
+                effective_group: name_of(&groups, gids.get(1).copied()),
+                egid: gids.get(1).copied(),
+                cpu_usage: cpu_usage.get(&key).copied().unwrap_or(0.0),
+                memory_usage: if total_memory > 0 { rss as f32 / total_memory as f32 * 100.0 } else { 0.0 },
+                disk_read: io.read_bytes,
+                disk_write: io.write_bytes,
+                disk_read_rate: io_rate.read_bytes,
+                disk_write_rate: io_rate.write_bytes,
+                threads: stat.threads,
+                status: state_name(stat.state),
+                start_time: boot_time + stat.start_ticks / USER_HZ,
+                exe_path: std::fs::read_link(self.proc_root.join(pid.to_string()).join("exe"))
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_else(|_| "N/A".to_string()),
+                container: cgroup.as_deref().and_then(cgroup::container_from_path),
+                cgroup,
+            });
+        }
+
+        sort_processes(&mut processes, sort, order);
+        processes.truncate(limit);
+
+        Ok(processes)
+    }
+
+    async fn process_detail(&self, pid: u32) -> Result<ProcessDetail> {
+        process_detail::read_process_detail(&self.proc_root, pid)
+    }
+
+    async fn cgroups(&self) -> Result<Vec<CgroupStats>> {
+        cgroup::list_cgroups(&self.proc_root, &self.sysfs_root.join("fs/cgroup"))
+    }
+}
+
+impl ProcfsBackend {
+    /// Deliver `signal` to `pid` after checking that it exists below the root
+    fn signal(&self, pid: u32, signal: &str) -> Result<()> {
+        if !self.proc_root.join(pid.to_string()).exists() {
+            anyhow::bail!("Process {} not found", pid);
+        }
+        if !self.is_live() {
+            anyhow::bail!("signals can only be sent on the live system, not below {}", self.root.display());
+        }
+
+        #[cfg(unix)]
+        {
+            let number = match signal.to_uppercase().trim_start_matches("SIG") {
+                "TERM" => libc::SIGTERM,
+                "KILL" => libc::SIGKILL,
+                "INT" => libc::SIGINT,
+                "STOP" => libc::SIGSTOP,
+                "CONT" => libc::SIGCONT,
+                other => anyhow::bail!("unsupported signal {}", other),
+            };
+            // SAFETY: kill(2) has no memory safety requirements
+            if unsafe { libc::kill(pid as libc::pid_t, number) } != 0 {
+                return Err(std::io::Error::last_os_error())
+                    .with_context(|| format!("Failed to send {} to process {}", signal, pid));
+            }
+            Ok(())
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = signal;
+            anyhow::bail!("signals are not supported on this platform")
+        }
+    }
+}
+
+#[async_trait]
+impl ProcessManager for ProcfsBackend {
+    async fn kill_process(&self, pid: u32, signal: &str) -> Result<()> {
+        self.signal(pid, signal)
+    }
+
+    async fn suspend_process(&self, pid: u32) -> Result<()> {
+        self.signal(pid, "SIGSTOP")
+    }
+
+    async fn resume_process(&self, pid: u32) -> Result<()> {
+        self.signal(pid, "SIGCONT")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_process_stat_with_parentheses_in_name() {
+        let stat = parse_process_stat(
+            "812 (tmux: server (1)) S 1 812 812 0 -1 4194560 300 0 0 0 150 50 0 0 20 0 3 0 4200 10000 200 0",
+        ).unwrap();
+        assert_eq!(stat.pid, 812);
+        assert_eq!(stat.name, "tmux: server (1)");
+        assert_eq!(stat.state, 'S');
+        assert_eq!(stat.ppid, 1);
+        assert_eq!(stat.cpu_ticks, 200);
+        assert_eq!(stat.threads, 3);
+        assert_eq!(stat.start_ticks, 4200);
+    }
+
+    #[test]
+    fn test_cpu_usage_and_process_cpu_tracker() {
+        let before = parse_stat("cpu  100 0 100 700 100 0 0 0 0 0\nbtime 1700000000\n");
+        let after = parse_stat("cpu  150 0 150 750 150 0 0 0 0 0\n");
+        assert_eq!(before.boot_time, 1700000000);
+        assert_eq!(after.cpus[0].usage_since(&before.cpus[0]), 50.0);
+
+        let mut tracker = ProcessCpuTracker::default();
+        let start = Instant::now();
+        tracker.update(HashMap::from([((1, 0), 1000)]), start);
+        let usage = tracker.update(HashMap::from([((1, 0), 1050), ((2, 0), 10)]), start + Duration::from_secs(1));
+        assert_eq!(usage[&(1, 0)], 50.0);
+        assert_eq!(usage[&(2, 0)], 0.0);
+    }
+}
@@ -0,0 +1,311 @@
+// src/monitor/remote.rs
+//! Monitoring another machine through its remote agent (optional feature).
+//!
+//! [`RemoteBackend`] fetches the agent's `/snapshot` once per refresh and
+//! serves every [`MonitorBackend`] call from that copy, so the collector and
+//! the TUI work on a remote host exactly as they do locally. Process lists,
+//! details and cgroups are asked for separately. When the agent cannot be
+//! reached, calls fail fast and the next request waits for an exponentially
+//! growing retry delay; [`RemoteBackend::status`] reports it.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::{
+    BatteryInfo, CgroupStats, CpuSnapshot, DiskInfo, DiskIoStats, DiskSnapshot, GpuInfo, MemorySnapshot,
+    MonitorBackend, NetworkSnapshot, PressureSnapshot, ProcessDetail, ProcessInfo, ProcessSort,
+    SensorsSnapshot, SocketInfo, SortOrder, SystemSnapshot,
+};
+
+/// Wait before the first retry; doubled after every further failure
+const INITIAL_RETRY_DELAY: Duration = Duration::from_secs(1);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// State of the connection to the agent
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionStatus {
+    /// No request has completed yet
+    Connecting,
+    Connected,
+    /// The last `failures` requests failed; the next one is made in `retry_in`
+    Lost {
+        error: String,
+        failures: u32,
+        retry_in: Duration,
+    },
+}
+
+#[derive(Default)]
+struct Connection {
+    snapshot: Option<Arc<SystemSnapshot>>,
+    fetched_at: Option<Instant>,
+    failures: u32,
+    error: Option<String>,
+    retry_at: Option<Instant>,
+}
+
+/// [`MonitorBackend`] reading the snapshots published by a remote agent
+pub struct RemoteBackend {
+    url: String,
+    token: Option<String>,
+    client: reqwest::Client,
+    cache_for: Duration,
+    connection: Mutex<Connection>,
+    /// Held while a snapshot request is in flight, so concurrent collector
+    /// tasks share one request instead of each making their own
+    fetching: tokio::sync::Mutex<()>,
+    processes_partial: AtomicBool,
+}
+
+impl RemoteBackend {
+    /// Connect to the agent at `url`, e.g. `http://box:8080` or just `box:8080`
+    pub fn new(url: &str, token: Option<String>) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            url: normalize_url(url),
+            token,
+            client,
+            cache_for: Duration::from_millis(250),
+            connection: Mutex::new(Connection::default()),
+            fetching: tokio::sync::Mutex::new(()),
+            processes_partial: AtomicBool::new(false),
+        })
+    }
+
+    /// How long a fetched snapshot is reused before asking the agent again
+    pub fn cache_for(mut self, duration: Duration) -> Self {
+        self.cache_for = duration;
+        self
+    }
+
+    /// Base URL of the agent
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Host and port of the agent, for display
+    pub fn host(&self) -> &str {
+        self.url.split_once("://").map_or(&self.url, |(_, host)| host)
+    }
+
+    pub fn status(&self) -> ConnectionStatus {
+        let connection = self.connection.lock().unwrap();
+        match (&connection.error, connection.retry_at) {
+            (Some(error), Some(retry_at)) => ConnectionStatus::Lost {
+                error: error.clone(),
+                failures: connection.failures,
+                retry_in: retry_at.saturating_duration_since(Instant::now()),
+            },
+            _ if connection.fetched_at.is_some() => ConnectionStatus::Connected,
+            _ => ConnectionStatus::Connecting,
+        }
+    }
+
+    /// Whether the last process list may miss processes, because the agent
+    /// predates sorting over all of them
+    pub fn processes_partial(&self) -> bool {
+        self.processes_partial.load(Ordering::Relaxed)
+    }
+
+    fn request(&self, path: &str) -> reqwest::RequestBuilder {
+        let request = self.client.get(format!("{}{}", self.url, path));
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.send(self.request(path), path).await
+    }
+
+    async fn send<T: serde::de::DeserializeOwned>(&self, request: reqwest::RequestBuilder, path: &str) -> Result<T> {
+        let response = request.send().await
+            .with_context(|| format!("cannot reach {}", self.host()))?;
+        match response.status() {
+            StatusCode::UNAUTHORIZED => anyhow::bail!("{} rejected the token", self.host()),
+            status if !status.is_success() => anyhow::bail!("{} answered {} for {}", self.host(), status, path),
+            _ => Ok(response.json().await?),
+        }
+    }
+
+    /// The agent's latest snapshot, fetched again once the cached copy is older than `cache_for`
+    async fn snapshot(&self) -> Result<Arc<SystemSnapshot>> {
+        let _fetching = self.fetching.lock().await;
+
+        {
+            let connection = self.connection.lock().unwrap();
+            if let (Some(error), Some(retry_at)) = (&connection.error, connection.retry_at) {
+                if Instant::now() < retry_at {
+                    anyhow::bail!("connection lost: {}", error);
+                }
+            } else if let (Some(snapshot), Some(fetched_at)) = (&connection.snapshot, connection.fetched_at) {
+                if fetched_at.elapsed() < self.cache_for {
+                    return Ok(snapshot.clone());
+                }
+            }
+        }
+
+        let result = self.get::<SystemSnapshot>("/snapshot").await;
+
+        let mut connection = self.connection.lock().unwrap();
+        match result {
+            Ok(snapshot) => {
+                let snapshot = Arc::new(snapshot);
+                *connection = Connection {
+                    snapshot: Some(snapshot.clone()),
+                    fetched_at: Some(Instant::now()),
+                    ..Connection::default()
+                };
+                Ok(snapshot)
+            }
+            Err(e) => {
+                connection.failures += 1;
+                connection.error = Some(format!("{:#}", e));
+                connection.retry_at = Some(Instant::now() + retry_delay(connection.failures));
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Query of the agent's `/processes`
+#[derive(serde::Serialize)]
+struct ProcessQuery<'a> {
+    sort: ProcessSort,
+    order: SortOrder,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limit: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    filter: Option<&'a str>,
+}
+
+/// Answer of the agent's `/processes`
+#[derive(serde::Deserialize)]
+struct ProcessPage {
+    processes: Vec<ProcessInfo>,
+    /// Processes matching before the limit was applied
+    total: Option<usize>,
+}
+
+/// Prefix `http://` when no scheme is given and drop trailing slashes
+fn normalize_url(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("http://{}", url)
+    }
+}
+
+/// Delay before retrying after `failures` consecutive failed requests
+fn retry_delay(failures: u32) -> Duration {
+    INITIAL_RETRY_DELAY
+        .saturating_mul(1 << failures.saturating_sub(1).min(16))
+        .min(MAX_RETRY_DELAY)
+}
+
+fn received<T: Clone>(value: &Option<T>, what: &str) -> Result<T> {
+    value.clone().with_context(|| format!("agent has not collected {} yet", what))
+}
+
+#[async_trait]
+impl MonitorBackend for RemoteBackend {
+    async fn cpu_snapshot(&self) -> Result<CpuSnapshot> {
+        received(&self.snapshot().await?.cpu, "CPU data")
+    }
+
+    async fn memory_snapshot(&self) -> Result<MemorySnapshot> {
+        received(&self.snapshot().await?.memory, "memory data")
+    }
+
+    async fn disk_snapshot(&self) -> Result<DiskSnapshot> {
+        received(&self.snapshot().await?.disk, "disk data")
+    }
+
+    async fn disk_list(&self) -> Result<Vec<DiskInfo>> {
+        Ok(self.snapshot().await?.disk_list.clone())
+    }
+
+    async fn disk_io(&self) -> Result<Vec<DiskIoStats>> {
+        Ok(self.snapshot().await?.disk_io.clone())
+    }
+
+    async fn network_snapshot(&self) -> Result<NetworkSnapshot> {
+        received(&self.snapshot().await?.network, "network data")
+    }
+
+    async fn sensors_snapshot(&self) -> Result<SensorsSnapshot> {
+        received(&self.snapshot().await?.sensors, "sensor data")
+    }
+
+    async fn pressure(&self) -> Result<PressureSnapshot> {
+        received(&self.snapshot().await?.pressure, "pressure stall information")
+    }
+
+    async fn batteries(&self) -> Result<Vec<BatteryInfo>> {
+        Ok(self.snapshot().await?.batteries.clone())
+    }
+
+    async fn gpu_list(&self) -> Result<Vec<GpuInfo>> {
+        Ok(self.snapshot().await?.gpus.clone())
+    }
+
+    async fn sockets(&self) -> Result<Vec<SocketInfo>> {
+        Ok(self.snapshot().await?.sockets.clone())
+    }
+
+    async fn process_list(&self, filter: Option<&str>, sort: ProcessSort, order: SortOrder, limit: usize) -> Result<Vec<ProcessInfo>> {
+        // Snapshots only carry the agent's busiest processes, so the agent
+        // sorts the full list; the snapshot keeps the retry delay in effect
+        self.snapshot().await?;
+        let query = ProcessQuery { sort, order, limit: (limit != usize::MAX).then_some(limit), filter };
+        let page: ProcessPage = self.send(self.request("/processes").query(&query), "/processes").await?;
+        // Older agents sort their published snapshot and do not say how many there are
+        self.processes_partial.store(page.total.is_none(), Ordering::Relaxed);
+        Ok(page.processes)
+    }
+
+    async fn process_detail(&self, pid: u32) -> Result<ProcessDetail> {
+        self.get(&format!("/processes/{}", pid)).await
+    }
+
+    async fn cgroups(&self) -> Result<Vec<CgroupStats>> {
+        self.get("/cgroups").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(normalize_url("box:8080"), "http://box:8080");
+        assert_eq!(normalize_url("https://box.example/agent/"), "https://box.example/agent");
+
+        let backend = RemoteBackend::new("http://box:8080/", None).unwrap();
+        assert_eq!(backend.url(), "http://box:8080");
+        assert_eq!(backend.host(), "box:8080");
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_up_to_the_limit() {
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(2), Duration::from_secs(2));
+        assert_eq!(retry_delay(4), Duration::from_secs(8));
+        assert_eq!(retry_delay(6), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+}
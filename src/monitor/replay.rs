@@ -0,0 +1,412 @@
+// src/monitor/replay.rs
+//! Recording snapshots to disk and playing them back.
+//!
+//! A recording is newline-delimited JSON: a [`RecordingHeader`] line followed
+//! by one [`Frame`] per sample, each holding a full [`SystemSnapshot`].
+//! [`ReplayBackend`] serves a recording through [`MonitorBackend`], so the
+//! collector and the TUI work on it exactly as they do on a live system.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::process::sort_processes;
+use super::{
+    BatteryInfo, CgroupStats, CpuSnapshot, DiskInfo, DiskIoStats, DiskSnapshot, GpuInfo, MemorySnapshot,
+    MonitorBackend, NetworkSnapshot, PressureSnapshot, ProcessDetail, ProcessInfo, ProcessSort,
+    SensorsSnapshot, SocketInfo, SortOrder, SystemSnapshot,
+};
+
+/// Value of [`RecordingHeader::format`]
+pub const RECORDING_FORMAT: &str = "systrix-recording";
+
+/// Bumped whenever a change to the snapshot types breaks reading older files
+pub const RECORDING_VERSION: u32 = 1;
+
+/// Playback speeds reachable with [`ReplayBackend::faster`] and [`ReplayBackend::slower`]
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 64.0;
+
+/// First line of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub format: String,
+    pub version: u32,
+    pub hostname: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub interval_ms: u64,
+}
+
+impl RecordingHeader {
+    pub fn new(hostname: Option<String>, interval: Duration) -> Self {
+        Self {
+            format: RECORDING_FORMAT.to_string(),
+            version: RECORDING_VERSION,
+            hostname,
+            started_at: Utc::now(),
+            interval_ms: interval.as_millis() as u64,
+        }
+    }
+}
+
+/// One sample of a recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Frame {
+    pub timestamp: DateTime<Utc>,
+    pub snapshot: SystemSnapshot,
+}
+
+/// Appends frames to a recording, flushing after each so an interrupted
+/// recording stays readable
+pub struct RecordingWriter<W: Write> {
+    writer: W,
+    frames: usize,
+}
+
+impl<W: Write> RecordingWriter<W> {
+    pub fn new(mut writer: W, header: &RecordingHeader) -> Result<Self> {
+        serde_json::to_writer(&mut writer, header)?;
+        writeln!(writer)?;
+        writer.flush()?;
+        Ok(Self { writer, frames: 0 })
+    }
+
+    pub fn write_frame(&mut self, frame: &Frame) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, frame)?;
+        writeln!(self.writer)?;
+        self.writer.flush()?;
+        self.frames += 1;
+        Ok(())
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+}
+
+/// A recording loaded into memory
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub header: RecordingHeader,
+    pub frames: Vec<Frame>,
+}
+
+impl Recording {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("failed to open recording {}", path.display()))?;
+        Self::read(std::io::BufReader::new(file))
+            .with_context(|| format!("failed to read recording {}", path.display()))
+    }
+
+    pub fn read(reader: impl BufRead) -> Result<Self> {
+        let mut lines = reader.lines();
+
+        let header: RecordingHeader = serde_json::from_str(&lines.next().context("recording is empty")??)
+            .context("invalid recording header")?;
+        if header.format != RECORDING_FORMAT {
+            anyhow::bail!("not a systrix recording (format \"{}\")", header.format);
+        }
+        if header.version > RECORDING_VERSION {
+            anyhow::bail!(
+                "recording version {} is newer than the supported version {}",
+                header.version,
+                RECORDING_VERSION
+            );
+        }
+
+        let lines: Vec<String> = lines.collect::<std::io::Result<_>>()?;
+        let mut frames = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(frame) => frames.push(frame),
+                // The last line may be cut short when recording was killed
+                Err(_) if i + 1 == lines.len() => break,
+                Err(e) => return Err(e).with_context(|| format!("invalid frame on line {}", i + 2)),
+            }
+        }
+        if frames.is_empty() {
+            anyhow::bail!("recording has no frames");
+        }
+
+        Ok(Self { header, frames })
+    }
+
+    /// Time between the first and the last frame
+    pub fn duration(&self) -> Duration {
+        let first = self.frames[0].timestamp;
+        let last = self.frames[self.frames.len() - 1].timestamp;
+        (last - first).to_std().unwrap_or_default()
+    }
+}
+
+/// Where playback is and how it advances
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackStatus {
+    pub timestamp: DateTime<Utc>,
+    /// Position relative to the first frame
+    pub position: Duration,
+    pub duration: Duration,
+    pub speed: f64,
+    pub paused: bool,
+    pub frame: usize,
+    pub frames: usize,
+}
+
+#[derive(Debug)]
+struct Playback {
+    /// Position at `anchor`
+    position: Duration,
+    anchor: Instant,
+    speed: f64,
+    paused: bool,
+}
+
+/// Serves a recording as if it were a live system.
+///
+/// Playback starts at the first frame and advances in real time, scaled by
+/// the playback speed, until it stops on the last frame.
+pub struct ReplayBackend {
+    recording: Recording,
+    playback: Mutex<Playback>,
+}
+
+impl ReplayBackend {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            playback: Mutex::new(Playback {
+                position: Duration::ZERO,
+                anchor: Instant::now(),
+                speed: 1.0,
+                paused: false,
+            }),
+        }
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        Ok(Self::new(Recording::open(path)?))
+    }
+
+    pub fn header(&self) -> &RecordingHeader {
+        &self.recording.header
+    }
+
+    fn position(&self, playback: &Playback) -> Duration {
+        let position = if playback.paused {
+            playback.position
+        } else {
+            playback.position + playback.anchor.elapsed().mul_f64(playback.speed)
+        };
+        position.min(self.recording.duration())
+    }
+
+    /// Apply a change to the playback from its current position
+    fn update(&self, change: impl FnOnce(&mut Playback, Duration)) {
+        let mut playback = self.playback.lock().unwrap();
+        let position = self.position(&playback);
+        playback.position = position;
+        playback.anchor = Instant::now();
+        change(&mut playback, position);
+    }
+
+    pub fn toggle_pause(&self) {
+        self.update(|playback, _| playback.paused = !playback.paused);
+    }
+
+    pub fn set_speed(&self, speed: f64) {
+        self.update(|playback, _| playback.speed = speed.clamp(MIN_SPEED, MAX_SPEED));
+    }
+
+    pub fn faster(&self) {
+        self.set_speed(self.status().speed * 2.0);
+    }
+
+    pub fn slower(&self) {
+        self.set_speed(self.status().speed / 2.0);
+    }
+
+    /// Jump forward (positive) or back (negative) by `seconds`
+    pub fn seek(&self, seconds: i64) {
+        let duration = self.recording.duration();
+        self.update(|playback, position| {
+            let delta = Duration::from_secs(seconds.unsigned_abs());
+            playback.position = if seconds < 0 {
+                position.saturating_sub(delta)
+            } else {
+                (position + delta).min(duration)
+            };
+        });
+    }
+
+    fn frame_index(&self, position: Duration) -> usize {
+        let start = self.recording.frames[0].timestamp;
+        let position = chrono::Duration::from_std(position).unwrap_or_default();
+        self.recording.frames
+            .partition_point(|frame| frame.timestamp - start <= position)
+            .saturating_sub(1)
+    }
+
+    /// The frame at the current playback position
+    pub fn current(&self) -> &Frame {
+        let position = self.position(&self.playback.lock().unwrap());
+        &self.recording.frames[self.frame_index(position)]
+    }
+
+    pub fn status(&self) -> PlaybackStatus {
+        let playback = self.playback.lock().unwrap();
+        let position = self.position(&playback);
+        let frame = self.frame_index(position);
+        PlaybackStatus {
+            timestamp: self.recording.frames[frame].timestamp,
+            position,
+            duration: self.recording.duration(),
+            speed: playback.speed,
+            paused: playback.paused,
+            frame,
+            frames: self.recording.frames.len(),
+        }
+    }
+
+    fn snapshot(&self) -> &SystemSnapshot {
+        &self.current().snapshot
+    }
+}
+
+fn recorded<T: Clone>(value: &Option<T>, what: &str) -> Result<T> {
+    value.clone().with_context(|| format!("{} not recorded at this point", what))
+}
+
+#[async_trait]
+impl MonitorBackend for ReplayBackend {
+    async fn cpu_snapshot(&self) -> Result<CpuSnapshot> {
+        recorded(&self.snapshot().cpu, "CPU data")
+    }
+
+    async fn memory_snapshot(&self) -> Result<MemorySnapshot> {
+        recorded(&self.snapshot().memory, "memory data")
+    }
+
+    async fn disk_snapshot(&self) -> Result<DiskSnapshot> {
+        recorded(&self.snapshot().disk, "disk data")
+    }
+
+    async fn disk_list(&self) -> Result<Vec<DiskInfo>> {
+        Ok(self.snapshot().disk_list.clone())
+    }
+
+    async fn disk_io(&self) -> Result<Vec<DiskIoStats>> {
+        Ok(self.snapshot().disk_io.clone())
+    }
+
+    async fn network_snapshot(&self) -> Result<NetworkSnapshot> {
+        recorded(&self.snapshot().network, "network data")
+    }
+
+    async fn sensors_snapshot(&self) -> Result<SensorsSnapshot> {
+        recorded(&self.snapshot().sensors, "sensor data")
+    }
+
+    async fn pressure(&self) -> Result<PressureSnapshot> {
+        recorded(&self.snapshot().pressure, "pressure stall information")
+    }
+
+    async fn batteries(&self) -> Result<Vec<BatteryInfo>> {
+        Ok(self.snapshot().batteries.clone())
+    }
+
+    async fn gpu_list(&self) -> Result<Vec<GpuInfo>> {
+        Ok(self.snapshot().gpus.clone())
+    }
+
+    async fn sockets(&self) -> Result<Vec<SocketInfo>> {
+        Ok(self.snapshot().sockets.clone())
+    }
+
+    async fn process_list(&self, filter: Option<&str>, sort: ProcessSort, order: SortOrder, limit: usize) -> Result<Vec<ProcessInfo>> {
+        let filter = filter.map(|f| f.to_lowercase());
+        let mut processes: Vec<ProcessInfo> = self.snapshot().processes.iter()
+            .filter(|p| match &filter {
+                Some(f) => p.name.to_lowercase().contains(f) || p.user.to_lowercase().contains(f),
+                None => true,
+            })
+            .cloned()
+            .collect();
+        sort_processes(&mut processes, sort, order);
+        processes.truncate(limit);
+        Ok(processes)
+    }
+
+    async fn process_detail(&self, pid: u32) -> Result<ProcessDetail> {
+        anyhow::bail!("process details of PID {} are not part of the recording", pid)
+    }
+
+    async fn cgroups(&self) -> Result<Vec<CgroupStats>> {
+        anyhow::bail!("cgroups are not part of the recording")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recording(seconds: &[i64]) -> Recording {
+        let start = Utc::now();
+        Recording {
+            header: RecordingHeader::new(None, Duration::from_secs(1)),
+            frames: seconds.iter()
+                .map(|&s| Frame {
+                    timestamp: start + chrono::Duration::seconds(s),
+                    snapshot: SystemSnapshot::default(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_read_rejects_newer_versions_and_tolerates_truncation() {
+        let mut header = RecordingHeader::new(Some("host".to_string()), Duration::from_secs(1));
+        let frame = serde_json::to_string(&recording(&[0]).frames[0]).unwrap();
+
+        let content = format!("{}\n{}\n{}", serde_json::to_string(&header).unwrap(), frame, &frame[..10]);
+        let loaded = Recording::read(content.as_bytes()).unwrap();
+        assert_eq!(loaded.frames.len(), 1);
+        assert_eq!(loaded.header.hostname.as_deref(), Some("host"));
+
+        header.version = RECORDING_VERSION + 1;
+        let content = format!("{}\n{}\n", serde_json::to_string(&header).unwrap(), frame);
+        assert!(Recording::read(content.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_playback_seek_pause_and_speed() {
+        let replay = ReplayBackend::new(recording(&[0, 1, 2, 5, 10]));
+        replay.toggle_pause();
+        assert_eq!(replay.status().frame, 0);
+
+        replay.seek(3);
+        assert_eq!(replay.status().frame, 2);
+        replay.seek(100);
+        let status = replay.status();
+        assert_eq!((status.frame, status.position), (4, Duration::from_secs(10)));
+        replay.seek(-6);
+        assert_eq!(replay.status().frame, 2);
+
+        replay.faster();
+        replay.faster();
+        assert_eq!(replay.status().speed, 4.0);
+        for _ in 0..10 {
+            replay.slower();
+        }
+        assert_eq!(replay.status().speed, MIN_SPEED);
+        assert!(replay.status().paused);
+    }
+}
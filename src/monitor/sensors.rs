@@ -0,0 +1,227 @@
+// src/monitor/sensors.rs
+//! Temperature and fan sensors.
+//!
+//! Temperatures and fan speeds are read from the hwmon class in sysfs.
+//! sysinfo's `Components` supply temperatures where there is no hwmon, as on
+//! other platforms. The sysfs root is a parameter so fixture directories can
+//! replace `/sys` in tests.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use sysinfo::Components;
+use tokio::sync::Mutex;
+
+/// Margin below the critical temperature at which a sensor counts as hot
+const HOT_MARGIN_CELSIUS: f32 = 10.0;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SensorsSnapshot {
+    pub temperatures: Vec<TemperatureSensor>,
+    pub fans: Vec<FanSensor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemperatureSensor {
+    pub label: String,
+    /// Current temperature in °C
+    pub current: f32,
+    /// Highest temperature seen, in °C
+    pub max: Option<f32>,
+    /// Temperature at which the hardware throttles or shuts down, in °C
+    pub critical: Option<f32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SensorStatus {
+    Normal,
+    /// Within [`HOT_MARGIN_CELSIUS`] of the critical temperature
+    Hot,
+    /// At or above the critical temperature; the hardware is likely throttling
+    Critical,
+}
+
+impl TemperatureSensor {
+    pub fn status(&self) -> SensorStatus {
+        match self.critical {
+            Some(critical) if self.current >= critical => SensorStatus::Critical,
+            Some(critical) if self.current >= critical - HOT_MARGIN_CELSIUS => SensorStatus::Hot,
+            _ => SensorStatus::Normal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FanSensor {
+    pub label: String,
+    pub rpm: u32,
+    pub min_rpm: Option<u32>,
+    pub max_rpm: Option<u32>,
+}
+
+impl FanSensor {
+    /// A fan that reports a minimum speed but has stopped
+    pub fn is_stalled(&self) -> bool {
+        self.rpm == 0 && self.min_rpm.is_some_and(|min| min > 0)
+    }
+}
+
+impl SensorsSnapshot {
+    /// Sensors that are hot or critical, hottest relative to their limit first
+    pub fn warnings(&self) -> Vec<&TemperatureSensor> {
+        let mut warnings: Vec<_> = self.temperatures.iter()
+            .filter(|t| t.status() != SensorStatus::Normal)
+            .collect();
+        warnings.sort_by(|a, b| {
+            let headroom = |t: &TemperatureSensor| t.critical.unwrap_or(f32::MAX) - t.current;
+            headroom(a).total_cmp(&headroom(b))
+        });
+        warnings
+    }
+}
+
+/// Temperatures and fans found under `<sysfs_root>/class/hwmon`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HwmonReadings {
+    pub temperatures: Vec<TemperatureSensor>,
+    pub fans: Vec<FanSensor>,
+}
+
+/// Read every hwmon chip below `sysfs_root`.
+///
+/// Values are reported by the kernel in millidegrees and RPM. Sensors whose
+/// input cannot be read are skipped.
+pub fn read_hwmon(sysfs_root: &Path) -> HwmonReadings {
+    let mut readings = HwmonReadings::default();
+
+    let Ok(entries) = std::fs::read_dir(sysfs_root.join("class/hwmon")) else {
+        return readings;
+    };
+    let mut chips: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    chips.sort();
+
+    for chip in chips {
+        let read = |name: &str| std::fs::read_to_string(chip.join(name))
+            .ok()
+            .map(|value| value.trim().to_string());
+        let read_number = |name: &str| read(name).and_then(|value| value.parse::<i64>().ok());
+        let chip_name = read("name").unwrap_or_else(|| "hwmon".to_string());
+
+        // Sensor channels are numbered from 1 but may have gaps
+        let mut channels: Vec<(String, u32)> = std::fs::read_dir(&chip)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                let channel = name.strip_suffix("_input")?;
+                let kind = channel.trim_end_matches(|c: char| c.is_ascii_digit());
+                let index = channel[kind.len()..].parse().ok()?;
+                Some((kind.to_string(), index))
+            })
+            .collect();
+        channels.sort();
+
+        for (kind, index) in channels {
+            let prefix = format!("{}{}", kind, index);
+            let Some(input) = read_number(&format!("{}_input", prefix)) else {
+                continue;
+            };
+            let label = match read(&format!("{}_label", prefix)) {
+                Some(label) => format!("{} {}", chip_name, label),
+                None => format!("{} {}", chip_name, prefix),
+            };
+
+            match kind.as_str() {
+                "temp" => {
+                    let celsius = |millis: i64| millis as f32 / 1000.0;
+                    readings.temperatures.push(TemperatureSensor {
+                        label,
+                        current: celsius(input),
+                        max: read_number(&format!("{}_max", prefix)).map(celsius),
+                        critical: read_number(&format!("{}_crit", prefix)).map(celsius),
+                    });
+                }
+                "fan" => {
+                    let rpm = |value: i64| value.max(0) as u32;
+                    readings.fans.push(FanSensor {
+                        label,
+                        rpm: rpm(input),
+                        min_rpm: read_number(&format!("{}_min", prefix)).map(rpm),
+                        max_rpm: read_number(&format!("{}_max", prefix)).map(rpm),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    readings
+}
+
+/// Temperatures and fans from hwmon; where hwmon has no temperatures and
+/// `sysfs_root` is the live `/sys`, temperatures come from sysinfo instead
+pub async fn get_sensors_snapshot(components: &Mutex<Components>, sysfs_root: &Path) -> Result<SensorsSnapshot> {
+    let hwmon = read_hwmon(sysfs_root);
+    // sysinfo always reads the running machine, which another root is not
+    if !hwmon.temperatures.is_empty() || sysfs_root != Path::new("/sys") {
+        return Ok(SensorsSnapshot {
+            temperatures: hwmon.temperatures,
+            fans: hwmon.fans,
+        });
+    }
+
+    let mut components = components.lock().await;
+    components.refresh_list();
+
+    let finite = |value: f32| Some(value).filter(|v| v.is_finite());
+    let temperatures = components.list()
+        .iter()
+        .filter(|c| c.temperature().is_finite())
+        .map(|c| TemperatureSensor {
+            label: c.label().to_string(),
+            current: c.temperature(),
+            max: finite(c.max()),
+            critical: c.critical().and_then(finite),
+        })
+        .collect();
+
+    Ok(SensorsSnapshot {
+        temperatures,
+        fans: hwmon.fans,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sensor(current: f32, critical: Option<f32>) -> TemperatureSensor {
+        TemperatureSensor {
+            label: "cpu".to_string(),
+            current,
+            max: None,
+            critical,
+        }
+    }
+
+    #[test]
+    fn test_sensor_status() {
+        assert_eq!(sensor(60.0, Some(100.0)).status(), SensorStatus::Normal);
+        assert_eq!(sensor(92.0, Some(100.0)).status(), SensorStatus::Hot);
+        assert_eq!(sensor(100.0, Some(100.0)).status(), SensorStatus::Critical);
+        assert_eq!(sensor(120.0, None).status(), SensorStatus::Normal);
+    }
+
+    #[test]
+    fn test_warnings_sorted_by_headroom() {
+        let snapshot = SensorsSnapshot {
+            temperatures: vec![sensor(91.0, Some(100.0)), sensor(50.0, Some(100.0)), sensor(99.0, Some(100.0))],
+            fans: Vec::new(),
+        };
+
+        let warnings: Vec<f32> = snapshot.warnings().iter().map(|t| t.current).collect();
+        assert_eq!(warnings, vec![99.0, 91.0]);
+    }
+}
@@ -0,0 +1,398 @@
+// src/monitor/sockets.rs
+//! Socket inventory from `/proc/net`.
+//!
+//! The kernel lists sockets per protocol in `/proc/net/{tcp,tcp6,udp,udp6,unix}`
+//! without saying who owns them. Owners are found by matching the socket inode
+//! against the `socket:[inode]` links in every `/proc/<pid>/fd`.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SocketProtocol {
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl SocketProtocol {
+    pub const ALL: [SocketProtocol; 5] = [Self::Tcp, Self::Tcp6, Self::Udp, Self::Udp6, Self::Unix];
+
+    /// File name below `/proc/net`
+    pub fn proc_file(&self) -> &'static str {
+        match self {
+            Self::Tcp => "tcp",
+            Self::Tcp6 => "tcp6",
+            Self::Udp => "udp",
+            Self::Udp6 => "udp6",
+            Self::Unix => "unix",
+        }
+    }
+
+    pub fn is_tcp(&self) -> bool {
+        matches!(self, Self::Tcp | Self::Tcp6)
+    }
+}
+
+impl fmt::Display for SocketProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.proc_file())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SocketState {
+    Established,
+    SynSent,
+    SynRecv,
+    FinWait1,
+    FinWait2,
+    TimeWait,
+    Close,
+    CloseWait,
+    LastAck,
+    Listen,
+    Closing,
+    /// UDP and Unix datagram sockets without a peer
+    Unconnected,
+    Connecting,
+    Disconnecting,
+    Unknown,
+}
+
+impl SocketState {
+    /// State column of the TCP and UDP tables (`include/net/tcp_states.h`)
+    fn from_inet(code: u8, protocol: SocketProtocol) -> Self {
+        match code {
+            0x01 => Self::Established,
+            0x02 => Self::SynSent,
+            0x03 => Self::SynRecv,
+            0x04 => Self::FinWait1,
+            0x05 => Self::FinWait2,
+            0x06 => Self::TimeWait,
+            0x07 if !protocol.is_tcp() => Self::Unconnected,
+            0x07 => Self::Close,
+            0x08 => Self::CloseWait,
+            0x09 => Self::LastAck,
+            0x0A => Self::Listen,
+            0x0B => Self::Closing,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// `St` column of `/proc/net/unix` (`socket_state` in `include/uapi/linux/net.h`)
+    fn from_unix(code: u8, listening: bool) -> Self {
+        match code {
+            _ if listening => Self::Listen,
+            0x01 => Self::Unconnected,
+            0x02 => Self::Connecting,
+            0x03 => Self::Established,
+            0x04 => Self::Disconnecting,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for SocketState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Established => "ESTABLISHED",
+            Self::SynSent => "SYN_SENT",
+            Self::SynRecv => "SYN_RECV",
+            Self::FinWait1 => "FIN_WAIT1",
+            Self::FinWait2 => "FIN_WAIT2",
+            Self::TimeWait => "TIME_WAIT",
+            Self::Close => "CLOSE",
+            Self::CloseWait => "CLOSE_WAIT",
+            Self::LastAck => "LAST_ACK",
+            Self::Listen => "LISTEN",
+            Self::Closing => "CLOSING",
+            Self::Unconnected => "UNCONN",
+            Self::Connecting => "CONNECTING",
+            Self::Disconnecting => "DISCONNECTING",
+            Self::Unknown => "UNKNOWN",
+        };
+        f.pad(name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocketInfo {
+    pub protocol: SocketProtocol,
+    pub state: SocketState,
+    /// Local and remote addresses; `None` for Unix sockets
+    pub local: Option<SocketAddr>,
+    pub remote: Option<SocketAddr>,
+    /// Bound path of a Unix socket, `@` prefixed for abstract names
+    pub path: Option<String>,
+    pub inode: u64,
+    pub uid: Option<u32>,
+    /// Owning process, when it could be determined
+    pub pid: Option<u32>,
+    pub process_name: Option<String>,
+}
+
+impl SocketInfo {
+    /// Accepting connections, or bound and waiting for datagrams
+    pub fn is_listening(&self) -> bool {
+        match self.state {
+            SocketState::Listen => true,
+            SocketState::Unconnected => matches!(self.protocol, SocketProtocol::Udp | SocketProtocol::Udp6),
+            _ => false,
+        }
+    }
+
+    pub fn local_port(&self) -> Option<u16> {
+        self.local.map(|addr| addr.port())
+    }
+
+    pub fn remote_port(&self) -> Option<u16> {
+        self.remote.map(|addr| addr.port()).filter(|&port| port != 0)
+    }
+
+    /// Local address, or the path of a Unix socket
+    pub fn local_display(&self) -> String {
+        match (&self.local, &self.path) {
+            (Some(addr), _) => addr.to_string(),
+            (None, Some(path)) => path.clone(),
+            (None, None) => "-".to_string(),
+        }
+    }
+
+    /// Remote address, `*` when there is no peer
+    pub fn remote_display(&self) -> String {
+        match self.remote {
+            Some(addr) if addr.port() != 0 || !addr.ip().is_unspecified() => addr.to_string(),
+            _ => "*".to_string(),
+        }
+    }
+
+    /// Owner as `name (pid)`
+    pub fn owner_display(&self) -> String {
+        match (self.pid, &self.process_name) {
+            (Some(pid), Some(name)) => format!("{} ({})", name, pid),
+            (Some(pid), None) => pid.to_string(),
+            _ => "-".to_string(),
+        }
+    }
+}
+
+/// Parse an address of the form `0100007F:0050`.
+///
+/// The kernel prints the address as native-endian 32-bit words of the
+/// network-order bytes, so the words are converted back with `to_ne_bytes`.
+fn parse_address(field: &str) -> Option<SocketAddr> {
+    let (address, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let word = |i: usize| u32::from_str_radix(address.get(i * 8..(i + 1) * 8)?, 16).ok();
+    let ip = match address.len() {
+        8 => IpAddr::V4(Ipv4Addr::from(word(0)?.to_ne_bytes())),
+        32 => {
+            let mut bytes = [0u8; 16];
+            for i in 0..4 {
+                bytes[i * 4..(i + 1) * 4].copy_from_slice(&word(i)?.to_ne_bytes());
+            }
+            let ip = Ipv6Addr::from(bytes);
+            // Dual-stack sockets show IPv4 peers as ::ffff:a.b.c.d
+            match ip.to_ipv4_mapped() {
+                Some(v4) => IpAddr::V4(v4),
+                None => IpAddr::V6(ip),
+            }
+        }
+        _ => return None,
+    };
+
+    Some(SocketAddr::new(ip, port))
+}
+
+/// Parse `/proc/net/{tcp,tcp6,udp,udp6}`
+pub fn parse_inet(content: &str, protocol: SocketProtocol) -> Vec<SocketInfo> {
+    content.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+            Some(SocketInfo {
+                protocol,
+                state: SocketState::from_inet(u8::from_str_radix(fields[3], 16).ok()?, protocol),
+                local: parse_address(fields[1]),
+                remote: parse_address(fields[2]),
+                path: None,
+                inode: fields[9].parse().ok()?,
+                uid: fields[7].parse().ok(),
+                pid: None,
+                process_name: None,
+            })
+        })
+        .collect()
+}
+
+/// Parse `/proc/net/unix`
+pub fn parse_unix(content: &str) -> Vec<SocketInfo> {
+    /// `__SO_ACCEPTCON`, set on sockets that called listen()
+    const ACCEPTING: u32 = 1 << 16;
+
+    content.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 7 {
+                return None;
+            }
+            let flags = u32::from_str_radix(fields[3], 16).ok()?;
+            let state = u8::from_str_radix(fields[5], 16).ok()?;
+            Some(SocketInfo {
+                protocol: SocketProtocol::Unix,
+                state: SocketState::from_unix(state, flags & ACCEPTING != 0),
+                local: None,
+                remote: None,
+                path: fields.get(7).map(|path| path.to_string()),
+                inode: fields[6].parse().ok()?,
+                uid: None,
+                pid: None,
+                process_name: None,
+            })
+        })
+        .collect()
+}
+
+/// Map socket inodes to the first process holding them open.
+///
+/// Without privileges only the caller's own processes can be inspected;
+/// sockets of other users are left without an owner.
+pub fn socket_owners(proc_root: &Path) -> HashMap<u64, (u32, String)> {
+    let mut owners = HashMap::new();
+
+    let Ok(entries) = std::fs::read_dir(proc_root) else {
+        return owners;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        // Processes may exit or deny access while the tree is being walked
+        let Ok(fds) = std::fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        let mut name = None;
+        for fd in fds.filter_map(|e| e.ok()) {
+            let Ok(target) = std::fs::read_link(fd.path()) else {
+                continue;
+            };
+            let Some(inode) = target.to_str()
+                .and_then(|t| t.strip_prefix("socket:["))
+                .and_then(|t| t.strip_suffix(']'))
+                .and_then(|t| t.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            let name = name.get_or_insert_with(|| {
+                std::fs::read_to_string(entry.path().join("comm"))
+                    .map(|comm| comm.trim().to_string())
+                    .unwrap_or_default()
+            });
+            owners.entry(inode).or_insert_with(|| (pid, name.clone()));
+        }
+    }
+
+    owners
+}
+
+/// All sockets below `<proc_root>/net` with their owning processes
+pub fn list_sockets(proc_root: &Path) -> Result<Vec<SocketInfo>> {
+    let net = proc_root.join("net");
+    let mut sockets = Vec::new();
+    let mut found = false;
+
+    for protocol in SocketProtocol::ALL {
+        // IPv6 may be disabled, so missing tables are not an error
+        let Ok(content) = std::fs::read_to_string(net.join(protocol.proc_file())) else {
+            continue;
+        };
+        found = true;
+        sockets.extend(match protocol {
+            SocketProtocol::Unix => parse_unix(&content),
+            _ => parse_inet(&content, protocol),
+        });
+    }
+    if !found {
+        anyhow::bail!("no socket tables found in {}", net.display());
+    }
+
+    let owners = socket_owners(proc_root);
+    for socket in &mut sockets {
+        if let Some((pid, name)) = owners.get(&socket.inode) {
+            socket.pid = Some(*pid);
+            socket.process_name = Some(name.clone());
+        }
+    }
+
+    sockets.sort_by(|a, b| {
+        a.protocol.cmp(&b.protocol)
+            .then_with(|| b.is_listening().cmp(&a.is_listening()))
+            .then_with(|| a.local_port().cmp(&b.local_port()))
+    });
+    Ok(sockets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_endian = "little")]
+    #[test]
+    fn test_parse_inet() {
+        let tcp = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:C350 2E0E1AC0:01BB 01 00000000:00000000 02:000A7F8C 00000000  1000        0 34567 2 0000000000000000 20 4 30 10 -1
+";
+        let sockets = parse_inet(tcp, SocketProtocol::Tcp);
+        assert_eq!(sockets.len(), 2);
+        assert_eq!(sockets[0].state, SocketState::Listen);
+        assert_eq!(sockets[0].local, Some("127.0.0.1:631".parse().unwrap()));
+        assert!(sockets[0].is_listening());
+        assert_eq!(sockets[0].remote_display(), "*");
+        assert_eq!(sockets[1].remote, Some("192.26.14.46:443".parse().unwrap()));
+        assert_eq!(sockets[1].uid, Some(1000));
+        assert_eq!(sockets[1].inode, 34567);
+
+        let udp6 = "\
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  0: 00000000000000000000000001000000:14E9 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000   114        0 45678 2 0000000000000000 0
+  1: 0000000000000000FFFF00000100007F:0035 00000000000000000000000000000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 45679 2 0000000000000000 0
+";
+        let sockets = parse_inet(udp6, SocketProtocol::Udp6);
+        assert_eq!(sockets[0].local, Some("[::1]:5353".parse().unwrap()));
+        assert_eq!(sockets[0].state, SocketState::Unconnected);
+        assert!(sockets[0].is_listening());
+        assert_eq!(sockets[1].local, Some("127.0.0.1:53".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_parse_unix() {
+        let unix = "\
+Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 12345 /run/dbus/system_bus_socket
+0000000000000000: 00000003 00000000 00000000 0001 03 12346
+0000000000000000: 00000002 00000000 00000000 0002 01 12347 @/tmp/.X11-unix/X0
+";
+        let sockets = parse_unix(unix);
+        assert_eq!(sockets.len(), 3);
+        assert_eq!(sockets[0].state, SocketState::Listen);
+        assert_eq!(sockets[0].path.as_deref(), Some("/run/dbus/system_bus_socket"));
+        assert_eq!(sockets[1].state, SocketState::Established);
+        assert_eq!(sockets[1].path, None);
+        assert_eq!(sockets[2].local_display(), "@/tmp/.X11-unix/X0");
+        assert!(!sockets[2].is_listening());
+    }
+}
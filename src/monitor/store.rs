@@ -0,0 +1,492 @@
+// src/monitor/store.rs
+//! Persistent, append-only store of metrics and top processes.
+//!
+//! The store is a directory of segment files named after the time of their
+//! first record (`segment-<unix ms>.ndjson`), each holding one
+//! [`StoredSnapshot`] per line. Writes only ever append to the newest
+//! segment; a new one is started when it grows past the segment size or
+//! age. Whole segments are deleted once they fall out of the retention
+//! window or the store exceeds its size cap, so old data goes first.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{Collector, MetricsSample, ProcessInfo, ProcessSort, SortOrder, SystemSnapshot};
+
+const SEGMENT_PREFIX: &str = "segment-";
+const SEGMENT_SUFFIX: &str = ".ndjson";
+
+/// Limits of a [`MetricsStore`]
+#[derive(Debug, Clone)]
+pub struct StoreConfig {
+    pub dir: PathBuf,
+    /// A segment is closed once it reaches this size...
+    pub segment_bytes: u64,
+    /// ...or once its first record is this old
+    pub segment_duration: Duration,
+    /// Segments whose newest record is older than this are deleted
+    pub retention: Duration,
+    /// Oldest segments are deleted while the store is larger than this
+    pub max_bytes: u64,
+}
+
+impl StoreConfig {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            segment_bytes: 8 * 1024 * 1024,
+            segment_duration: Duration::from_secs(3600),
+            retention: Duration::from_secs(7 * 24 * 3600),
+            max_bytes: 256 * 1024 * 1024,
+        }
+    }
+}
+
+/// Resource use of one process at the time of a stored snapshot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessSummary {
+    pub pid: u32,
+    pub name: String,
+    pub user: String,
+    pub cpu_usage: f32,
+    pub memory_usage: f32,
+}
+
+impl From<&ProcessInfo> for ProcessSummary {
+    fn from(process: &ProcessInfo) -> Self {
+        Self {
+            pid: process.pid,
+            name: process.name.clone(),
+            user: process.user.clone(),
+            cpu_usage: process.cpu_usage,
+            memory_usage: process.memory_usage,
+        }
+    }
+}
+
+/// One line of a segment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredSnapshot {
+    #[serde(flatten)]
+    pub metrics: MetricsSample,
+    /// Heaviest processes by CPU, then memory
+    pub processes: Vec<ProcessSummary>,
+}
+
+impl StoredSnapshot {
+    /// Headline metrics plus the `process_limit` busiest processes of a snapshot
+    pub fn from_snapshot(snapshot: &SystemSnapshot, timestamp: DateTime<Utc>, process_limit: usize) -> Self {
+        let mut processes = snapshot.processes.clone();
+        super::process::sort_processes(&mut processes, ProcessSort::Cpu, SortOrder::Desc);
+        let mut top: Vec<ProcessSummary> = processes.iter().take(process_limit).map(ProcessSummary::from).collect();
+
+        // Memory hogs are worth keeping even when they are idle
+        let mut by_memory = processes;
+        super::process::sort_processes(&mut by_memory, ProcessSort::Mem, SortOrder::Desc);
+        for process in by_memory.iter().take(process_limit) {
+            if !top.iter().any(|p| p.pid == process.pid) {
+                top.push(process.into());
+            }
+        }
+
+        Self {
+            metrics: MetricsSample::from_snapshot(snapshot, timestamp),
+            processes: top,
+        }
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.metrics.timestamp
+    }
+}
+
+/// A segment file on disk
+#[derive(Debug, Clone, PartialEq)]
+pub struct Segment {
+    pub path: PathBuf,
+    /// Time of the first record
+    pub start: DateTime<Utc>,
+    pub size: u64,
+}
+
+struct OpenSegment {
+    start: DateTime<Utc>,
+    size: u64,
+    writer: BufWriter<File>,
+}
+
+/// Append-only segmented time-series store in one directory
+pub struct MetricsStore {
+    config: StoreConfig,
+    current: Option<OpenSegment>,
+}
+
+impl MetricsStore {
+    pub fn open(config: StoreConfig) -> Result<Self> {
+        fs::create_dir_all(&config.dir)
+            .with_context(|| format!("cannot create history directory {}", config.dir.display()))?;
+        Ok(Self { config, current: None })
+    }
+
+    pub fn config(&self) -> &StoreConfig {
+        &self.config
+    }
+
+    /// Segments sorted from oldest to newest
+    pub fn segments(&self) -> Result<Vec<Segment>> {
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(&self.config.dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let Some(start) = name.to_str().and_then(segment_start) else {
+                continue;
+            };
+            segments.push(Segment {
+                path: entry.path(),
+                start,
+                size: entry.metadata()?.len(),
+            });
+        }
+        segments.sort_by_key(|s| s.start);
+        Ok(segments)
+    }
+
+    /// Append one record, starting a new segment when the current one is full or old
+    pub fn append(&mut self, record: &StoredSnapshot) -> Result<()> {
+        let timestamp = record.timestamp();
+        let full = self.current.as_ref().map_or(true, |segment| {
+            segment.size >= self.config.segment_bytes
+                || (timestamp - segment.start).to_std().unwrap_or_default() >= self.config.segment_duration
+        });
+        if full {
+            self.rotate(timestamp)?;
+        }
+
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        let segment = self.current.as_mut().expect("rotate opens a segment");
+        segment.writer.write_all(&line)?;
+        segment.writer.flush()?;
+        segment.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Close the current segment and continue in the newest one with room,
+    /// or a new one starting at `timestamp`
+    fn rotate(&mut self, timestamp: DateTime<Utc>) -> Result<()> {
+        let reopen = match self.current.take() {
+            // Right after opening the store, keep filling the last segment
+            None => self.segments()?.pop().filter(|last| {
+                last.size < self.config.segment_bytes
+                    && (timestamp - last.start).to_std().is_ok_and(|age| age < self.config.segment_duration)
+            }),
+            Some(_) => None,
+        };
+        let (path, start) = match reopen {
+            Some(last) => (last.path, last.start),
+            None => (self.config.dir.join(segment_name(timestamp)), timestamp),
+        };
+
+        let file = OpenOptions::new().create(true).append(true).open(&path)
+            .with_context(|| format!("cannot open {}", path.display()))?;
+        self.current = Some(OpenSegment {
+            start,
+            size: file.metadata()?.len(),
+            writer: BufWriter::new(file),
+        });
+
+        self.enforce_retention(timestamp)?;
+        Ok(())
+    }
+
+    /// Delete segments out of the retention window, then the oldest ones
+    /// while the store is over its size cap. The newest segment is kept.
+    pub fn enforce_retention(&self, now: DateTime<Utc>) -> Result<usize> {
+        let segments = self.segments()?;
+        let Some((_, older)) = segments.split_last() else {
+            return Ok(0);
+        };
+
+        let retention = chrono::Duration::from_std(self.config.retention).unwrap_or(chrono::Duration::MAX);
+        let mut total: u64 = segments.iter().map(|s| s.size).sum();
+        let mut removed = 0;
+        for (segment, next) in older.iter().zip(&segments[1..]) {
+            // A segment ends where the next one starts
+            let expired = now.signed_duration_since(next.start) > retention;
+            if !expired && total <= self.config.max_bytes {
+                break;
+            }
+            fs::remove_file(&segment.path)
+                .with_context(|| format!("cannot remove {}", segment.path.display()))?;
+            total -= segment.size;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Records with `from <= timestamp < to`, oldest first.
+    ///
+    /// A line cut short by a crash while writing is skipped.
+    pub fn query(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<StoredSnapshot>> {
+        let segments = self.segments()?;
+        let mut records = Vec::new();
+        for (i, segment) in segments.iter().enumerate() {
+            let end = segments.get(i + 1).map(|next| next.start);
+            if segment.start >= to || end.is_some_and(|end| end < from) {
+                continue;
+            }
+
+            let file = File::open(&segment.path)
+                .with_context(|| format!("cannot open {}", segment.path.display()))?;
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                let Ok(record) = serde_json::from_str::<StoredSnapshot>(&line) else {
+                    continue;
+                };
+                if record.timestamp() >= from && record.timestamp() < to {
+                    records.push(record);
+                }
+            }
+        }
+        records.sort_by_key(|r| r.timestamp());
+        Ok(records)
+    }
+}
+
+fn segment_name(start: DateTime<Utc>) -> String {
+    format!("{}{}{}", SEGMENT_PREFIX, start.timestamp_millis(), SEGMENT_SUFFIX)
+}
+
+fn segment_start(name: &str) -> Option<DateTime<Utc>> {
+    let millis = name.strip_prefix(SEGMENT_PREFIX)?.strip_suffix(SEGMENT_SUFFIX)?.parse().ok()?;
+    Utc.timestamp_millis_opt(millis).single()
+}
+
+/// Process aggregated over a range of stored snapshots
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProcessUsage {
+    pub pid: u32,
+    pub name: String,
+    pub user: String,
+    pub avg_cpu: f32,
+    pub max_cpu: f32,
+    pub avg_memory: f32,
+    pub max_memory: f32,
+    /// Number of snapshots the process appeared in
+    pub samples: usize,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Processes that used the most CPU (or memory) over the records.
+///
+/// Averages are taken over all records, counting zero where the process
+/// was not among the stored ones, so a short spike ranks below a process
+/// that was busy all along.
+pub fn top_processes(records: &[StoredSnapshot], sort: ProcessSort, limit: usize) -> Vec<ProcessUsage> {
+    let mut usage: HashMap<(u32, String), ProcessUsage> = HashMap::new();
+    for record in records {
+        for process in &record.processes {
+            let entry = usage.entry((process.pid, process.name.clone())).or_insert_with(|| ProcessUsage {
+                pid: process.pid,
+                name: process.name.clone(),
+                user: process.user.clone(),
+                avg_cpu: 0.0,
+                max_cpu: 0.0,
+                avg_memory: 0.0,
+                max_memory: 0.0,
+                samples: 0,
+                first_seen: record.timestamp(),
+                last_seen: record.timestamp(),
+            });
+            entry.avg_cpu += process.cpu_usage;
+            entry.max_cpu = entry.max_cpu.max(process.cpu_usage);
+            entry.avg_memory += process.memory_usage;
+            entry.max_memory = entry.max_memory.max(process.memory_usage);
+            entry.samples += 1;
+            entry.last_seen = record.timestamp();
+        }
+    }
+
+    let count = records.len().max(1) as f32;
+    let mut usage: Vec<ProcessUsage> = usage.into_values()
+        .map(|mut p| {
+            p.avg_cpu /= count;
+            p.avg_memory /= count;
+            p
+        })
+        .collect();
+    usage.sort_by(|a, b| {
+        let ordering = match sort {
+            ProcessSort::Mem => b.avg_memory.total_cmp(&a.avg_memory),
+            _ => b.avg_cpu.total_cmp(&a.avg_cpu),
+        };
+        ordering.then_with(|| a.pid.cmp(&b.pid))
+    });
+    usage.truncate(limit);
+    usage
+}
+
+/// Append the collector's latest snapshot to the store every `interval`
+/// until the task is aborted. Write errors are logged and retried on the next tick.
+pub fn spawn_recorder(mut store: MetricsStore, collector: Arc<Collector>, interval: Duration, process_limit: usize) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval.max(Duration::from_secs(1)));
+        loop {
+            ticker.tick().await;
+            let record = StoredSnapshot::from_snapshot(&collector.latest(), Utc::now(), process_limit);
+            if let Err(e) = store.append(&record) {
+                tracing::warn!("cannot write history: {:#}", e);
+            }
+        }
+    })
+}
+
+/// Parse a point in time for history queries.
+///
+/// Accepts RFC 3339 (`2026-10-18T03:00:00Z`), local `YYYY-MM-DD HH:MM[:SS]`,
+/// local `HH:MM` (today) or a duration meaning that long ago (`90m`, `2h`).
+pub fn parse_time(spec: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let spec = spec.trim();
+    if spec == "now" {
+        return Ok(now);
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(spec) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let local = |naive: chrono::NaiveDateTime| {
+        chrono::Local.from_local_datetime(&naive).earliest().map(|t| t.with_timezone(&Utc))
+    };
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Some(time) = chrono::NaiveDateTime::parse_from_str(spec, format).ok().and_then(local) {
+            return Ok(time);
+        }
+    }
+    for format in ["%H:%M:%S", "%H:%M"] {
+        if let Ok(time) = chrono::NaiveTime::parse_from_str(spec, format) {
+            let today = now.with_timezone(&chrono::Local).date_naive();
+            if let Some(time) = local(today.and_time(time)) {
+                return Ok(time);
+            }
+        }
+    }
+
+    let ago = humantime::parse_duration(spec)
+        .with_context(|| format!("invalid time '{}': use RFC 3339, 'YYYY-MM-DD HH:MM', 'HH:MM' or a duration such as 2h", spec))?;
+    Ok(now - chrono::Duration::from_std(ago)?)
+}
+
+/// Where the store lives when the config file does not say:
+/// `$XDG_DATA_HOME/systrix/history`, falling back to `~/.local/share/systrix/history`
+pub fn default_dir() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".local").join("share")))?;
+    Some(base.join("systrix").join("history"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(timestamp: DateTime<Utc>, cpu: f32, processes: &[(u32, &str, f32)]) -> StoredSnapshot {
+        StoredSnapshot {
+            metrics: MetricsSample { timestamp, cpu: Some(cpu), ..Default::default() },
+            processes: processes.iter()
+                .map(|&(pid, name, cpu_usage)| ProcessSummary {
+                    pid,
+                    name: name.to_string(),
+                    user: "root".to_string(),
+                    cpu_usage,
+                    memory_usage: 1.0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_rotation_retention_and_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = StoreConfig::new(dir.path());
+        config.segment_duration = Duration::from_secs(60);
+        config.retention = Duration::from_secs(300);
+        let start = Utc.timestamp_opt(1_760_000_000, 0).unwrap();
+
+        let mut store = MetricsStore::open(config.clone()).unwrap();
+        for minute in 0..10 {
+            let timestamp = start + chrono::Duration::seconds(minute * 60 + 30);
+            store.append(&record(timestamp, minute as f32, &[])).unwrap();
+        }
+        drop(store);
+
+        // One segment per minute; those that ended in the last five minutes survive
+        let store = MetricsStore::open(config).unwrap();
+        let segments = store.segments().unwrap();
+        assert_eq!(segments.len(), 7);
+        assert_eq!(segments[0].start, start + chrono::Duration::seconds(3 * 60 + 30));
+
+        let records = store.query(start + chrono::Duration::minutes(6), start + chrono::Duration::minutes(8)).unwrap();
+        assert_eq!(records.iter().map(|r| r.metrics.cpu.unwrap()).collect::<Vec<_>>(), vec![6.0, 7.0]);
+
+        // A torn last line does not hide the records before it
+        let last = &segments.last().unwrap().path;
+        let mut file = OpenOptions::new().append(true).open(last).unwrap();
+        file.write_all(b"{\"timestamp\":").unwrap();
+        assert_eq!(store.query(start, start + chrono::Duration::hours(1)).unwrap().len(), 7);
+    }
+
+    #[test]
+    fn test_size_cap_keeps_newest_segment() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = StoreConfig::new(dir.path());
+        config.segment_bytes = 1;
+        config.max_bytes = 1;
+        let start = Utc.timestamp_opt(1_760_000_000, 0).unwrap();
+
+        let mut store = MetricsStore::open(config).unwrap();
+        for second in 0..3 {
+            store.append(&record(start + chrono::Duration::seconds(second), 1.0, &[])).unwrap();
+        }
+        let segments = store.segments().unwrap();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].start, start + chrono::Duration::seconds(2));
+    }
+
+    #[test]
+    fn test_top_processes_averages_over_the_range() {
+        let start = Utc::now();
+        let records = vec![
+            record(start, 50.0, &[(1, "spike", 60.0), (2, "steady", 30.0)]),
+            record(start + chrono::Duration::seconds(1), 50.0, &[(2, "steady", 30.0)]),
+            record(start + chrono::Duration::seconds(2), 50.0, &[(2, "steady", 30.0)]),
+        ];
+
+        let top = top_processes(&records, ProcessSort::Cpu, 10);
+        assert_eq!(top[0].name, "steady");
+        assert_eq!(top[0].avg_cpu, 30.0);
+        assert_eq!(top[0].samples, 3);
+        assert_eq!(top[1].name, "spike");
+        assert_eq!(top[1].avg_cpu, 20.0);
+        assert_eq!(top[1].max_cpu, 60.0);
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let now = Utc.timestamp_opt(1_760_000_000, 0).unwrap();
+        assert_eq!(parse_time("now", now).unwrap(), now);
+        assert_eq!(parse_time("2h", now).unwrap(), now - chrono::Duration::hours(2));
+        assert_eq!(parse_time("2025-10-09T09:00:00Z", now).unwrap(), Utc.timestamp_opt(1_760_000_400, 0).unwrap());
+        assert!(parse_time("2025-10-09 03:00", now).is_ok());
+        assert!(parse_time("03:00", now).is_ok());
+        assert!(parse_time("yesterday-ish", now).is_err());
+    }
+}
@@ -1,16 +1,18 @@
 // src/remote_agent.rs
 //! Remote monitoring agent server (optional feature).
 //!
-//! Provides HTTP + WebSocket API for remote system monitoring.
+//! Provides HTTP + WebSocket API for remote system monitoring. Every route
+//! except `/health` requires the agent token as `Authorization: Bearer <token>`.
 
 #[cfg(feature = "remote")]
 use anyhow::Result;
 
 #[cfg(feature = "remote")]
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::Json,
+    extract::{Path, Query, Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{Json, Response},
     routing::get,
     Router,
 };
@@ -22,12 +24,23 @@ use serde_json::{json, Value};
 use std::sync::Arc;
 
 #[cfg(feature = "remote")]
-use crate::monitor::{MonitorBackend, SysinfoBackend};
+use crate::alerts::{AlertEngine, AlertRule, Notifier};
+
+#[cfg(feature = "remote")]
+use crate::monitor::{store, Collector, CollectorConfig, MetricsHistory, MetricsStore, MonitorBackend, ProcessSort, SortOrder, SysinfoBackend, SystemSnapshot};
+
+/// Time between two samples added to the agent's history
+#[cfg(feature = "remote")]
+const HISTORY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
 #[cfg(feature = "remote")]
 pub struct RemoteAgent {
-    backend: Arc<SysinfoBackend>,
+    state: AgentState,
     config: RemoteConfig,
+    recorder: tokio::task::JoinHandle<()>,
+    /// Replaced while serving when the configuration changes
+    store_recorder: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
+    alert_watcher: std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>,
 }
 
 #[cfg(feature = "remote")]
@@ -35,34 +48,181 @@ pub struct RemoteConfig {
     pub bind: String,
     pub port: u16,
     pub token: String,
+    /// Time between two samples, in milliseconds
+    pub refresh_interval_ms: u64,
+    /// Maximum number of processes kept in each published snapshot
+    pub process_limit: usize,
+    /// Answer cross-origin requests, so browser dashboards can call the agent
+    pub enable_cors: bool,
+}
+
+#[cfg(feature = "remote")]
+#[derive(Clone)]
+struct AgentState {
+    collector: Arc<Collector>,
+    backend: Arc<dyn MonitorBackend>,
+    history: Arc<std::sync::Mutex<MetricsHistory>>,
+    token: Arc<str>,
 }
 
 #[cfg(feature = "remote")]
 impl RemoteAgent {
     pub fn new(config: RemoteConfig) -> Self {
+        Self::with_backend(Arc::new(SysinfoBackend::new()), config)
+    }
+
+    /// Serve the metrics of `backend` instead of the live system
+    pub fn with_backend(backend: Arc<dyn MonitorBackend>, config: RemoteConfig) -> Self {
+        let collector = Collector::spawn(backend.clone(), Self::collector_config(config.refresh_interval_ms, config.process_limit));
+
+        let collector = Arc::new(collector);
+        let history = Arc::new(std::sync::Mutex::new(MetricsHistory::default()));
+
+        let recorder = {
+            let (collector, history) = (collector.clone(), history.clone());
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(HISTORY_INTERVAL);
+                loop {
+                    ticker.tick().await;
+                    history.lock().unwrap().record(&collector.latest());
+                }
+            })
+        };
+
         Self {
-            backend: Arc::new(SysinfoBackend::new()),
+            state: AgentState {
+                collector,
+                backend,
+                history,
+                token: config.token.as_str().into(),
+            },
             config,
+            recorder,
+            store_recorder: std::sync::Mutex::new(None),
+            alert_watcher: std::sync::Mutex::new(None),
         }
     }
-    
-    pub async fn start(&self) -> Result<()> {
-        let app = Router::new()
-            .route("/health", get(health_check))
+
+    fn collector_config(refresh_interval_ms: u64, process_limit: usize) -> CollectorConfig {
+        CollectorConfig::with_refresh_interval(refresh_interval_ms).process_limit(process_limit)
+    }
+
+    /// Sample on a new schedule; the last snapshot is served until the
+    /// restarted collector replaces its parts
+    pub fn set_schedule(&self, refresh_interval_ms: u64, process_limit: usize) {
+        self.state.collector.restart(Self::collector_config(refresh_interval_ms, process_limit));
+    }
+
+    /// Also append snapshots to the on-disk history every `interval`,
+    /// instead of any earlier recording
+    pub fn record_history(&self, store: MetricsStore, interval: std::time::Duration, process_limit: usize) {
+        let task = store::spawn_recorder(store, self.state.collector.clone(), interval, process_limit);
+        replace_task(&self.store_recorder, Some(task));
+    }
+
+    pub fn stop_recording_history(&self) {
+        replace_task(&self.store_recorder, None);
+    }
+
+    /// Evaluate alert rules against every sample and send transitions to the
+    /// notifier, instead of any earlier rules
+    pub fn watch_alerts(&self, rules: Vec<AlertRule>, notifier: Arc<Notifier>) {
+        let (collector, backend) = (self.state.collector.clone(), self.state.backend.clone());
+        let reads_processes = rules.iter().any(|rule| rule.expr.reads_processes());
+        replace_task(&self.alert_watcher, Some(tokio::spawn(async move {
+            let mut engine = AlertEngine::new(rules);
+            let mut ticker = tokio::time::interval(HISTORY_INTERVAL);
+            loop {
+                ticker.tick().await;
+                let mut snapshot = collector.latest();
+                // Published snapshots only keep the busiest processes, so rules
+                // about processes by name are checked against all of them
+                if reads_processes {
+                    snapshot.processes = backend.process_list(None, ProcessSort::Pid, SortOrder::Asc, usize::MAX).await
+                        .unwrap_or_else(|e| {
+                            tracing::debug!("cannot list processes for alerts: {}", e);
+                            Vec::new()
+                        });
+                }
+                let events = engine.evaluate(&snapshot, chrono::Utc::now());
+                for event in &events {
+                    tracing::info!("{}", event);
+                }
+                notifier.notify(events);
+            }
+        })));
+    }
+
+    pub fn stop_watching_alerts(&self) {
+        replace_task(&self.alert_watcher, None);
+    }
+
+    /// Routes of the agent, with token authentication applied
+    pub fn router(&self) -> Router {
+        let protected = Router::new()
             .route("/metrics", get(get_metrics))
+            .route("/snapshot", get(get_snapshot))
             .route("/processes", get(get_processes))
-            .with_state(self.backend.clone());
-        
+            .route("/processes/:pid", get(get_process_detail))
+            .route("/cgroups", get(get_cgroups))
+            .route("/history", get(get_history))
+            .route_layer(middleware::from_fn_with_state(self.state.clone(), require_token));
+
+        let router = Router::new()
+            .route("/health", get(health_check))
+            .merge(protected)
+            .with_state(self.state.clone());
+        if self.config.enable_cors {
+            router.layer(tower_http::cors::CorsLayer::permissive())
+        } else {
+            router
+        }
+    }
+
+    pub async fn start(&self) -> Result<()> {
         let addr = format!("{}:{}", self.config.bind, self.config.port);
         tracing::info!("Remote agent listening on {}", addr);
-        
+
         let listener = tokio::net::TcpListener::bind(&addr).await?;
-        axum::serve(listener, app).await?;
-        
+        axum::serve(listener, self.router()).await?;
+
         Ok(())
     }
 }
 
+#[cfg(feature = "remote")]
+impl Drop for RemoteAgent {
+    fn drop(&mut self) {
+        self.recorder.abort();
+        replace_task(&self.store_recorder, None);
+        replace_task(&self.alert_watcher, None);
+    }
+}
+
+/// Put `task` in `slot`, stopping the task it held
+#[cfg(feature = "remote")]
+fn replace_task(slot: &std::sync::Mutex<Option<tokio::task::JoinHandle<()>>>, task: Option<tokio::task::JoinHandle<()>>) {
+    let previous = std::mem::replace(&mut *slot.lock().unwrap(), task);
+    if let Some(previous) = previous {
+        previous.abort();
+    }
+}
+
+#[cfg(feature = "remote")]
+async fn require_token(State(state): State<AgentState>, request: Request, next: Next) -> Result<Response, StatusCode> {
+    let authorized = request.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| token == &*state.token);
+
+    if authorized {
+        Ok(next.run(request).await)
+    } else {
+        Err(StatusCode::UNAUTHORIZED)
+    }
+}
+
 #[cfg(feature = "remote")]
 async fn health_check() -> Json<Value> {
     Json(json!({
@@ -73,29 +233,108 @@ async fn health_check() -> Json<Value> {
 
 #[cfg(feature = "remote")]
 async fn get_metrics(
-    State(backend): State<Arc<SysinfoBackend>>,
+    State(state): State<AgentState>,
 ) -> Result<Json<Value>, StatusCode> {
-    let cpu = backend.cpu_snapshot().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let memory = backend.memory_snapshot().await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
+    let snapshot = state.collector.latest();
+    let cpu = snapshot.cpu.ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let memory = snapshot.memory.ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let network = snapshot.network;
+    let pressure = snapshot.pressure;
+
     Ok(Json(json!({
         "cpu": cpu,
         "memory": memory,
+        "network": network,
+        "pressure": pressure,
         "timestamp": chrono::Utc::now().to_rfc3339(),
     })))
 }
 
+#[cfg(feature = "remote")]
+async fn get_snapshot(State(state): State<AgentState>) -> Json<SystemSnapshot> {
+    Json(state.collector.latest())
+}
+
+#[cfg(feature = "remote")]
+#[derive(serde::Deserialize)]
+struct ProcessQuery {
+    #[serde(default)]
+    sort: ProcessSort,
+    order: Option<SortOrder>,
+    limit: Option<usize>,
+    /// Only processes whose name or user contains this
+    filter: Option<String>,
+}
+
 #[cfg(feature = "remote")]
 async fn get_processes(
-    State(backend): State<Arc<SysinfoBackend>>,
+    State(state): State<AgentState>,
+    Query(query): Query<ProcessQuery>,
 ) -> Result<Json<Value>, StatusCode> {
-    let processes = backend.process_list(None, "cpu", 50).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    
+    // Published snapshots only hold the busiest processes, so other orders
+    // are sorted by the backend over every process
+    let order = query.order.unwrap_or_else(|| query.sort.default_order());
+    let mut processes = state.backend.process_list(query.filter.as_deref(), query.sort, order, usize::MAX).await
+        .map_err(|_| StatusCode::SERVICE_UNAVAILABLE)?;
+    let total = processes.len();
+    if let Some(limit) = query.limit {
+        processes.truncate(limit);
+    }
+
     Ok(Json(json!({
         "processes": processes,
         "count": processes.len(),
+        "total": total,
     })))
 }
+
+#[cfg(feature = "remote")]
+async fn get_process_detail(
+    State(state): State<AgentState>,
+    Path(pid): Path<u32>,
+) -> Result<Json<Value>, StatusCode> {
+    let detail = state.backend.process_detail(pid).await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+    Ok(Json(json!(detail)))
+}
+
+#[cfg(feature = "remote")]
+async fn get_cgroups(State(state): State<AgentState>) -> Result<Json<Value>, StatusCode> {
+    let cgroups = state.backend.cgroups().await
+        .map_err(|_| StatusCode::SERVICE_UNAVAILABLE)?;
+    Ok(Json(json!(cgroups)))
+}
+
+#[cfg(feature = "remote")]
+#[derive(serde::Deserialize)]
+struct HistoryQuery {
+    /// How far back to look, e.g. `5m`
+    window: Option<String>,
+    /// Maximum number of points; longer windows are averaged down to it
+    points: Option<usize>,
+    /// Only this metric, as `[timestamp, value]` pairs
+    metric: Option<String>,
+}
+
+#[cfg(feature = "remote")]
+async fn get_history(
+    State(state): State<AgentState>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    let window = match &query.window {
+        Some(window) => humantime::parse_duration(window).map_err(|_| StatusCode::BAD_REQUEST)?,
+        None => std::time::Duration::from_secs(300),
+    };
+    let points = query.points.unwrap_or(300);
+    let history = state.history.lock().unwrap();
+
+    Ok(Json(match &query.metric {
+        Some(metric) => json!({
+            "metric": metric,
+            "points": history.series(metric, window, points),
+        }),
+        None => json!({
+            "samples": history.window(window, points),
+        }),
+    }))
+}
@@ -16,6 +16,10 @@ impl EventHandler {
         Self { refresh_interval }
     }
 
+    pub fn set_refresh_interval(&mut self, refresh_interval: u64) {
+        self.refresh_interval = refresh_interval;
+    }
+
     pub async fn handle_events(&mut self, ui: &mut Ui) -> Result<bool> {
         // Poll for events with timeout
         if event::poll(Duration::from_millis(self.refresh_interval))? {
@@ -28,9 +32,15 @@ impl EventHandler {
     }
 
     async fn handle_key_event(&self, key: KeyEvent, ui: &mut Ui) -> Result<bool> {
+        #[cfg(feature = "remote")]
+        if ui.is_fleet_overview() {
+            return Ok(self.handle_fleet_key(key, ui));
+        }
+        
+        let keys = ui.keys();
         match key.code {
             // Quit
-            KeyCode::Char('q') | KeyCode::Char('Q') => return Ok(true),
+            KeyCode::Char(c) if c.eq_ignore_ascii_case(&keys.quit) => return Ok(true),
             
             // Quit with Ctrl+C
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
@@ -43,8 +53,22 @@ impl EventHandler {
             KeyCode::Char('3') if !ui.is_export_format_modal() => ui.set_active_panel(2),
             KeyCode::Char('4') if !ui.is_export_format_modal() => ui.set_active_panel(3),
             KeyCode::Char('5') if !ui.is_export_format_modal() => ui.set_active_panel(4),
+            KeyCode::Char('6') if !ui.is_export_format_modal() => ui.set_active_panel(5),
+            KeyCode::Char('7') if !ui.is_export_format_modal() => ui.set_active_panel(6),
+            KeyCode::Tab if ui.is_detail_view_open() => ui.detail_view_tab(true),
+            KeyCode::BackTab if ui.is_detail_view_open() => ui.detail_view_tab(false),
             KeyCode::Tab => ui.next_panel(),
             
+            // Detail view tabs and scrolling
+            KeyCode::Left if ui.is_detail_view_open() => ui.detail_view_tab(false),
+            KeyCode::Right if ui.is_detail_view_open() => ui.detail_view_tab(true),
+            KeyCode::Up if ui.is_detail_view_open() => ui.detail_view_scroll(-1),
+            KeyCode::Down if ui.is_detail_view_open() => ui.detail_view_scroll(1),
+            KeyCode::PageUp if ui.is_detail_view_open() => ui.detail_view_scroll(-10),
+            KeyCode::PageDown if ui.is_detail_view_open() => ui.detail_view_scroll(10),
+            KeyCode::Home if ui.is_detail_view_open() => ui.detail_view_scroll_to(true),
+            KeyCode::End if ui.is_detail_view_open() => ui.detail_view_scroll_to(false),
+            
             // List navigation
             KeyCode::Up => {
                 if ui.is_export_format_modal() {
@@ -73,31 +97,40 @@ impl EventHandler {
                     ui.cancel_action(); // Close modal first
                     ui.export_data(format);
                 } else if !ui.is_modal_open() {
-                    ui.show_details();
+                    ui.show_details().await;
                 }
             }
-            KeyCode::Char('k') if !ui.is_search_mode() && !ui.is_modal_open() => {
+            KeyCode::Char(c) if c == keys.kill && !ui.is_search_mode() && !ui.is_modal_open() && !ui.is_read_only() => {
                 ui.kill_selected_process().await?
             }
-            KeyCode::Char('y') | KeyCode::Char('Y') => {
-                if ui.is_kill_confirm_modal() {
-                    ui.confirm_kill().await?;
-                }
+            KeyCode::Char('y') | KeyCode::Char('Y') if ui.is_kill_confirm_modal() => {
+                ui.confirm_kill().await?;
             }
-            KeyCode::Char('n') | KeyCode::Char('N') => {
-                if ui.is_kill_confirm_modal() {
-                    ui.cancel_action();
-                }
+            KeyCode::Char('n') | KeyCode::Char('N') if ui.is_kill_confirm_modal() => {
+                ui.cancel_action();
             }
-            KeyCode::Char('s') if !ui.is_search_mode() && !ui.is_modal_open() => {
+            KeyCode::Char(c) if c == keys.suspend && !ui.is_search_mode() && !ui.is_modal_open() && !ui.is_read_only() => {
                 ui.suspend_selected_process().await?
             }
-            KeyCode::Char('r') if !ui.is_search_mode() && !ui.is_modal_open() => {
+            KeyCode::Char(c) if c == keys.resume && !ui.is_search_mode() && !ui.is_modal_open() && !ui.is_read_only() => {
                 ui.resume_selected_process().await?
             }
-            KeyCode::Char('p') if !ui.is_search_mode() && !ui.is_modal_open() => ui.toggle_pause(),
-            KeyCode::Char('t') if !ui.is_search_mode() && !ui.is_modal_open() => ui.toggle_theme(),
-            KeyCode::Char('e') if !ui.is_search_mode() && !ui.is_modal_open() => {
+            KeyCode::Char(c) if c == keys.group && !ui.is_search_mode() && !ui.is_modal_open() => ui.toggle_group_view(),
+            KeyCode::Char(c) if c == keys.tree && !ui.is_search_mode() && !ui.is_modal_open() => ui.toggle_tree_view(),
+            KeyCode::Char(c) if c == keys.connections && !ui.is_search_mode() && !ui.is_modal_open() => ui.toggle_connections_view(),
+            KeyCode::Left if !ui.is_modal_open() => ui.set_selected_collapsed(true),
+            KeyCode::Right if !ui.is_modal_open() => ui.set_selected_collapsed(false),
+            KeyCode::Char(c) if c == keys.pause && !ui.is_search_mode() && !ui.is_modal_open() => ui.toggle_pause(),
+            
+            // Playback of a recording
+            KeyCode::Char('+') | KeyCode::Char('=') if ui.is_replay() && !ui.is_search_mode() => ui.replay_speed(true),
+            KeyCode::Char('-') if ui.is_replay() && !ui.is_search_mode() => ui.replay_speed(false),
+            KeyCode::Char('[') if ui.is_replay() && !ui.is_search_mode() => ui.replay_seek(-10),
+            KeyCode::Char(']') if ui.is_replay() && !ui.is_search_mode() => ui.replay_seek(10),
+            KeyCode::Char('{') if ui.is_replay() && !ui.is_search_mode() => ui.replay_seek(-60),
+            KeyCode::Char('}') if ui.is_replay() && !ui.is_search_mode() => ui.replay_seek(60),
+            KeyCode::Char(c) if c == keys.theme && !ui.is_search_mode() && !ui.is_modal_open() => ui.toggle_theme(),
+            KeyCode::Char(c) if c == keys.export && !ui.is_search_mode() && !ui.is_modal_open() => {
                 ui.show_export_format_modal()
             }
             KeyCode::Char('1') if ui.is_export_format_modal() => {
@@ -109,7 +142,9 @@ impl EventHandler {
             KeyCode::Char('3') if ui.is_export_format_modal() => {
                 ui.export_format_select(2);
             }
-            KeyCode::Char('/') if !ui.is_search_mode() && !ui.is_modal_open() => ui.start_search(),
+            KeyCode::Char(c) if c == keys.search && !ui.is_search_mode() && !ui.is_modal_open() => ui.start_search(),
+            #[cfg(feature = "remote")]
+            KeyCode::Esc if ui.can_leave_host() => ui.leave_host(),
             KeyCode::Esc => ui.cancel_action(),
             
             // Search mode
@@ -121,4 +156,24 @@ impl EventHandler {
         
         Ok(false)
     }
+    
+    /// Keys on the fleet table; returns whether to quit
+    #[cfg(feature = "remote")]
+    fn handle_fleet_key(&self, key: KeyEvent, ui: &mut Ui) -> bool {
+        let keys = ui.keys();
+        match key.code {
+            KeyCode::Char(c) if c.eq_ignore_ascii_case(&keys.quit) => return true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return true,
+            KeyCode::Up => ui.fleet_select(-1),
+            KeyCode::Down => ui.fleet_select(1),
+            KeyCode::PageUp => ui.fleet_select(-10),
+            KeyCode::PageDown => ui.fleet_select(10),
+            KeyCode::Enter => ui.open_selected_host(),
+            KeyCode::Char('o') => ui.fleet_sort_next(),
+            KeyCode::Char('O') => ui.fleet_reverse(),
+            KeyCode::Char(c) if c == keys.theme => ui.toggle_theme(),
+            _ => {}
+        }
+        false
+    }
 }
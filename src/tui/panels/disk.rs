@@ -2,13 +2,13 @@
 //! Disk panel with partition details and usage information.
 
 use ratatui::{
-    layout::{Constraint, Rect},
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     widgets::{Block, Borders, Cell, Row, Table},
     Frame,
 };
 
-use crate::monitor::DiskInfo;
+use crate::monitor::{DiskInfo, DiskIoStats};
 use crate::tui::ui::Theme;
 use crate::utils;
 
@@ -16,6 +16,7 @@ pub fn render(
     f: &mut Frame,
     area: Rect,
     disk_list: &[DiskInfo],
+    disk_io: &[DiskIoStats],
     _theme: &Theme,
 ) {
     if disk_list.is_empty() {
@@ -25,7 +26,20 @@ pub fn render(
         return;
     }
     
-    let header_cells = ["Mount Point", "Type", "Total", "Used", "Available", "Usage"]
+    let (partitions_area, io_area) = if disk_io.is_empty() {
+        (area, None)
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),  // Partitions
+                Constraint::Length(disk_io.len().min(10) as u16 + 3),  // Devices
+            ])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
+    };
+    
+    let header_cells = ["Mount Point", "Type", "Total", "Used", "Available", "Read/s", "Write/s", "Usage"]
         .iter()
         .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
     let header = Row::new(header_cells)
@@ -33,6 +47,9 @@ pub fn render(
         .height(1);
     
     let rows = disk_list.iter().map(|disk| {
+        // Throughput of the device this filesystem lives on
+        let io = disk_io.iter().find(|d| d.mount_points.contains(&disk.mount_point));
+        
         let usage_bar = if disk.total > 0 {
             let percent = (disk.usage_percent as u16).min(100);
             let bar_width = 20;
@@ -52,6 +69,8 @@ pub fn render(
             Cell::from(utils::format_bytes(disk.total)),
             Cell::from(utils::format_bytes(disk.used)),
             Cell::from(utils::format_bytes(disk.available)),
+            Cell::from(io.map(|d| utils::format_bytes(d.read_bytes_per_sec)).unwrap_or_else(|| "-".to_string())),
+            Cell::from(io.map(|d| utils::format_bytes(d.write_bytes_per_sec)).unwrap_or_else(|| "-".to_string())),
             Cell::from(usage_bar),
         ];
         
@@ -72,6 +91,8 @@ pub fn render(
         Constraint::Length(10),
         Constraint::Length(10),
         Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
         Constraint::Min(30),
     ])
     .header(header)
@@ -79,5 +100,59 @@ pub fn render(
         .borders(Borders::ALL)
         .title(format!("Disk Partitions ({} total)", disk_list.len())));
     
+    f.render_widget(table, partitions_area);
+    
+    if let Some(io_area) = io_area {
+        render_io(f, io_area, disk_io);
+    }
+}
+
+fn render_io(f: &mut Frame, area: Rect, disk_io: &[DiskIoStats]) {
+    let header_cells = ["Device", "Read/s", "Write/s", "IOPS (r/w)", "Await", "Queue", "Utilization", "Mounts"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::DarkGray))
+        .height(1);
+    
+    let rows = disk_io.iter().map(|device| {
+        let cells = vec![
+            Cell::from(device.device.clone()),
+            Cell::from(utils::format_bytes(device.read_bytes_per_sec)),
+            Cell::from(utils::format_bytes(device.write_bytes_per_sec)),
+            Cell::from(format!("{:.0}/{:.0}", device.read_iops, device.write_iops)),
+            Cell::from(format!("{:.1} ms", device.await_ms)),
+            Cell::from(format!("{:.2}", device.queue_depth)),
+            Cell::from(format!("[{}] {:.0}%", utils::create_bar(device.utilization as f32, 10), device.utilization)),
+            Cell::from(device.mount_points.join(", ")),
+        ];
+        
+        // A saturated device is the usual cause of slow disk access
+        let style = if device.utilization > 90.0 {
+            Style::default().fg(Color::Red)
+        } else if device.utilization > 60.0 {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        
+        Row::new(cells).height(1).style(style)
+    });
+    
+    let table = Table::new(rows, [
+        Constraint::Length(12),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(11),
+        Constraint::Length(10),
+        Constraint::Length(7),
+        Constraint::Length(19),
+        Constraint::Min(20),
+    ])
+    .header(header)
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Block Device I/O ({} devices)", disk_io.len())));
+    
     f.render_widget(table, area);
 }
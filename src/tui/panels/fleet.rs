@@ -0,0 +1,102 @@
+// src/tui/panels/fleet.rs
+//! Fleet panel with one row per remote agent.
+
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table},
+    Frame,
+};
+
+use crate::monitor::fleet::{FleetSort, HostHealth, HostRow};
+use crate::monitor::remote::ConnectionStatus;
+use crate::monitor::SortOrder;
+use crate::tui::ui::Theme;
+
+const COLUMNS: [(&str, FleetSort); 6] = [
+    ("HOST", FleetSort::Name),
+    ("CPU%", FleetSort::Cpu),
+    ("MEM%", FleetSort::Memory),
+    ("DISK%", FleetSort::Disk),
+    ("LOAD", FleetSort::Load),
+    ("ALERT", FleetSort::Health),
+];
+
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    rows: &[HostRow],
+    selected_index: usize,
+    sort: FleetSort,
+    order: SortOrder,
+    theme: &Theme,
+) {
+    let header_cells = COLUMNS.iter()
+        .map(|(title, column)| {
+            let title = match (*column == sort, order) {
+                (true, SortOrder::Asc) => format!("{} ▲", title),
+                (true, SortOrder::Desc) => format!("{} ▼", title),
+                (false, _) => title.to_string(),
+            };
+            Cell::from(title).style(Style::default().add_modifier(Modifier::BOLD))
+        })
+        .chain(std::iter::once(Cell::from("STATUS").style(Style::default().add_modifier(Modifier::BOLD))));
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::DarkGray))
+        .height(1);
+
+    let percent = |value: Option<f64>| value.map(|v| format!("{:.1}", v)).unwrap_or_else(|| "-".to_string());
+
+    let table_rows = rows.iter().enumerate().map(|(i, row)| {
+        let status = match &row.status {
+            ConnectionStatus::Connecting => "connecting".to_string(),
+            ConnectionStatus::Connected => "connected".to_string(),
+            ConnectionStatus::Lost { error, retry_in, .. } => {
+                format!("retry in {:.0}s: {}", retry_in.as_secs_f64().ceil(), error)
+            }
+        };
+
+        let cells = vec![
+            Cell::from(row.name.clone()),
+            Cell::from(percent(row.cpu)),
+            Cell::from(percent(row.memory)),
+            Cell::from(percent(row.disk)),
+            Cell::from(row.load.map(|l| format!("{:.2}", l)).unwrap_or_else(|| "-".to_string())),
+            Cell::from(row.health.label()),
+            Cell::from(status),
+        ];
+
+        let style = if i == selected_index {
+            Style::default()
+                .bg(theme.primary_color())
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            match row.health {
+                HostHealth::Ok => Style::default(),
+                HostHealth::Warning => Style::default().fg(Color::Yellow),
+                HostHealth::Critical | HostHealth::Down => Style::default().fg(Color::Red),
+            }
+        };
+
+        Row::new(cells).style(style).height(1)
+    });
+
+    let title = format!("Fleet ({} hosts) - [Enter] Open host [o] Sort column [O] Reverse", rows.len());
+
+    let table = Table::new(table_rows, [
+        Constraint::Length(24),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Min(20),
+    ])
+    .header(header)
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .title(title));
+
+    f.render_widget(table, area);
+}
@@ -1,5 +1,88 @@
 // src/tui/panels/gpu.rs
-//! GPU panel (stub).
+//! GPU panel with utilization, video memory, clocks and temperature.
 
-// TODO: Implement GPU panel with NVIDIA GPU monitoring
-// Requires nvml-wrapper feature and NVIDIA drivers
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::monitor::GpuInfo;
+use crate::tui::ui::Theme;
+use crate::utils;
+
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    gpus: &[GpuInfo],
+    _theme: &Theme,
+) {
+    if gpus.is_empty() {
+        let text = Paragraph::new("No GPU information available")
+            .block(Block::default().borders(Borders::ALL).title("GPU"));
+        f.render_widget(text, area);
+        return;
+    }
+
+    let header_cells = ["#", "Name", "Driver", "Utilization", "Video Memory", "Core", "Memory", "Temp", "Power"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::DarkGray))
+        .height(1);
+
+    let or_na = |value: Option<String>| value.unwrap_or_else(|| "N/A".to_string());
+
+    let rows = gpus.iter().map(|gpu| {
+        let utilization = gpu.utilization
+            .map(|u| format!("[{}] {:>3.0}%", utils::create_bar(u, 10), u));
+        let memory = gpu.memory_percent().zip(gpu.memory_used.zip(gpu.memory_total))
+            .map(|(percent, (used, total))| format!(
+                "[{}] {}/{}",
+                utils::create_bar(percent, 10),
+                utils::format_bytes(used),
+                utils::format_bytes(total)
+            ));
+
+        let cells = vec![
+            Cell::from(gpu.index.to_string()),
+            Cell::from(gpu.name.chars().take(28).collect::<String>()),
+            Cell::from(gpu.driver.clone()),
+            Cell::from(or_na(utilization)),
+            Cell::from(or_na(memory)),
+            Cell::from(or_na(gpu.core_clock_mhz.map(|mhz| format!("{} MHz", mhz)))),
+            Cell::from(or_na(gpu.memory_clock_mhz.map(|mhz| format!("{} MHz", mhz)))),
+            Cell::from(or_na(gpu.temperature.map(|t| format!("{:.0}°C", t)))),
+            Cell::from(or_na(gpu.power_watts.map(|w| format!("{:.1} W", w)))),
+        ];
+
+        let style = if gpu.utilization.unwrap_or(0.0) > 90.0 || gpu.memory_percent().unwrap_or(0.0) > 90.0 {
+            Style::default().fg(Color::Red)
+        } else if gpu.utilization.unwrap_or(0.0) > 75.0 || gpu.memory_percent().unwrap_or(0.0) > 75.0 {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+
+        Row::new(cells).height(1).style(style)
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Length(3),
+        Constraint::Min(20),
+        Constraint::Length(8),
+        Constraint::Length(19),
+        Constraint::Length(32),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(6),
+        Constraint::Length(8),
+    ])
+    .header(header)
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .title(format!("GPUs ({} total)", gpus.len())));
+
+    f.render_widget(table, area);
+}
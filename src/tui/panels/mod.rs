@@ -3,7 +3,11 @@
 
 pub mod overview;
 pub mod processes;
+pub mod process_detail;
 pub mod network;
 pub mod disk;
+#[cfg(feature = "remote")]
+pub mod fleet;
+pub mod sensors;
 pub mod gpu;
 pub mod settings;
@@ -1,5 +1,6 @@
 // src/tui/panels/network.rs
-//! Network panel with interface details and traffic graphs.
+//! Network panel with interface details and traffic graphs, plus a
+//! connections view listing TCP and UDP sockets with their owners.
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -8,7 +9,8 @@ use ratatui::{
     Frame,
 };
 
-use crate::monitor::NetworkSnapshot;
+use crate::monitor::sockets::SocketState;
+use crate::monitor::{NetworkSnapshot, SocketInfo};
 use crate::tui::ui::Theme;
 use crate::utils;
 
@@ -16,6 +18,9 @@ pub fn render(
     f: &mut Frame,
     area: Rect,
     network_data: &Option<NetworkSnapshot>,
+    sockets: &[SocketInfo],
+    connections_view: bool,
+    scroll_offset: usize,
     theme: &Theme,
 ) {
     let chunks = Layout::default()
@@ -29,9 +34,11 @@ pub fn render(
     // Summary
     if let Some(network) = network_data {
         let summary_text = format!(
-            "Total: ↓ {} | ↑ {} | {} interfaces",
+            "Total: ↓ {} ({}/s) | ↑ {} ({}/s) | {} interfaces",
             utils::format_bytes(network.total_rx),
+            utils::format_bytes(network.total_rx_rate),
             utils::format_bytes(network.total_tx),
+            utils::format_bytes(network.total_tx_rate),
             network.interfaces.len()
         );
         
@@ -41,8 +48,13 @@ pub fn render(
         
         f.render_widget(summary, chunks[0]);
         
+        if connections_view {
+            render_connections(f, chunks[1], sockets, scroll_offset);
+            return;
+        }
+        
         // Interface table
-        let header_cells = ["Interface", "RX Total", "TX Total", "RX Rate", "TX Rate", "Packets RX", "Packets TX"]
+        let header_cells = ["Interface", "RX Total", "TX Total", "RX Rate", "TX Rate", "Pkts RX/s", "Pkts TX/s", "Errors/s"]
             .iter()
             .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
         let header = Row::new(header_cells)
@@ -56,8 +68,9 @@ pub fn render(
                 Cell::from(utils::format_bytes(iface.transmitted)),
                 Cell::from(format!("{}/s", utils::format_bytes(iface.rx_rate))),
                 Cell::from(format!("{}/s", utils::format_bytes(iface.tx_rate))),
-                Cell::from(iface.packets_received.to_string()),
-                Cell::from(iface.packets_transmitted.to_string()),
+                Cell::from(format!("{:.1}", iface.packets_rx_rate)),
+                Cell::from(format!("{:.1}", iface.packets_tx_rate)),
+                Cell::from(format!("{:.1}", iface.errors_rx_rate + iface.errors_tx_rate)),
             ];
             Row::new(cells).height(1)
         });
@@ -70,11 +83,12 @@ pub fn render(
             Constraint::Length(12),
             Constraint::Length(12),
             Constraint::Length(12),
+            Constraint::Length(10),
         ])
         .header(header)
         .block(Block::default()
             .borders(Borders::ALL)
-            .title("Network Interfaces"));
+            .title("Network Interfaces ([c] Connections)"));
         
         f.render_widget(table, chunks[1]);
     } else {
@@ -83,3 +97,49 @@ pub fn render(
         f.render_widget(text, area);
     }
 }
+
+/// TCP and UDP sockets that are connected or listening
+pub fn connection_count(sockets: &[SocketInfo]) -> usize {
+    sockets.iter().filter(|s| s.local.is_some()).count()
+}
+
+fn render_connections(f: &mut Frame, area: Rect, sockets: &[SocketInfo], scroll_offset: usize) {
+    let header_cells = ["Proto", "State", "Local Address", "Remote Address", "Process"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::DarkGray))
+        .height(1);
+    
+    let connections: Vec<&SocketInfo> = sockets.iter().filter(|s| s.local.is_some()).collect();
+    
+    let rows = connections.iter().skip(scroll_offset).map(|socket| {
+        let style = match socket.state {
+            _ if socket.is_listening() => Style::default().fg(Color::Green),
+            SocketState::Established => Style::default(),
+            _ => Style::default().fg(Color::DarkGray),
+        };
+        let cells = vec![
+            Cell::from(socket.protocol.to_string()),
+            Cell::from(socket.state.to_string()),
+            Cell::from(socket.local_display()),
+            Cell::from(socket.remote_display()),
+            Cell::from(socket.owner_display()),
+        ];
+        Row::new(cells).height(1).style(style)
+    });
+    
+    let table = Table::new(rows, [
+        Constraint::Length(6),
+        Constraint::Length(12),
+        Constraint::Length(42),
+        Constraint::Length(42),
+        Constraint::Min(15),
+    ])
+    .header(header)
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Connections ({} total, [c] Interfaces)", connections.len())));
+    
+    f.render_widget(table, area);
+}
@@ -5,14 +5,25 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Gauge, Paragraph},
+    widgets::{Block, Borders, Gauge, Paragraph, Sparkline},
     Frame,
 };
 
-use crate::monitor::{BatteryInfo, CpuSnapshot, DiskSnapshot, MemorySnapshot, NetworkSnapshot};
-use crate::tui::ui::Theme;
+use crate::monitor::memory::MemoryDetail;
+use crate::monitor::{BatteryInfo, CpuSnapshot, DiskSnapshot, MemorySnapshot, MetricsHistory, NetworkSnapshot, PressureSnapshot};
+use crate::tui::ui::{SettingsState, Theme};
 use crate::utils;
 
+/// Width of one core's number, bar and percentage
+const CORE_CELL_WIDTH: u16 = 24;
+
+/// Height of the CPU graph, borders included
+const CPU_GRAPH_HEIGHT: u16 = 6;
+
+/// Time span of the CPU graph
+const CPU_GRAPH_WINDOW: std::time::Duration = std::time::Duration::from_secs(300);
+
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     f: &mut Frame,
     area: Rect,
@@ -21,48 +32,68 @@ pub fn render(
     disk_data: &Option<DiskSnapshot>,
     network_data: &Option<NetworkSnapshot>,
     battery_data: &Option<BatteryInfo>,
+    batteries: &[BatteryInfo],
+    pressure_data: &Option<PressureSnapshot>,
+    history: &MetricsHistory,
+    settings: &SettingsState,
     theme: &Theme,
 ) {
     // Check if battery is present to adjust layout
     let has_battery = battery_data.as_ref().map(|b| b.is_present).unwrap_or(false);
+    // The memory breakdown needs a second line for its legend
+    let memory_detail = memory_data.as_ref().and_then(|m| m.detail.as_ref());
+    let memory_height = if memory_detail.is_some() { 4 } else { 3 };
+    // Per-core bars are laid out in as many columns as fit
+    let cores: &[f32] = match (settings.show_per_core_cpu, cpu_data) {
+        (true, Some(cpu)) => &cpu.per_core_usage,
+        _ => &[],
+    };
+    let core_columns = (area.width.saturating_sub(2) / CORE_CELL_WIDTH).max(1) as usize;
+    let core_rows = (cores.len() + core_columns - 1) / core_columns;
     
-    let chunks = if has_battery {
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),  // CPU
-                Constraint::Length(3),  // Memory
-                Constraint::Length(3),  // Disk
-                Constraint::Length(3),  // Network
-                Constraint::Length(3),  // Battery
-                Constraint::Min(0),     // Details
-            ])
-            .split(area)
-    } else {
-        Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),  // CPU
-                Constraint::Length(3),  // Memory
-                Constraint::Length(3),  // Disk
-                Constraint::Length(3),  // Network
-                Constraint::Min(0),     // Details
-            ])
-            .split(area)
+    let mut constraints = vec![Constraint::Length(3)];  // CPU
+    let mut row = |constraint| {
+        constraints.push(constraint);
+        constraints.len() - 1
     };
+    let graph_row = settings.show_graphs.then(|| row(Constraint::Length(CPU_GRAPH_HEIGHT)));
+    let cores_row = (core_rows > 0).then(|| row(Constraint::Length(core_rows as u16 + 2)));
+    let memory_row = row(Constraint::Length(memory_height));
+    let disk_row = row(Constraint::Length(3));
+    let network_row = row(Constraint::Length(3));
+    let battery_row = has_battery.then(|| row(Constraint::Length(3)));
+    let details_row = row(Constraint::Min(0));
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(constraints)
+        .split(area);
     
     // CPU gauge
     if let Some(cpu) = cpu_data {
+        let average = |secs| history.average("cpu.global", std::time::Duration::from_secs(secs));
+        let title = match (average(60), average(300)) {
+            (Some(one), Some(five)) => format!("CPU Usage (avg 1m {:.1}% │ 5m {:.1}%)", one, five),
+            _ => "CPU Usage".to_string(),
+        };
         let gauge = Gauge::default()
-            .block(Block::default().borders(Borders::ALL).title("CPU Usage"))
+            .block(Block::default().borders(Borders::ALL).title(title))
             .gauge_style(Style::default().fg(theme.primary_color()))
             .percent(cpu.global_usage as u16)
             .label(format!("{:.1}%", cpu.global_usage));
         f.render_widget(gauge, chunks[0]);
     }
     
-    // Memory gauge
-    if let Some(memory) = memory_data {
+    if let Some(graph_row) = graph_row {
+        render_cpu_graph(f, chunks[graph_row], history, theme);
+    }
+    if let Some(cores_row) = cores_row {
+        render_cores(f, chunks[cores_row], cores, core_columns);
+    }
+    
+    // Memory gauge, or the stacked breakdown when the kernel reports one
+    if let (Some(memory), Some(detail)) = (memory_data, memory_detail) {
+        render_memory_breakdown(f, chunks[memory_row], memory, detail);
+    } else if let Some(memory) = memory_data {
         let gauge = Gauge::default()
             .block(Block::default().borders(Borders::ALL).title("Memory Usage"))
             .gauge_style(Style::default().fg(Color::Green))
@@ -71,7 +102,7 @@ pub fn render(
                 memory.usage_percent,
                 utils::format_bytes(memory.used),
                 utils::format_bytes(memory.total)));
-        f.render_widget(gauge, chunks[1]);
+        f.render_widget(gauge, chunks[memory_row]);
     }
     
     // Disk gauge
@@ -84,23 +115,25 @@ pub fn render(
                 disk.usage_percent,
                 utils::format_bytes(disk.used),
                 utils::format_bytes(disk.total)));
-        f.render_widget(gauge, chunks[2]);
+        f.render_widget(gauge, chunks[disk_row]);
     }
     
     // Network info
     if let Some(network) = network_data {
         let net_text = format!(
-            "↓ {} | ↑ {}",
+            "↓ {}/s | ↑ {}/s (total ↓ {} | ↑ {})",
+            utils::format_bytes(network.total_rx_rate),
+            utils::format_bytes(network.total_tx_rate),
             utils::format_bytes(network.total_rx),
             utils::format_bytes(network.total_tx)
         );
         let paragraph = Paragraph::new(net_text)
             .block(Block::default().borders(Borders::ALL).title("Network"));
-        f.render_widget(paragraph, chunks[3]);
+        f.render_widget(paragraph, chunks[network_row]);
     }
     
     // Battery gauge (if present)
-    if has_battery {
+    if let Some(battery_row) = battery_row {
         if let Some(battery) = battery_data {
             let battery_color = crate::monitor::battery::get_battery_color(battery.percentage, battery.is_charging);
             let status_icon = if battery.is_charging { "⚡" } else { "🔋" };
@@ -115,7 +148,7 @@ pub fn render(
                 .gauge_style(Style::default().fg(battery_color))
                 .percent(battery.percentage as u16)
                 .label(label);
-            f.render_widget(gauge, chunks[4]);
+            f.render_widget(gauge, chunks[battery_row]);
         }
     }
     
@@ -153,6 +186,61 @@ pub fn render(
         ]));
     }
     
+    // Contention that CPU percentage alone does not show
+    if let Some(pressure) = pressure_data {
+        let mut spans = vec![Span::styled("Pressure (avg10): ", Style::default().add_modifier(Modifier::BOLD))];
+        for (name, resource) in [("cpu", &pressure.cpu), ("mem", &pressure.memory), ("io", &pressure.io)] {
+            let Some(resource) = resource else {
+                continue;
+            };
+            let stall = resource.some.avg10;
+            let color = if stall > 25.0 {
+                Color::Red
+            } else if stall > 5.0 {
+                Color::Yellow
+            } else {
+                Color::Reset
+            };
+            spans.push(Span::raw(format!("{} ", name)));
+            spans.push(Span::styled(format!("{:.1}%  ", stall), Style::default().fg(color)));
+        }
+        details_lines.push(Line::from(""));
+        details_lines.push(Line::from(spans));
+    }
+    
+    // Memory details the breakdown does not show
+    if let Some(detail) = memory_detail {
+        details_lines.push(Line::from(""));
+        details_lines.push(Line::from(vec![
+            Span::styled("Shared: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(utils::format_bytes(detail.shared)),
+            Span::styled("  Dirty: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(utils::format_bytes(detail.dirty)),
+            Span::styled("  Writeback: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(utils::format_bytes(detail.writeback)),
+        ]));
+        let commit_color = if detail.commit_percent() > 100.0 { Color::Red } else { Color::Reset };
+        details_lines.push(Line::from(vec![
+            Span::styled("Committed: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format!("{} of {} limit ({:.0}%)",
+                    utils::format_bytes(detail.committed_as),
+                    utils::format_bytes(detail.commit_limit),
+                    detail.commit_percent()),
+                Style::default().fg(commit_color),
+            ),
+        ]));
+        if detail.hugepages_total > 0 {
+            details_lines.push(Line::from(vec![
+                Span::styled("Huge Pages: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{} of {} free ({} each)",
+                    detail.hugepages_free,
+                    detail.hugepages_total,
+                    utils::format_bytes(detail.hugepage_size))),
+            ]));
+        }
+    }
+    
     // Add battery details if present
     if has_battery {
         if let Some(battery) = battery_data {
@@ -167,11 +255,101 @@ pub fn render(
                     Span::raw(&battery.vendor),
                 ]));
             }
+            
+            // Individual batteries behind the combined gauge
+            if batteries.len() > 1 {
+                for bat in batteries {
+                    details_lines.push(Line::from(vec![
+                        Span::styled(format!("  {}: ", bat.name), Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled(
+                            format!("{:.0}% {} (health {:.0}%)", bat.percentage, bat.status, bat.health),
+                            Style::default().fg(crate::monitor::battery::get_battery_color(bat.percentage, bat.is_charging)),
+                        ),
+                    ]));
+                }
+            }
         }
     }
     
-    let details_chunk = if has_battery { chunks[5] } else { chunks[4] };
     let details = Paragraph::new(details_lines)
         .block(Block::default().borders(Borders::ALL).title("System Information"));
-    f.render_widget(details, details_chunk);
+    f.render_widget(details, chunks[details_row]);
+}
+
+/// Global CPU usage over the last minutes, one bar per column
+fn render_cpu_graph(f: &mut Frame, area: Rect, history: &MetricsHistory, theme: &Theme) {
+    let width = area.width.saturating_sub(2) as usize;
+    let data: Vec<u64> = history.series("cpu.global", CPU_GRAPH_WINDOW, width)
+        .iter()
+        .map(|(_, usage)| usage.round() as u64)
+        .collect();
+    let graph = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(format!("CPU History ({})", humantime::format_duration(CPU_GRAPH_WINDOW))))
+        .data(&data)
+        .max(100)
+        .style(Style::default().fg(theme.primary_color()));
+    f.render_widget(graph, area);
+}
+
+/// One bar per core, filled row by row
+fn render_cores(f: &mut Frame, area: Rect, cores: &[f32], columns: usize) {
+    let lines: Vec<Line> = cores
+        .chunks(columns)
+        .enumerate()
+        .map(|(row, usages)| {
+            Line::from(usages.iter().enumerate().flat_map(|(column, &usage)| {
+                let color = if usage > 90.0 {
+                    Color::Red
+                } else if usage > 60.0 {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                };
+                [
+                    Span::raw(format!("{:>3} ", row * columns + column)),
+                    Span::styled(utils::create_bar(usage, 10), Style::default().fg(color)),
+                    Span::raw(format!(" {:>5.1}%  ", usage)),
+                ]
+            }).collect::<Vec<_>>())
+        })
+        .collect();
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Per-Core Usage"));
+    f.render_widget(paragraph, area);
+}
+
+/// One bar split into applications, buffers, cache, slab and free memory
+fn render_memory_breakdown(f: &mut Frame, area: Rect, memory: &MemorySnapshot, detail: &MemoryDetail) {
+    const COLORS: [Color; 5] = [Color::Green, Color::Blue, Color::Cyan, Color::Magenta, Color::DarkGray];
+    
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Memory Usage - {:.1}% ({} / {})",
+            memory.usage_percent,
+            utils::format_bytes(memory.used),
+            utils::format_bytes(memory.total)));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    
+    let width = inner.width as u64;
+    let total = detail.total.max(1);
+    let parts = detail.breakdown();
+    
+    let mut bar = Vec::new();
+    let mut legend = Vec::new();
+    let mut used_cells = 0;
+    for (i, ((label, bytes), color)) in parts.iter().zip(COLORS).enumerate() {
+        // The last part takes whatever rounding left over
+        let cells = if i == parts.len() - 1 {
+            width.saturating_sub(used_cells)
+        } else {
+            (bytes * width / total).min(width - used_cells)
+        };
+        used_cells += cells;
+        bar.push(Span::styled("█".repeat(cells as usize), Style::default().fg(color)));
+        legend.push(Span::styled("■ ", Style::default().fg(color)));
+        legend.push(Span::raw(format!("{} {}  ", label, utils::format_bytes(*bytes))));
+    }
+    
+    f.render_widget(Paragraph::new(vec![Line::from(bar), Line::from(legend)]), inner);
 }
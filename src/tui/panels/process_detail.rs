@@ -0,0 +1,159 @@
+// src/tui/panels/process_detail.rs
+//! Tabbed, scrollable view of a single process.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Tabs},
+    Frame,
+};
+
+use crate::monitor::{ProcessDetail, ProcessInfo};
+use crate::tui::ui::Theme;
+use crate::utils;
+
+pub const TABS: [&str; 5] = ["Overview", "Environment", "Files", "Memory", "Limits"];
+
+/// State of the detail view of one process
+pub struct DetailView {
+    pub process: ProcessInfo,
+    /// `Err` holds the reason the detail could not be fetched
+    pub detail: Result<ProcessDetail, String>,
+    pub tab: usize,
+    pub scroll: usize,
+}
+
+impl DetailView {
+    pub fn new(process: ProcessInfo, detail: Result<ProcessDetail, String>) -> Self {
+        Self { process, detail, tab: 0, scroll: 0 }
+    }
+
+    pub fn next_tab(&mut self) {
+        self.tab = (self.tab + 1) % TABS.len();
+        self.scroll = 0;
+    }
+
+    pub fn previous_tab(&mut self) {
+        self.tab = (self.tab + TABS.len() - 1) % TABS.len();
+        self.scroll = 0;
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let max = self.lines().len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(max);
+    }
+
+    pub fn scroll_to(&mut self, top: bool) {
+        self.scroll = if top { 0 } else { self.lines().len().saturating_sub(1) };
+    }
+
+    /// Text of the active tab, one entry per line
+    fn lines(&self) -> Vec<String> {
+        let p = &self.process;
+        let detail = match &self.detail {
+            Ok(detail) => detail,
+            Err(e) if self.tab == 0 => {
+                return vec![
+                    format!("PID: {}", p.pid),
+                    format!("Name: {}", p.name),
+                    format!("User: {}", p.user),
+                    format!("Executable: {}", p.exe_path),
+                    String::new(),
+                    format!("Details unavailable: {}", e),
+                ];
+            }
+            Err(_) => return vec!["Details unavailable".to_string()],
+        };
+        let or_na = |value: &Option<String>| value.clone().unwrap_or_else(|| "N/A".to_string());
+
+        let mut lines: Vec<String> = match self.tab {
+            0 => vec![
+                format!("PID: {}    Parent: {}", p.pid, p.ppid.map(|ppid| ppid.to_string()).unwrap_or_else(|| "-".to_string())),
+                format!("Name: {}", detail.name),
+                format!("User: {}{}", p.user, p.uid.map(|uid| format!(" (uid {})", uid)).unwrap_or_default()),
+                format!("Effective: {} / {}", p.effective_user, p.effective_group),
+                format!("Status: {}    Threads: {}", p.status, p.threads),
+                format!("CPU: {:.1}%    Memory: {:.1}%", p.cpu_usage, p.memory_usage),
+                format!("Disk Read: {} ({}/s)", utils::format_bytes(p.disk_read), utils::format_bytes(p.disk_read_rate)),
+                format!("Disk Write: {} ({}/s)", utils::format_bytes(p.disk_write), utils::format_bytes(p.disk_write_rate)),
+                String::new(),
+                format!("Executable: {}", or_na(&detail.exe)),
+                format!("Working dir: {}", or_na(&detail.cwd)),
+                format!("Root: {}", or_na(&detail.root)),
+                String::new(),
+                "Command line:".to_string(),
+            ]
+            .into_iter()
+            .chain(detail.cmdline.iter().enumerate().map(|(i, arg)| format!("  [{}] {}", i, arg)))
+            .collect(),
+            1 => detail.environ.iter().map(|(key, value)| format!("{}={}", key, value)).collect(),
+            2 => detail.open_files.iter().map(|file| format!("{:>5}  {}", file.fd, file.target)).collect(),
+            3 => {
+                let maps = &detail.maps;
+                vec![
+                    format!("Regions: {}", maps.regions),
+                    format!("Mapped: {}", utils::format_bytes(maps.total_size)),
+                    format!("Anonymous: {}", utils::format_bytes(maps.anonymous_size)),
+                    format!("Heap: {}", utils::format_bytes(maps.heap_size)),
+                    format!("Stack: {}", utils::format_bytes(maps.stack_size)),
+                    format!("File-backed: {}", utils::format_bytes(maps.file_backed_size)),
+                    String::new(),
+                ]
+                .into_iter()
+                .chain(maps.files.iter().map(|file| format!("{:>10}  {:>3}x  {}", utils::format_bytes(file.size), file.regions, file.path)))
+                .collect()
+            }
+            _ => {
+                let limit = |value: Option<u64>| value.map(|v| v.to_string()).unwrap_or_else(|| "unlimited".to_string());
+                std::iter::once(format!("{:<26} {:>20} {:>20}  UNITS", "LIMIT", "SOFT", "HARD"))
+                    .chain(detail.limits.iter().map(|l| format!("{:<26} {:>20} {:>20}  {}", l.name, limit(l.soft), limit(l.hard), l.units)))
+                    .collect()
+            }
+        };
+
+        // procfs section backing each tab after the overview
+        let section = ["", "environ", "fd", "maps", "limits"][self.tab];
+        if self.tab == 0 && !detail.unavailable.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("Not readable: {}", detail.unavailable.join(", ")));
+        } else if detail.unavailable.iter().any(|s| s == section) {
+            lines = vec![format!("Not readable: {} (try elevated privileges)", section)];
+        } else if lines.is_empty() {
+            lines.push("(none)".to_string());
+        }
+
+        lines
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, view: &DetailView, theme: &Theme) {
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(format!("ℹ️  Process {} ({}) - [←→] Tabs [↑↓] Scroll [ESC] Close", view.process.pid, view.process.name))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let tabs = Tabs::new(TABS.to_vec())
+        .block(Block::default().borders(Borders::BOTTOM))
+        .select(view.tab)
+        .style(Style::default().fg(Color::White))
+        .highlight_style(Style::default()
+            .fg(theme.primary_color())
+            .add_modifier(Modifier::BOLD));
+    f.render_widget(tabs, chunks[0]);
+
+    let text = view.lines().join("\n");
+    let body = Paragraph::new(text)
+        .style(Style::default().fg(Color::White).bg(Color::Black))
+        .scroll((view.scroll.min(u16::MAX as usize) as u16, 0));
+    f.render_widget(body, chunks[1]);
+}
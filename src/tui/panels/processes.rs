@@ -8,27 +8,41 @@ use ratatui::{
     Frame,
 };
 
+use crate::monitor::cgroup::ProcessGroup;
 use crate::monitor::ProcessInfo;
 use crate::tui::ui::Theme;
+use crate::utils;
 
+/// A process as displayed in the table
+pub struct ProcessRow<'a> {
+    pub process: &'a ProcessInfo,
+    /// Name column text, including the tree prefix in tree view
+    pub label: String,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     f: &mut Frame,
     area: Rect,
-    processes: &[&ProcessInfo],
+    processes: &[ProcessRow],
     selected_index: usize,
     _scroll_offset: usize,
     theme: &Theme,
     search_query: &str,
     search_mode: bool,
+    tree_view: bool,
+    container: Option<&str>,
+    partial: bool,
 ) {
-    let header_cells = ["PID", "USER", "NAME", "CPU%", "MEM%", "THREADS"]
+    let header_cells = ["PID", "USER", "NAME", "CPU%", "MEM%", "READ/s", "WRITE/s", "THREADS"]
         .iter()
         .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
     let header = Row::new(header_cells)
         .style(Style::default().bg(Color::DarkGray))
         .height(1);
     
-    let rows = processes.iter().enumerate().map(|(i, proc)| {
+    let rows = processes.iter().enumerate().map(|(i, row)| {
+        let proc = row.process;
         let style = if i == selected_index {
             Style::default()
                 .bg(theme.primary_color())
@@ -41,30 +55,102 @@ pub fn render(
         let cells = vec![
             Cell::from(proc.pid.to_string()),
             Cell::from(proc.user.chars().take(10).collect::<String>()),
-            Cell::from(proc.name.chars().take(20).collect::<String>()),
+            Cell::from(row.label.clone()),
             Cell::from(format!("{:.1}", proc.cpu_usage)),
             Cell::from(format!("{:.1}", proc.memory_usage)),
+            Cell::from(utils::format_bytes(proc.disk_read_rate)),
+            Cell::from(utils::format_bytes(proc.disk_write_rate)),
             Cell::from(proc.threads.to_string()),
         ];
         
         Row::new(cells).style(style).height(1)
     });
     
+    let view = match (tree_view, container) {
+        (true, _) => "Process Tree".to_string(),
+        (false, Some(container)) => format!("Processes in {} [ESC] All", container),
+        (false, None) => "Processes".to_string(),
+    };
+    // An older agent only sends its busiest processes
+    let view = if partial { format!("{} (partial list from the agent)", view) } else { view };
     let title = if search_mode {
-        format!("Processes - Search: {}█", search_query)
+        format!("{} - Search: {}█", view, search_query)
     } else if !search_query.is_empty() {
-        format!("Processes ({} filtered) - Press ESC to clear", processes.len())
+        format!("{} ({} filtered) - Press ESC to clear", view, processes.len())
+    } else if tree_view {
+        format!("{} ({} shown) - [←→] Collapse/Expand [v] List view", view, processes.len())
     } else {
-        format!("Processes ({} total) - Press / to search", processes.len())
+        format!("{} ({} total) - Press / to search, v for tree, g to group", view, processes.len())
     };
     
+    let name_width = if tree_view { 40 } else { 22 };
+    
     let table = Table::new(rows, [
         Constraint::Length(8),
         Constraint::Length(12),
-        Constraint::Length(22),
+        Constraint::Length(name_width),
         Constraint::Length(8),
         Constraint::Length(8),
         Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ])
+    .header(header)
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .title(title));
+    
+    f.render_widget(table, area);
+}
+
+/// Process table aggregated by container
+pub fn render_groups(
+    f: &mut Frame,
+    area: Rect,
+    groups: &[ProcessGroup],
+    selected_index: usize,
+    theme: &Theme,
+) {
+    let header_cells = ["CONTAINER", "PROCS", "CPU%", "MEM%", "READ/s", "WRITE/s", "THREADS"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::DarkGray))
+        .height(1);
+    
+    let rows = groups.iter().enumerate().map(|(i, group)| {
+        let style = if i == selected_index {
+            Style::default()
+                .bg(theme.primary_color())
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        
+        let cells = vec![
+            Cell::from(group.name()),
+            Cell::from(group.pids.len().to_string()),
+            Cell::from(format!("{:.1}", group.cpu_usage)),
+            Cell::from(format!("{:.1}", group.memory_usage)),
+            Cell::from(utils::format_bytes(group.disk_read_rate)),
+            Cell::from(utils::format_bytes(group.disk_write_rate)),
+            Cell::from(group.threads.to_string()),
+        ];
+        
+        Row::new(cells).style(style).height(1)
+    });
+    
+    let title = format!("Containers ({} groups) - [Enter] Show processes [g] List view", groups.len());
+    
+    let table = Table::new(rows, [
+        Constraint::Length(40),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(8),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
     ])
     .header(header)
     .block(Block::default()
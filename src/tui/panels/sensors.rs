@@ -0,0 +1,140 @@
+// src/tui/panels/sensors.rs
+//! Sensors panel with temperatures, fan speeds and throttling warnings.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::monitor::sensors::SensorStatus;
+use crate::monitor::SensorsSnapshot;
+use crate::tui::ui::Theme;
+
+pub fn render(
+    f: &mut Frame,
+    area: Rect,
+    sensors_data: &Option<SensorsSnapshot>,
+    theme: &Theme,
+) {
+    let sensors = match sensors_data {
+        Some(sensors) if !sensors.temperatures.is_empty() || !sensors.fans.is_empty() => sensors,
+        _ => {
+            let text = Paragraph::new("No temperature or fan sensors available")
+                .block(Block::default().borders(Borders::ALL).title("Sensors"));
+            f.render_widget(text, area);
+            return;
+        }
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // Warnings
+            Constraint::Min(0),     // Temperatures
+            Constraint::Length(sensors.fans.len().min(8) as u16 + 3),  // Fans
+        ])
+        .split(area);
+
+    // Warnings
+    let warnings = sensors.warnings();
+    let stalled: Vec<_> = sensors.fans.iter().filter(|fan| fan.is_stalled()).collect();
+    let (warning_text, warning_style) = if let Some(hottest) = warnings.first() {
+        let text = format!(
+            "⚠ {} sensor(s) near critical - hottest: {} at {:.1}°C{}",
+            warnings.len(),
+            hottest.label,
+            hottest.current,
+            if hottest.status() == SensorStatus::Critical { " (throttling likely)" } else { "" }
+        );
+        (text, Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+    } else if !stalled.is_empty() {
+        (format!("⚠ {} fan(s) stalled", stalled.len()), Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+    } else {
+        ("✓ All sensors within limits".to_string(), Style::default().fg(theme.primary_color()))
+    };
+
+    let summary = Paragraph::new(warning_text)
+        .style(warning_style)
+        .block(Block::default().borders(Borders::ALL).title("Status"));
+    f.render_widget(summary, chunks[0]);
+
+    // Temperatures
+    let header_cells = ["Sensor", "Current", "Max", "Critical", "Headroom"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::DarkGray))
+        .height(1);
+
+    let celsius = |value: Option<f32>| value.map(|v| format!("{:.1}°C", v)).unwrap_or_else(|| "-".to_string());
+
+    let rows = sensors.temperatures.iter().map(|temp| {
+        let style = match temp.status() {
+            SensorStatus::Critical => Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            SensorStatus::Hot => Style::default().fg(Color::Yellow),
+            SensorStatus::Normal => Style::default(),
+        };
+
+        let cells = vec![
+            Cell::from(temp.label.clone()),
+            Cell::from(format!("{:.1}°C", temp.current)),
+            Cell::from(celsius(temp.max)),
+            Cell::from(celsius(temp.critical)),
+            Cell::from(celsius(temp.critical.map(|c| c - temp.current))),
+        ];
+
+        Row::new(cells).height(1).style(style)
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Min(30),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+        Constraint::Length(10),
+    ])
+    .header(header)
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Temperatures ({} sensors)", sensors.temperatures.len())));
+    f.render_widget(table, chunks[1]);
+
+    // Fans
+    let header_cells = ["Fan", "Speed", "Min", "Max"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells)
+        .style(Style::default().bg(Color::DarkGray))
+        .height(1);
+
+    let rpm = |value: Option<u32>| value.map(|v| format!("{} RPM", v)).unwrap_or_else(|| "-".to_string());
+
+    let rows = sensors.fans.iter().map(|fan| {
+        let style = if fan.is_stalled() {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+
+        Row::new(vec![
+            Cell::from(fan.label.clone()),
+            Cell::from(format!("{} RPM", fan.rpm)),
+            Cell::from(rpm(fan.min_rpm)),
+            Cell::from(rpm(fan.max_rpm)),
+        ]).height(1).style(style)
+    });
+
+    let table = Table::new(rows, [
+        Constraint::Min(30),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+    ])
+    .header(header)
+    .block(Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Fans ({})", sensors.fans.len())));
+    f.render_widget(table, chunks[2]);
+}
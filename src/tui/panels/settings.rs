@@ -39,7 +39,7 @@ fn render_settings_menu(
     settings: &SettingsState,
     theme: &Theme,
 ) {
-    let menu_items = vec![
+    let menu_items = [
         "Appearance",
         "Performance",
         "Display",
@@ -9,8 +9,34 @@ use ratatui::{
     Frame,
 };
 
-use crate::monitor::{BatteryInfo, CpuSnapshot, DiskInfo, DiskSnapshot, MemorySnapshot, MonitorBackend, NetworkSnapshot, ProcessInfo};
-use super::panels::{disk, network, overview, processes, settings};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::alerts::{AlertEngine, Notifier, Severity};
+use crate::config::{Config, KeyBindings, ProcessManagementConfig, ThemeName};
+use crate::monitor::cgroup::{self, ProcessGroup};
+use crate::monitor::process::ProcessTree;
+use crate::monitor::{MetricsHistory, MetricsSample, MonitorBackend, ReplayBackend, SystemSnapshot};
+#[cfg(feature = "remote")]
+use crate::monitor::fleet::{sort_rows, FleetSort, HostHealth, HostRow};
+#[cfg(feature = "remote")]
+use crate::monitor::remote::{ConnectionStatus, RemoteBackend};
+#[cfg(feature = "remote")]
+use crate::monitor::SortOrder;
+use crate::utils;
+use super::panels::process_detail::DetailView;
+use super::panels::processes::ProcessRow;
+use super::panels::{disk, gpu, network, overview, process_detail, processes, sensors, settings};
+#[cfg(feature = "remote")]
+use super::panels::fleet;
+
+/// Panels in tab order; number keys select them starting at 1
+pub const PANEL_TITLES: [&str; 7] = ["Overview", "Processes", "Network", "Disk", "GPU", "Sensors", "Settings"];
+/// Index of the settings panel, which is always last
+pub const SETTINGS_PANEL: usize = PANEL_TITLES.len() - 1;
+/// How long a logged warning stays in the footer
+const WARNING_SHOWN: Duration = Duration::from_secs(10);
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Theme {
@@ -25,6 +51,7 @@ pub enum ModalType {
     KillConfirm,
     ProcessDetail,
     ExportFormat,
+    Inspect,
 }
 
 pub struct SettingsState {
@@ -35,6 +62,16 @@ pub struct SettingsState {
     pub show_per_core_cpu: bool,
 }
 
+impl From<ThemeName> for Theme {
+    fn from(name: ThemeName) -> Self {
+        match name {
+            ThemeName::Dark => Theme::Dark,
+            ThemeName::Light => Theme::Light,
+            ThemeName::Dracula => Theme::Dracula,
+        }
+    }
+}
+
 impl Theme {
     pub fn primary_color(&self) -> Color {
         match self {
@@ -43,15 +80,6 @@ impl Theme {
             Theme::Dracula => Color::Magenta,
         }
     }
-    
-    #[allow(dead_code)]
-    pub fn bg_color(&self) -> Color {
-        match self {
-            Theme::Dark => Color::Black,
-            Theme::Light => Color::White,
-            Theme::Dracula => Color::Rgb(40, 42, 54),
-        }
-    }
 }
 
 pub struct Ui {
@@ -66,22 +94,76 @@ pub struct Ui {
     search_query: String,
     settings_state: SettingsState,
     pending_kill_pid: Option<u32>,
+    process_management: ProcessManagementConfig,
     export_format_selection: usize, // 0=CSV, 1=JSON, 2=HTML
+    keys: KeyBindings,
+    
+    // Configuration last applied, and why the latest edit was not
+    config: Option<Config>,
+    config_error: Option<String>,
+    /// Latest logged warning and when it came in
+    warning: Option<(String, Instant)>,
     
     // Data
-    cpu_data: Option<CpuSnapshot>,
-    memory_data: Option<MemorySnapshot>,
-    disk_data: Option<DiskSnapshot>,
-    network_data: Option<NetworkSnapshot>,
-    battery_data: Option<BatteryInfo>,
-    process_data: Vec<ProcessInfo>,
-    disk_list: Vec<DiskInfo>,
+    snapshot: SystemSnapshot,
+    history: MetricsHistory,
+    alerts: AlertEngine,
+    notifier: Option<Arc<Notifier>>,
     selected_process_index: usize,
     filtered_process_indices: Vec<usize>,
+    
+    // Process tree view
+    tree_view: bool,
+    collapsed_pids: HashSet<u32>,
+    tree_labels: Vec<String>,
+    
+    // Grouping by container
+    group_view: bool,
+    groups: Vec<ProcessGroup>,
+    container_filter: Option<String>,
+    
+    // Sockets instead of interfaces on the network panel
+    connections_view: bool,
+    
+    // Process detail view
+    backend: Arc<dyn MonitorBackend>,
+    detail_view: Option<DetailView>,
+    
+    // Playback controls when showing a recording
+    replay: Option<Arc<ReplayBackend>>,
+    
+    // Connection state when monitoring another host
+    #[cfg(feature = "remote")]
+    remote: Option<Arc<RemoteBackend>>,
+    
+    // Fleet dashboard when watching several agents
+    #[cfg(feature = "remote")]
+    fleet: Option<FleetState>,
+}
+
+/// Change of the shown host requested from the fleet dashboard
+#[cfg(feature = "remote")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FleetRequest {
+    /// Show the normal panels for the host at this configuration index
+    Open(usize),
+    /// Go back to the fleet table
+    Close,
+}
+
+#[cfg(feature = "remote")]
+struct FleetState {
+    rows: Vec<HostRow>,
+    selected: usize,
+    sort: FleetSort,
+    order: SortOrder,
+    /// Configuration index of the host whose panels are shown
+    open: Option<usize>,
+    request: Option<FleetRequest>,
 }
 
 impl Ui {
-    pub fn with_refresh_interval(refresh_interval: u64) -> Self {
+    pub fn new(backend: Arc<dyn MonitorBackend>, refresh_interval: u64) -> Self {
         Self {
             active_panel: 0,
             scroll_offset: 0,
@@ -100,43 +182,129 @@ impl Ui {
                 show_per_core_cpu: true,
             },
             pending_kill_pid: None,
+            process_management: ProcessManagementConfig::default(),
             export_format_selection: 0,
-            cpu_data: None,
-            memory_data: None,
-            disk_data: None,
-            network_data: None,
-            battery_data: None,
-            process_data: Vec::new(),
-            disk_list: Vec::new(),
+            keys: KeyBindings::default(),
+            config: None,
+            config_error: None,
+            warning: None,
+            snapshot: SystemSnapshot::default(),
+            history: MetricsHistory::default(),
+            alerts: AlertEngine::default(),
+            notifier: None,
             selected_process_index: 0,
             filtered_process_indices: Vec::new(),
+            tree_view: false,
+            collapsed_pids: HashSet::new(),
+            tree_labels: Vec::new(),
+            group_view: false,
+            groups: Vec::new(),
+            container_filter: None,
+            connections_view: false,
+            backend,
+            detail_view: None,
+            replay: None,
+            #[cfg(feature = "remote")]
+            remote: None,
+            #[cfg(feature = "remote")]
+            fleet: None,
         }
     }
 
-    pub async fn update_data(&mut self, backend: &impl MonitorBackend) -> Result<()> {
+    /// Take over the latest snapshot published by the collector
+    pub fn update_data(&mut self, snapshot: SystemSnapshot) {
         if self.paused {
-            return Ok(());
+            return;
         }
         
-        self.cpu_data = Some(backend.cpu_snapshot().await?);
-        self.memory_data = Some(backend.memory_snapshot().await?);
-        self.disk_data = Some(backend.disk_snapshot().await?);
-        self.network_data = Some(backend.network_snapshot().await?);
-        self.battery_data = crate::monitor::battery::get_battery_info().await.ok();
-        self.process_data = backend.process_list(None, "cpu", 100).await?;
-        self.disk_list = backend.disk_list().await?;
+        self.snapshot = snapshot;
+        self.record_history();
+        self.evaluate_alerts();
         
         // Update filtered indices based on search
         self.update_filtered_processes();
-        
-        Ok(())
+    }
+    
+    /// Add the shown snapshot to the history, about once per refresh
+    fn record_history(&mut self) {
+        let timestamp = self.snapshot_time();
+        let min_gap = match &self.replay {
+            Some(_) => chrono::Duration::milliseconds(1),
+            None => chrono::Duration::milliseconds(self.settings_state.refresh_interval as i64 / 2),
+        };
+        if self.history.latest().map_or(true, |last| timestamp - last.timestamp >= min_gap) {
+            self.history.push(MetricsSample::from_snapshot(&self.snapshot, timestamp));
+        }
+    }
+    
+    /// Time the shown snapshot was taken
+    fn snapshot_time(&self) -> chrono::DateTime<chrono::Utc> {
+        match &self.replay {
+            Some(replay) => replay.status().timestamp,
+            None => chrono::Utc::now(),
+        }
+    }
+    
+    /// Take over the appearance, limits, keys, kill protection and alert
+    /// rules of the configuration. The theme and export format can also be
+    /// changed in the TUI, so they are only set when the configuration
+    /// changes them, and alert states only restart when the rules change.
+    pub fn apply_config(&mut self, config: &Config, notifier: Option<Arc<Notifier>>) {
+        let previous = self.config.replace(config.clone());
+        if previous.as_ref().map(|p| p.general.theme) != Some(config.general.theme) {
+            self.theme = config.general.theme.into();
+        }
+        if previous.as_ref().map(|p| p.export.default_format) != Some(config.export.default_format) {
+            self.export_format_selection = match config.export.default_format {
+                crate::export::ExportFormat::Csv => 0,
+                crate::export::ExportFormat::Json => 1,
+                crate::export::ExportFormat::Html => 2,
+            };
+        }
+        if previous.as_ref().map(|p| &p.alerts.rules) != Some(&config.alerts.rules) {
+            self.alerts = AlertEngine::new(config.alerts.rules.clone());
+        }
+        self.settings_state.process_limit = config.tui.process_limit;
+        self.settings_state.show_graphs = config.tui.show_graphs;
+        self.settings_state.show_per_core_cpu = config.tui.show_per_core_cpu;
+        self.keys = config.tui.keys;
+        self.process_management = config.process_management.clone();
+        self.notifier = notifier;
+        self.update_filtered_processes();
+    }
+    
+    pub fn set_refresh_interval(&mut self, refresh_interval: u64) {
+        self.settings_state.refresh_interval = refresh_interval;
+    }
+    
+    /// Show why an edit of the configuration file was ignored, or clear it
+    pub fn set_config_error(&mut self, error: Option<String>) {
+        self.config_error = error;
+    }
+    
+    /// Show a logged warning in the footer for a while
+    pub fn show_warning(&mut self, warning: String) {
+        self.warning = Some((warning, Instant::now()));
+    }
+    
+    pub fn keys(&self) -> KeyBindings {
+        self.keys
+    }
+    
+    fn evaluate_alerts(&mut self) {
+        let now = self.snapshot_time();
+        let events = self.alerts.evaluate(&self.snapshot, now);
+        // A recording only shows what happened back then
+        if let (Some(notifier), false) = (&self.notifier, self.is_replay()) {
+            notifier.notify(events);
+        }
     }
     
     fn update_filtered_processes(&mut self) {
-        if self.search_query.is_empty() {
-            self.filtered_process_indices = (0..self.process_data.len()).collect();
+        let matching: Vec<usize> = if self.search_query.is_empty() {
+            (0..self.snapshot.processes.len()).collect()
         } else {
-            self.filtered_process_indices = self.process_data
+            self.snapshot.processes
                 .iter()
                 .enumerate()
                 .filter(|(_, p)| {
@@ -144,7 +312,127 @@ impl Ui {
                     p.user.to_lowercase().contains(&self.search_query.to_lowercase())
                 })
                 .map(|(i, _)| i)
+                .collect()
+        };
+        
+        // Drilled down into a single container
+        let matching: Vec<usize> = match &self.container_filter {
+            Some(name) => matching.into_iter()
+                .filter(|&i| cgroup::group_name(self.snapshot.processes[i].container.as_ref()) == *name)
+                .collect(),
+            None => matching,
+        };
+        
+        if self.group_view {
+            let matched: Vec<_> = matching.iter()
+                .map(|&i| self.snapshot.processes[i].clone())
                 .collect();
+            self.groups = cgroup::group_by_container(&matched);
+            self.filtered_process_indices.clear();
+            self.tree_labels.clear();
+            return;
+        }
+        
+        if !self.tree_view {
+            self.filtered_process_indices = matching;
+            self.filtered_process_indices.truncate(self.settings_state.process_limit);
+            self.tree_labels.clear();
+            return;
+        }
+        
+        // Tree view: order the visible processes depth-first
+        let matched: Vec<_> = matching.iter()
+            .map(|&i| self.snapshot.processes[i].clone())
+            .collect();
+        let index_by_pid: HashMap<u32, usize> = matching.iter()
+            .map(|&i| (self.snapshot.processes[i].pid, i))
+            .collect();
+        let tree = ProcessTree::build(&matched);
+        let rows = tree.flatten(&self.collapsed_pids);
+        
+        self.filtered_process_indices = rows.iter()
+            .filter_map(|row| index_by_pid.get(&row.node.process.pid).copied())
+            .collect();
+        self.tree_labels = rows.iter()
+            .map(|row| {
+                let marker = if row.collapsed {
+                    format!("▸ [+{}] ", row.node.descendant_count())
+                } else if !row.node.children.is_empty() {
+                    "▾ ".to_string()
+                } else {
+                    String::new()
+                };
+                format!("{}{}{}", row.prefix, marker, row.node.process.name)
+            })
+            .collect();
+    }
+    
+    /// Number of selectable rows in the processes panel
+    fn process_row_count(&self) -> usize {
+        if self.group_view {
+            self.groups.len()
+        } else {
+            self.filtered_process_indices.len()
+        }
+    }
+    
+    pub fn toggle_group_view(&mut self) {
+        if self.active_panel == 1 {
+            self.group_view = !self.group_view;
+            self.tree_view = false;
+            self.container_filter = None;
+            self.selected_process_index = 0;
+            self.scroll_offset = 0;
+            self.update_filtered_processes();
+        }
+    }
+    
+    /// Show only the processes of the selected container group
+    fn open_selected_group(&mut self) {
+        if let Some(group) = self.groups.get(self.selected_process_index) {
+            self.container_filter = Some(group.name());
+            self.group_view = false;
+            self.selected_process_index = 0;
+            self.scroll_offset = 0;
+            self.update_filtered_processes();
+        }
+    }
+    
+    pub fn toggle_connections_view(&mut self) {
+        if self.active_panel == 2 {
+            self.connections_view = !self.connections_view;
+            self.scroll_offset = 0;
+        }
+    }
+    
+    pub fn toggle_tree_view(&mut self) {
+        if self.active_panel == 1 {
+            self.tree_view = !self.tree_view;
+            self.group_view = false;
+            self.selected_process_index = 0;
+            self.scroll_offset = 0;
+            self.update_filtered_processes();
+        }
+    }
+    
+    /// Collapse (`true`) or expand (`false`) the selected subtree
+    pub fn set_selected_collapsed(&mut self, collapsed: bool) {
+        if self.active_panel != 1 || !self.tree_view {
+            return;
+        }
+        
+        let pid = self.filtered_process_indices
+            .get(self.selected_process_index)
+            .and_then(|&idx| self.snapshot.processes.get(idx))
+            .map(|p| p.pid);
+        
+        if let Some(pid) = pid {
+            if collapsed {
+                self.collapsed_pids.insert(pid);
+            } else {
+                self.collapsed_pids.remove(&pid);
+            }
+            self.update_filtered_processes();
         }
     }
 
@@ -162,6 +450,12 @@ impl Ui {
             ])
             .split(size);
         
+        #[cfg(feature = "remote")]
+        if self.is_fleet_overview() {
+            self.render_fleet(f, &chunks);
+            return;
+        }
+        
         // Render header
         self.render_header(f, chunks[0]);
         
@@ -176,23 +470,90 @@ impl Ui {
         
         // Render modal if active
         if self.show_modal {
-            self.render_modal(f, size);
+            match &self.detail_view {
+                Some(view) if self.modal_type == ModalType::Inspect => {
+                    process_detail::render(f, centered_rect(85, 85, size), view, &self.theme);
+                }
+                _ => self.render_modal(f, size),
+            }
         }
     }
 
     fn render_header(&self, f: &mut Frame, area: Rect) {
-        let cpu_usage = self.cpu_data.as_ref().map(|c| c.global_usage).unwrap_or(0.0);
-        let mem_usage = self.memory_data.as_ref().map(|m| m.usage_percent).unwrap_or(0.0);
-        let disk_usage = self.disk_data.as_ref().map(|d| d.usage_percent).unwrap_or(0.0);
+        let cpu_usage = self.snapshot.cpu.as_ref().map(|c| c.global_usage).unwrap_or(0.0);
+        let mem_usage = self.snapshot.memory.as_ref().map(|m| m.usage_percent).unwrap_or(0.0);
+        let disk_usage = self.snapshot.disk.as_ref().map(|d| d.usage_percent).unwrap_or(0.0);
         
-        let header_text = format!(
+        let mut header_text = format!(
             " SYSTRIX │ CPU: {:>5.1}% │ RAM: {:>5.1}% │ DISK: {:>5.1}% ",
             cpu_usage, mem_usage, disk_usage
         );
-        
+
+        // Surface overheating even when the sensors panel is not open
+        let hottest = self.snapshot.sensors.as_ref()
+            .and_then(|s| s.warnings().first().map(|t| (t.label.clone(), t.current)));
+        let color = match &hottest {
+            Some((label, current)) => {
+                header_text.push_str(&format!("│ ⚠ {} {:.0}°C ", label, current));
+                Color::Red
+            }
+            None => self.theme.primary_color(),
+        };
+
+        let firing = self.alerts.firing();
+        let color = match firing.first() {
+            Some(worst) => {
+                let names: Vec<String> = firing.iter().map(|a| a.rule.display_name()).collect();
+                header_text.push_str(&format!("│ ⚠ {}: {} ", if firing.len() == 1 { "ALERT" } else { "ALERTS" }, names.join(", ")));
+                if worst.rule.severity == Severity::Critical || color == Color::Red {
+                    Color::Red
+                } else {
+                    Color::Yellow
+                }
+            }
+            None => color,
+        };
+
+        if let Some(replay) = &self.replay {
+            let status = replay.status();
+            header_text.push_str(&format!(
+                "│ {} REPLAY {}{} ({} / {}) {}x ",
+                if status.paused { "⏸" } else { "▶" },
+                replay.header().hostname.as_ref().map(|h| format!("{} ", h)).unwrap_or_default(),
+                status.timestamp.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"),
+                utils::format_duration(status.position.as_secs()),
+                utils::format_duration(status.duration.as_secs()),
+                status.speed
+            ));
+        }
+
+        #[cfg(feature = "remote")]
+        let color = match &self.remote {
+            Some(remote) => match remote.status() {
+                ConnectionStatus::Connecting => {
+                    header_text.push_str(&format!("│ … connecting to {} ", remote.host()));
+                    color
+                }
+                ConnectionStatus::Connected => {
+                    header_text.push_str(&format!("│ ● {} ", remote.host()));
+                    color
+                }
+                ConnectionStatus::Lost { error, retry_in, .. } => {
+                    header_text.push_str(&format!(
+                        "│ ⚠ {} retrying in {}s: {} ",
+                        remote.host(),
+                        retry_in.as_secs_f64().ceil(),
+                        error
+                    ));
+                    Color::Red
+                }
+            },
+            None => color,
+        };
+
         let header = Paragraph::new(header_text)
             .style(Style::default()
-                .fg(self.theme.primary_color())
+                .fg(color)
                 .add_modifier(Modifier::BOLD))
             .block(Block::default().borders(Borders::ALL));
         
@@ -200,8 +561,7 @@ impl Ui {
     }
 
     fn render_tabs(&self, f: &mut Frame, area: Rect) {
-        let titles = vec!["Overview", "Processes", "Network", "Disk", "Settings"];
-        let tabs = Tabs::new(titles)
+        let tabs = Tabs::new(PANEL_TITLES.to_vec())
             .block(Block::default().borders(Borders::ALL))
             .select(self.active_panel)
             .style(Style::default().fg(Color::White))
@@ -216,11 +576,23 @@ impl Ui {
 
     fn render_panel(&mut self, f: &mut Frame, area: Rect) {
         match self.active_panel {
-            0 => overview::render(f, area, &self.cpu_data, &self.memory_data, &self.disk_data, &self.network_data, &self.battery_data, &self.theme),
+            0 => overview::render(f, area, &self.snapshot.cpu, &self.snapshot.memory, &self.snapshot.disk, &self.snapshot.network, &self.snapshot.battery, &self.snapshot.batteries, &self.snapshot.pressure, &self.history, &self.settings_state, &self.theme),
+            1 if self.group_view => {
+                let selected = self.selected_process_index.min(self.groups.len().saturating_sub(1));
+                processes::render_groups(f, area, &self.groups, selected, &self.theme);
+            },
             1 => {
-                let filtered_processes: Vec<&ProcessInfo> = self.filtered_process_indices
+                let filtered_processes: Vec<ProcessRow> = self.filtered_process_indices
                     .iter()
-                    .filter_map(|&i| self.process_data.get(i))
+                    .enumerate()
+                    .filter_map(|(row, &i)| {
+                        let process = self.snapshot.processes.get(i)?;
+                        let label = match self.tree_labels.get(row) {
+                            Some(label) => label.clone(),
+                            None => process.name.chars().take(20).collect(),
+                        };
+                        Some(ProcessRow { process, label })
+                    })
                     .collect();
                 
                 let actual_selected = if !self.filtered_process_indices.is_empty() {
@@ -229,10 +601,12 @@ impl Ui {
                     0
                 };
                 
-                processes::render(f, area, &filtered_processes, actual_selected, self.scroll_offset, &self.theme, &self.search_query, self.search_mode);
+                processes::render(f, area, &filtered_processes, actual_selected, self.scroll_offset, &self.theme, &self.search_query, self.search_mode, self.tree_view, self.container_filter.as_deref(), self.processes_partial());
             },
-            2 => network::render(f, area, &self.network_data, &self.theme),
-            3 => disk::render(f, area, &self.disk_list, &self.theme),
+            2 => network::render(f, area, &self.snapshot.network, &self.snapshot.sockets, self.connections_view, self.scroll_offset, &self.theme),
+            3 => disk::render(f, area, &self.snapshot.disk_list, &self.snapshot.disk_io, &self.theme),
+            4 => gpu::render(f, area, &self.snapshot.gpus, &self.theme),
+            5 => sensors::render(f, area, &self.snapshot.sensors, &self.theme),
             _ => settings::render(f, area, &self.settings_state, &self.theme, self.paused),
         }
     }
@@ -240,10 +614,49 @@ impl Ui {
 
 
     fn render_footer(&self, f: &mut Frame, area: Rect) {
-        let footer_text = " [q]Quit [1-5]Panels [↑↓]Navigate [k]Kill [e]JSON [Ctrl+C]CSV [Ctrl+H]HTML [p]Pause [t]Theme ";
-        let footer = Paragraph::new(footer_text)
-            .style(Style::default().fg(Color::DarkGray));
-        f.render_widget(footer, area);
+        let k = &self.keys;
+        let footer_text = if self.replay.is_some() {
+            format!(" [{}]Quit [1-7]Panels [↑↓]Navigate [{}]Tree [{}]Group [{}]Conns [{}]Export [{}]Play/Pause [+/-]Speed [[ ]]Seek 10s [{{ }}]Seek 1m [{}]Theme ",
+                    k.quit, k.tree, k.group, k.connections, k.export, k.pause, k.theme)
+        } else if self.can_leave_host() {
+            format!(" [{}]Quit [Esc]Fleet [1-7]Panels [↑↓]Navigate [{}]Tree [{}]Group [{}]Conns [{}]JSON [Ctrl+C]CSV [Ctrl+H]HTML [{}]Pause [{}]Theme ",
+                    k.quit, k.tree, k.group, k.connections, k.export, k.pause, k.theme)
+        } else if self.is_read_only() {
+            format!(" [{}]Quit [1-7]Panels [↑↓]Navigate [{}]Tree [{}]Group [{}]Conns [{}]JSON [Ctrl+C]CSV [Ctrl+H]HTML [{}]Pause [{}]Theme ",
+                    k.quit, k.tree, k.group, k.connections, k.export, k.pause, k.theme)
+        } else {
+            format!(" [{}]Quit [1-7]Panels [↑↓]Navigate [{}]Kill [{}]Tree [{}]Group [{}]Conns [{}]JSON [Ctrl+C]CSV [Ctrl+H]HTML [{}]Pause [{}]Theme ",
+                    k.quit, k.kill, k.tree, k.group, k.connections, k.export, k.pause, k.theme)
+        };
+        f.render_widget(Paragraph::new(self.footer_line(footer_text)), area);
+    }
+    
+    /// Key hints, or in their place a rejected edit of the config file until
+    /// it is fixed, or a warning that was logged recently
+    fn footer_line(&self, hints: String) -> ratatui::text::Line<'static> {
+        use ratatui::text::{Line, Span};
+        
+        match &self.config_error {
+            Some(error) => {
+                // Errors span several lines; the first says where, the last what is wrong
+                let lines: Vec<&str> = error.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+                let summary = match (lines.first(), lines.last()) {
+                    (Some(first), Some(last)) if lines.len() > 1 => format!("{}: {}", first, last),
+                    _ => lines.concat(),
+                };
+                Line::from(vec![
+                    Span::styled(" ⚠ Config not reloaded: ", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
+                    Span::styled(summary, Style::default().fg(Color::Red)),
+                ])
+            }
+            None => match &self.warning {
+                Some((warning, at)) if at.elapsed() < WARNING_SHOWN => Line::from(vec![
+                    Span::styled(" ⚠ ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+                    Span::styled(warning.clone(), Style::default().fg(Color::Yellow)),
+                ]),
+                _ => Line::from(Span::styled(hints, Style::default().fg(Color::DarkGray))),
+            },
+        }
     }
 
     fn render_modal(&self, f: &mut Frame, area: Rect) {
@@ -253,6 +666,7 @@ impl Ui {
             ModalType::KillConfirm => ("⚠️  Kill Process", 70, 70, Color::Red),
             ModalType::ProcessDetail => ("ℹ️  Process Details", 70, 60, Color::Cyan),
             ModalType::ExportFormat => ("📤 Export Data", 60, 40, Color::Green),
+            ModalType::Inspect => ("ℹ️  Process Details", 85, 85, Color::Cyan),
             ModalType::None => ("Modal", 60, 20, Color::White),
         };
         
@@ -277,42 +691,35 @@ impl Ui {
 
     // Navigation methods
     pub fn set_active_panel(&mut self, index: usize) {
-        self.active_panel = index.min(4);
+        self.active_panel = index.min(SETTINGS_PANEL);
         self.scroll_offset = 0;
         
         // Reset settings category when entering settings panel
-        if self.active_panel == 4 {
+        if self.active_panel == SETTINGS_PANEL {
             self.settings_state.selected_category = 0;
         }
     }
-    
-    pub fn set_settings_category(&mut self, category: usize) {
-        if self.active_panel == 4 {
-            self.settings_state.selected_category = category.min(4);
-        }
-    }
 
     pub fn next_panel(&mut self) {
-        self.active_panel = (self.active_panel + 1) % 5;
+        self.active_panel = (self.active_panel + 1) % PANEL_TITLES.len();
         self.scroll_offset = 0;
     }
 
     pub fn scroll_up(&mut self) {
         match self.active_panel {
-            1 => {
+            1 if self.selected_process_index > 0 => {
                 // Processes panel - use filtered list length
-                if self.selected_process_index > 0 {
-                    self.selected_process_index -= 1;
-                    if self.selected_process_index < self.scroll_offset {
-                        self.scroll_offset = self.selected_process_index;
-                    }
+                self.selected_process_index -= 1;
+                if self.selected_process_index < self.scroll_offset {
+                    self.scroll_offset = self.selected_process_index;
                 }
             }
-            4 => {
+            2 if self.connections_view => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(1);
+            }
+            SETTINGS_PANEL if self.settings_state.selected_category > 0 => {
                 // Settings panel
-                if self.settings_state.selected_category > 0 {
-                    self.settings_state.selected_category -= 1;
-                }
+                self.settings_state.selected_category -= 1;
             }
             _ => {}
         }
@@ -322,21 +729,19 @@ impl Ui {
         match self.active_panel {
             1 => {
                 // Processes panel - use filtered list length
-                let max_index = if self.filtered_process_indices.is_empty() {
-                    0
-                } else {
-                    self.filtered_process_indices.len().saturating_sub(1)
-                };
+                let max_index = self.process_row_count().saturating_sub(1);
                 
                 if self.selected_process_index < max_index {
                     self.selected_process_index += 1;
                 }
             }
-            4 => {
+            2 if self.connections_view => {
+                let max_offset = network::connection_count(&self.snapshot.sockets).saturating_sub(1);
+                self.scroll_offset = (self.scroll_offset + 1).min(max_offset);
+            }
+            SETTINGS_PANEL if self.settings_state.selected_category < 4 => {
                 // Settings panel (5 categories: 0-4)
-                if self.settings_state.selected_category < 4 {
-                    self.settings_state.selected_category += 1;
-                }
+                self.settings_state.selected_category += 1;
             }
             _ => {}
         }
@@ -352,7 +757,10 @@ impl Ui {
                     self.selected_process_index = 0;
                 }
             }
-            4 => {
+            2 if self.connections_view => {
+                self.scroll_offset = self.scroll_offset.saturating_sub(10);
+            }
+            SETTINGS_PANEL => {
                 // Settings panel - jump to first category
                 self.settings_state.selected_category = 0;
             }
@@ -364,15 +772,15 @@ impl Ui {
         match self.active_panel {
             1 => {
                 // Processes panel - use filtered list length
-                let max_index = if self.filtered_process_indices.is_empty() {
-                    0
-                } else {
-                    self.filtered_process_indices.len().saturating_sub(1)
-                };
+                let max_index = self.process_row_count().saturating_sub(1);
                 
                 self.selected_process_index = (self.selected_process_index + 10).min(max_index);
             }
-            4 => {
+            2 if self.connections_view => {
+                let max_offset = network::connection_count(&self.snapshot.sockets).saturating_sub(1);
+                self.scroll_offset = (self.scroll_offset + 10).min(max_offset);
+            }
+            SETTINGS_PANEL => {
                 // Settings panel - jump to last category
                 self.settings_state.selected_category = 4;
             }
@@ -386,7 +794,8 @@ impl Ui {
                 self.selected_process_index = 0;
                 self.scroll_offset = 0;
             }
-            4 => {
+            2 => self.scroll_offset = 0,
+            SETTINGS_PANEL => {
                 self.settings_state.selected_category = 0;
             }
             _ => {}
@@ -397,62 +806,90 @@ impl Ui {
         match self.active_panel {
             1 => {
                 // Processes panel - use filtered list length
-                let max_index = if self.filtered_process_indices.is_empty() {
-                    0
-                } else {
-                    self.filtered_process_indices.len().saturating_sub(1)
-                };
+                let max_index = self.process_row_count().saturating_sub(1);
                 
                 self.selected_process_index = max_index;
             }
-            4 => {
+            2 if self.connections_view => {
+                self.scroll_offset = network::connection_count(&self.snapshot.sockets).saturating_sub(1);
+            }
+            SETTINGS_PANEL => {
                 self.settings_state.selected_category = 4;
             }
             _ => {}
         }
     }
 
-    pub fn show_details(&mut self) {
-        if self.active_panel == 1 && !self.filtered_process_indices.is_empty() {
+    /// Fetch the full detail of the selected process and open the detail view
+    pub async fn show_details(&mut self) {
+        if self.active_panel == 1 && self.group_view {
+            self.open_selected_group();
+        } else if self.active_panel == 1 && !self.filtered_process_indices.is_empty() {
             let actual_index = self.filtered_process_indices.get(self.selected_process_index);
             if let Some(&idx) = actual_index {
-                if let Some(process) = self.process_data.get(idx) {
-                    self.modal_message = format!(
-                        "Process Details\n\n\
-                        PID: {}\n\
-                        Name: {}\n\
-                        User: {}\n\
-                        CPU: {:.1}%\n\
-                        Memory: {:.1}%\n\
-                        Disk Read: {}\n\
-                        Disk Write: {}\n\
-                        Threads: {}\n\
-                        Status: {}\n\
-                        Executable: {}\n\n\
-                        Press ESC to close",
-                        process.pid,
-                        process.name,
-                        process.user,
-                        process.cpu_usage,
-                        process.memory_usage,
-                        crate::utils::format_bytes(process.disk_read),
-                        crate::utils::format_bytes(process.disk_write),
-                        process.threads,
-                        process.status,
-                        process.exe_path
-                    );
-                    self.modal_type = ModalType::ProcessDetail;
+                if let Some(process) = self.snapshot.processes.get(idx).cloned() {
+                    let detail = self.backend.process_detail(process.pid).await
+                        .map_err(|e| e.to_string());
+                    self.detail_view = Some(DetailView::new(process, detail));
+                    self.modal_type = ModalType::Inspect;
                     self.show_modal = true;
                 }
             }
         }
     }
+    
+    pub fn is_detail_view_open(&self) -> bool {
+        self.show_modal && self.modal_type == ModalType::Inspect
+    }
+    
+    pub fn detail_view_tab(&mut self, forward: bool) {
+        if let Some(view) = self.detail_view.as_mut() {
+            if forward {
+                view.next_tab();
+            } else {
+                view.previous_tab();
+            }
+        }
+    }
+    
+    pub fn detail_view_scroll(&mut self, delta: isize) {
+        if let Some(view) = self.detail_view.as_mut() {
+            view.scroll_by(delta);
+        }
+    }
+    
+    pub fn detail_view_scroll_to(&mut self, top: bool) {
+        if let Some(view) = self.detail_view.as_mut() {
+            view.scroll_to(top);
+        }
+    }
 
     pub async fn kill_selected_process(&mut self) -> Result<()> {
         if self.active_panel == 1 && !self.filtered_process_indices.is_empty() {
             let actual_index = self.filtered_process_indices.get(self.selected_process_index);
             if let Some(&idx) = actual_index {
-                if let Some(process) = self.process_data.get(idx) {
+                if let Some(process) = self.snapshot.processes.get(idx) {
+                    if self.process_management.is_protected(process.pid) {
+                        self.modal_message = format!(
+                            "╔════════════════════════════════════════════════╗\n\
+                             ║              🛡️  PROTECTED PROCESS              ║\n\
+                             ╚════════════════════════════════════════════════╝\n\
+                             \n\
+                             {} (PID {}) is protected and will not be killed.\n\
+                             \n\
+                             See protect_system_processes and protected_pids\n\
+                             in the [process_management] section.\n\
+                             \n\
+                             ┌──────────────────────────────────────────────┐\n\
+                             │  Press [ESC] to close this message           │\n\
+                             └──────────────────────────────────────────────┘",
+                            process.name,
+                            process.pid
+                        );
+                        self.modal_type = ModalType::ProcessDetail;
+                        self.show_modal = true;
+                        return Ok(());
+                    }
                     self.pending_kill_pid = Some(process.pid);
                     self.modal_message = format!(
                         "╔════════════════════════════════════════════════╗\n\
@@ -488,6 +925,9 @@ impl Ui {
                     );
                     self.modal_type = ModalType::KillConfirm;
                     self.show_modal = true;
+                    if !self.process_management.require_confirmation {
+                        self.confirm_kill().await?;
+                    }
                 }
             }
         }
@@ -498,7 +938,7 @@ impl Ui {
         if self.active_panel == 1 && !self.filtered_process_indices.is_empty() {
             let actual_index = self.filtered_process_indices.get(self.selected_process_index);
             if let Some(&idx) = actual_index {
-                if let Some(process) = self.process_data.get(idx) {
+                if let Some(process) = self.snapshot.processes.get(idx) {
                     let pid = process.pid;
                     let name = process.name.clone();
                     
@@ -555,7 +995,7 @@ impl Ui {
         if self.active_panel == 1 && !self.filtered_process_indices.is_empty() {
             let actual_index = self.filtered_process_indices.get(self.selected_process_index);
             if let Some(&idx) = actual_index {
-                if let Some(process) = self.process_data.get(idx) {
+                if let Some(process) = self.snapshot.processes.get(idx) {
                     let pid = process.pid;
                     let name = process.name.clone();
                     
@@ -611,8 +1051,74 @@ impl Ui {
         std::sync::Arc::new(tokio::sync::Mutex::new(sysinfo::System::new_all()))
     }
 
+    /// Pause the display, or the playback when showing a recording
     pub fn toggle_pause(&mut self) {
-        self.paused = !self.paused;
+        match &self.replay {
+            Some(replay) => replay.toggle_pause(),
+            None => self.paused = !self.paused,
+        }
+    }
+    
+    pub fn set_replay(&mut self, replay: Arc<ReplayBackend>) {
+        self.replay = Some(replay);
+    }
+    
+    pub fn is_replay(&self) -> bool {
+        self.replay.is_some()
+    }
+    
+    #[cfg(feature = "remote")]
+    pub fn set_remote(&mut self, remote: Arc<RemoteBackend>) {
+        self.remote = Some(remote);
+    }
+    
+    /// Whether the shown processes are out of reach of kill, suspend and resume
+    pub fn is_read_only(&self) -> bool {
+        #[cfg(feature = "remote")]
+        if self.remote.is_some() {
+            return true;
+        }
+        self.is_replay()
+    }
+    
+    /// Whether the agent may have left processes out of the list
+    fn processes_partial(&self) -> bool {
+        #[cfg(feature = "remote")]
+        if let Some(remote) = &self.remote {
+            return remote.processes_partial();
+        }
+        false
+    }
+    
+    /// Whether Esc leads from a host's panels back to the fleet table
+    pub fn can_leave_host(&self) -> bool {
+        #[cfg(feature = "remote")]
+        if let Some(fleet) = &self.fleet {
+            return fleet.open.is_some() && !self.search_mode && !self.show_modal && self.container_filter.is_none();
+        }
+        false
+    }
+    
+    /// Double (`true`) or halve (`false`) the playback speed
+    pub fn replay_speed(&mut self, faster: bool) {
+        if let Some(replay) = &self.replay {
+            if faster {
+                replay.faster();
+            } else {
+                replay.slower();
+            }
+        }
+    }
+    
+    pub fn replay_seek(&mut self, seconds: i64) {
+        if let Some(replay) = &self.replay {
+            replay.seek(seconds);
+            // History and alert durations only grow forward in time
+            if seconds < 0 {
+                self.history.clear();
+                self.alerts.reset();
+            }
+        }
     }
 
     pub fn toggle_theme(&mut self) {
@@ -624,17 +1130,8 @@ impl Ui {
     }
     
     pub fn export_data(&mut self, format: crate::export::ExportFormat) {
-        match crate::export::export_snapshot(
-            &self.cpu_data,
-            &self.memory_data,
-            &self.disk_data,
-            &self.network_data,
-            &self.battery_data,
-            &self.process_data,
-            &self.disk_list,
-            format,
-            None,
-        ) {
+        let include_timestamps = self.config.as_ref().map_or(true, |c| c.export.include_timestamps);
+        match crate::export::export_snapshot(&self.snapshot, format, None, include_timestamps) {
             Ok(filename) => {
                 // Get full path
                 let full_path = std::env::current_dir()
@@ -673,7 +1170,7 @@ impl Ui {
                         crate::export::ExportFormat::Json => "JSON",
                         crate::export::ExportFormat::Html => "HTML",
                     },
-                    self.process_data.len()
+                    self.snapshot.processes.len()
                 );
                 self.modal_type = ModalType::ProcessDetail;
                 self.show_modal = true;
@@ -717,10 +1214,14 @@ impl Ui {
             self.search_mode = false;
             self.search_query.clear();
             self.update_filtered_processes();
+        } else if !self.show_modal && self.container_filter.is_some() {
+            self.container_filter = None;
+            self.update_filtered_processes();
         } else {
             self.show_modal = false;
             self.modal_type = ModalType::None;
             self.pending_kill_pid = None;
+            self.detail_view = None;
         }
     }
     
@@ -795,7 +1296,7 @@ impl Ui {
         {
             use std::process::Command;
             let output = Command::new("kill")
-                .args(&["-9", &pid.to_string()])
+                .args(["-9", &pid.to_string()])
                 .output()?;
             
             if output.status.success() {
@@ -870,10 +1371,6 @@ impl Ui {
         self.search_mode
     }
     
-    pub fn is_in_settings_panel(&self) -> bool {
-        self.active_panel == 4
-    }
-    
     pub fn search_input(&mut self, c: char) {
         self.search_query.push(c);
         self.update_filtered_processes();
@@ -886,6 +1383,153 @@ impl Ui {
     }
 }
 
+#[cfg(feature = "remote")]
+impl Ui {
+    /// Whether the fleet table is shown instead of the panels of one host
+    pub fn is_fleet_overview(&self) -> bool {
+        self.fleet.as_ref().is_some_and(|fleet| fleet.open.is_none())
+    }
+    
+    /// Start on the fleet table instead of the panels of one host
+    pub fn enable_fleet(&mut self) {
+        let sort = FleetSort::default();
+        self.fleet = Some(FleetState {
+            rows: Vec::new(),
+            selected: 0,
+            sort,
+            order: sort.default_order(),
+            open: None,
+            request: None,
+        });
+    }
+    
+    /// Take over the latest host summaries
+    pub fn update_fleet(&mut self, rows: Vec<HostRow>) {
+        if let Some(state) = &mut self.fleet {
+            state.rows = rows;
+            sort_rows(&mut state.rows, state.sort, state.order);
+        }
+    }
+    
+    /// Row of the selected host; the selection follows the host when rows are re-sorted
+    fn fleet_position(state: &FleetState) -> usize {
+        state.rows.iter().position(|row| row.index == state.selected).unwrap_or(0)
+    }
+    
+    pub fn fleet_select(&mut self, delta: isize) {
+        if let Some(state) = &mut self.fleet {
+            if state.rows.is_empty() {
+                return;
+            }
+            let position = Self::fleet_position(state).saturating_add_signed(delta).min(state.rows.len() - 1);
+            state.selected = state.rows[position].index;
+        }
+    }
+    
+    /// Order by the next column, in its natural direction
+    pub fn fleet_sort_next(&mut self) {
+        if let Some(state) = &mut self.fleet {
+            state.sort = state.sort.next();
+            state.order = state.sort.default_order();
+            sort_rows(&mut state.rows, state.sort, state.order);
+        }
+    }
+    
+    pub fn fleet_reverse(&mut self) {
+        if let Some(state) = &mut self.fleet {
+            state.order = match state.order {
+                SortOrder::Asc => SortOrder::Desc,
+                SortOrder::Desc => SortOrder::Asc,
+            };
+            sort_rows(&mut state.rows, state.sort, state.order);
+        }
+    }
+    
+    pub fn open_selected_host(&mut self) {
+        if let Some(state) = &mut self.fleet {
+            if !state.rows.is_empty() {
+                state.request = Some(FleetRequest::Open(state.selected));
+            }
+        }
+    }
+    
+    pub fn leave_host(&mut self) {
+        if let Some(state) = &mut self.fleet {
+            state.request = Some(FleetRequest::Close);
+        }
+    }
+    
+    /// The host change requested since the last call, for the app to carry out
+    pub fn take_fleet_request(&mut self) -> Option<FleetRequest> {
+        self.fleet.as_mut()?.request.take()
+    }
+    
+    /// Show the normal panels for a host opened from the fleet table
+    pub fn enter_host(&mut self, index: usize, remote: Arc<RemoteBackend>) {
+        if let Some(state) = &mut self.fleet {
+            state.open = Some(index);
+        }
+        self.backend = remote.clone();
+        self.remote = Some(remote);
+        self.reset_host_view();
+    }
+    
+    pub fn show_fleet(&mut self) {
+        if let Some(state) = &mut self.fleet {
+            state.open = None;
+        }
+        self.remote = None;
+        self.reset_host_view();
+    }
+    
+    /// Forget what was shown for the previous host
+    fn reset_host_view(&mut self) {
+        self.snapshot = SystemSnapshot::default();
+        self.history.clear();
+        self.alerts.reset();
+        self.active_panel = 0;
+        self.scroll_offset = 0;
+        self.selected_process_index = 0;
+        self.collapsed_pids.clear();
+        self.update_filtered_processes();
+    }
+    
+    fn render_fleet(&self, f: &mut Frame, chunks: &[Rect]) {
+        let Some(state) = &self.fleet else {
+            return;
+        };
+        
+        let count = |health: HostHealth| state.rows.iter().filter(|row| row.health == health).count();
+        let (down, critical, warning) = (count(HostHealth::Down), count(HostHealth::Critical), count(HostHealth::Warning));
+        let header_text = format!(
+            " SYSTRIX FLEET │ {} hosts │ {} down │ {} critical │ {} warning ",
+            state.rows.len(), down, critical, warning
+        );
+        let color = if down + critical > 0 {
+            Color::Red
+        } else if warning > 0 {
+            Color::Yellow
+        } else {
+            self.theme.primary_color()
+        };
+        let header = Paragraph::new(header_text)
+            .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
+            .block(Block::default().borders(Borders::ALL));
+        f.render_widget(header, chunks[0]);
+        
+        let tabs = Tabs::new(vec!["Fleet"])
+            .block(Block::default().borders(Borders::ALL))
+            .select(0)
+            .highlight_style(Style::default().fg(self.theme.primary_color()).add_modifier(Modifier::BOLD));
+        f.render_widget(tabs, chunks[1]);
+        
+        fleet::render(f, chunks[2], &state.rows, Self::fleet_position(state), state.sort, state.order, &self.theme);
+        
+        let hints = format!(" [{}]Quit [↑↓]Select [Enter]Open host [o]Sort [O]Reverse [{}]Theme ", self.keys.quit, self.keys.theme);
+        f.render_widget(Paragraph::new(self.footer_line(hints)), chunks[3]);
+    }
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
         .direction(Direction::Vertical)
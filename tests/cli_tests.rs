@@ -6,7 +6,7 @@ use predicates::prelude::*;
 
 #[test]
 fn test_version_command() {
-    let mut cmd = assert_cmd::cargo::cargo_bin("systrix");
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
     cmd.arg("version");
     
     cmd.assert()
@@ -16,7 +16,7 @@ fn test_version_command() {
 
 #[test]
 fn test_info_command() {
-    let mut cmd = assert_cmd::cargo::cargo_bin("systrix");
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
     cmd.arg("info");
     
     cmd.assert()
@@ -26,9 +26,41 @@ fn test_info_command() {
         .stdout(predicate::str::contains("Disk"));
 }
 
+#[cfg(target_os = "linux")]
+#[test]
+fn test_disk_io_command() {
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.args(["disk", "--io"]);
+    
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("UTIL%"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_sockets_listen() {
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.args(["sockets", "--listen", "--port", "1"]);
+    
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("PROTO"));
+}
+
+#[test]
+fn test_gpu_json() {
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.args(["gpu", "--json"]);
+    
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("["));
+}
+
 #[test]
 fn test_ps_command() {
-    let mut cmd = assert_cmd::cargo::cargo_bin("systrix");
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
     cmd.arg("ps").arg("--limit").arg("5");
     
     cmd.assert()
@@ -37,9 +69,62 @@ fn test_ps_command() {
         .stdout(predicate::str::contains("NAME"));
 }
 
+#[test]
+fn test_ps_sort_by_name_ascending() {
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("ps").arg("--sort").arg("name").arg("--order").arg("asc").arg("--limit").arg("3");
+    
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Showing"));
+}
+
+#[test]
+fn test_ps_tree() {
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("ps").arg("--tree").arg("--limit").arg("5");
+    
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("TREE"));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_inspect_json() {
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("inspect").arg(std::process::id().to_string()).arg("--json");
+    
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\"cmdline\""))
+        .stdout(predicate::str::contains("\"limits\""));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_cgroups_command() {
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("cgroups").arg("--limit").arg("5");
+    
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("CGROUP"));
+}
+
+#[test]
+fn test_ps_rejects_unknown_sort() {
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("ps").arg("--sort").arg("bogus");
+    
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid value"));
+}
+
 #[test]
 fn test_net_command() {
-    let mut cmd = assert_cmd::cargo::cargo_bin("systrix");
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
     cmd.arg("net");
     
     cmd.assert()
@@ -49,7 +134,7 @@ fn test_net_command() {
 
 #[test]
 fn test_disk_command() {
-    let mut cmd = assert_cmd::cargo::cargo_bin("systrix");
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
     cmd.arg("disk");
     
     cmd.assert()
@@ -59,7 +144,7 @@ fn test_disk_command() {
 
 #[test]
 fn test_kill_without_pid() {
-    let mut cmd = assert_cmd::cargo::cargo_bin("systrix");
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
     cmd.arg("kill");
     
     // Should fail because PID is required
@@ -68,7 +153,7 @@ fn test_kill_without_pid() {
 
 #[test]
 fn test_kill_system_process_without_force() {
-    let mut cmd = assert_cmd::cargo::cargo_bin("systrix");
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
     cmd.arg("kill").arg("1");
     
     // Should fail or warn about killing system process
@@ -77,6 +162,27 @@ fn test_kill_system_process_without_force() {
         .stderr(predicate::str::contains("system process"));
 }
 
+#[test]
+fn test_record_command() {
+    use tempfile::tempdir;
+    
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("session.ndjson");
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.args(["record", "--interval", "100ms", "--duration", "300ms", "-o"])
+        .arg(&path);
+    
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Recorded"));
+    
+    let content = std::fs::read_to_string(&path).unwrap();
+    let header = content.lines().next().unwrap();
+    assert!(header.contains("\"format\":\"systrix-recording\""));
+    assert!(content.lines().count() > 1);
+}
+
 #[test]
 fn test_report_command() {
     use std::fs;
@@ -85,7 +191,7 @@ fn test_report_command() {
     let dir = tempdir().unwrap();
     let report_path = dir.path().join("test_report.json");
     
-    let mut cmd = assert_cmd::cargo::cargo_bin("systrix");
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
     cmd.arg("report")
         .arg("--output")
         .arg(report_path.to_str().unwrap());
@@ -97,5 +203,137 @@ fn test_report_command() {
     
     // Verify it's valid JSON
     let content = fs::read_to_string(&report_path).unwrap();
-    let _json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert!(json["timestamp"].is_string());
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.env("SYSTRIX_EXPORT_INCLUDE_TIMESTAMPS", "false")
+        .arg("report")
+        .arg("--output")
+        .arg(report_path.to_str().unwrap());
+    cmd.assert().success();
+    
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+    assert!(json.get("timestamp").is_none());
+}
+
+#[test]
+fn test_fleet_needs_hosts() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.toml");
+    std::fs::write(&config, "[general]\nrefresh_interval_ms = 500\n").unwrap();
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("--config").arg(&config).arg("tui").arg("--fleet");
+    
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("no [[hosts]]"));
+}
+
+#[test]
+fn test_history_reads_the_store() {
+    use systrix::monitor::store::{MetricsStore, StoreConfig, StoredSnapshot};
+    use systrix::monitor::MetricsSample;
+    
+    let dir = tempfile::tempdir().unwrap();
+    let history = dir.path().join("history");
+    let config = dir.path().join("config.toml");
+    std::fs::write(&config, format!("[history]\npath = {:?}\n", history.to_str().unwrap())).unwrap();
+    
+    let mut store = MetricsStore::open(StoreConfig::new(&history)).unwrap();
+    let start = chrono::Utc::now() - chrono::Duration::minutes(30);
+    for minute in 0..3 {
+        store.append(&StoredSnapshot {
+            metrics: MetricsSample {
+                timestamp: start + chrono::Duration::minutes(minute),
+                cpu: Some(10.0 * (minute + 1) as f32),
+                ..Default::default()
+            },
+            processes: Vec::new(),
+        }).unwrap();
+    }
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("--config").arg(&config)
+        .args(["history", "--metric", "cpu.global", "--from", "2h", "--format", "csv"]);
+    
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with("timestamp,cpu.global\n"))
+        .stdout(predicate::str::contains(",10\n"))
+        .stdout(predicate::str::contains(",30\n"));
+}
+
+#[test]
+fn test_alerts_check_fails_when_a_rule_fires() {
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.args(["alerts", "check", "--rule", "memory.percent >= 0", "--rule", "memory.percent > 100"]);
+    
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("FIRING"))
+        .stdout(predicate::str::contains("1 of 2 rules firing"))
+        .stderr(predicate::str::contains("1 alert rule(s) firing"));
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.args(["alerts", "check", "--rule", "memory.percent >= 0 for 5m"]);
+    
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("MET*"))
+        .stdout(predicate::str::contains("0 of 1 rules firing"))
+        .stderr(predicate::str::contains("1 with their condition met (duration not evaluated)"));
+}
+
+#[test]
+fn test_config_init_and_validate() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("systrix").join("config.toml");
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("--config").arg(&config).args(["config", "init"]);
+    cmd.assert().success();
+    assert_eq!(std::fs::read_to_string(&config).unwrap(), systrix::config::DEFAULT_FILE);
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("--config").arg(&config).args(["config", "init"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("--config").arg(&config).args(["config", "validate"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("is valid"));
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("--config").arg(&config).args(["config", "show"])
+        .env("SYSTRIX_TUI_PROCESS_LIMIT", "7")
+        .env("SYSTRIX_REMOTE_TOKEN", "secret");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("process_limit = 7"))
+        .stdout(predicate::str::contains("secret").not());
+    
+    std::fs::write(&config, "[tui]\nprocess_limit = 0\n").unwrap();
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("--config").arg(&config).args(["config", "validate"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("tui.process_limit: must be at least 1"));
+}
+
+#[test]
+fn test_kill_refuses_protected_pids() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.toml");
+    std::fs::write(&config, format!("[process_management]\nprotected_pids = [{}]\n", std::process::id())).unwrap();
+    
+    let mut cmd = Command::cargo_bin("systrix").unwrap();
+    cmd.arg("--config").arg(&config).args(["kill", &std::process::id().to_string()]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("protected in [process_management]"));
 }
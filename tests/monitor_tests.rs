@@ -1,7 +1,7 @@
 // tests/monitor_tests.rs
 //! Integration tests for monitoring functionality.
 
-use systrix::monitor::{MonitorBackend, SysinfoBackend};
+use systrix::monitor::{MonitorBackend, ProcessSort, SortOrder, SysinfoBackend};
 
 #[allow(unused_imports)]
 use systrix::monitor::ProcessManager;
@@ -59,14 +59,34 @@ async fn test_network_snapshot() {
     let snapshot = result.unwrap();
     
     // Network interfaces may be empty on some systems
-    assert!(snapshot.total_rx >= 0);
-    assert!(snapshot.total_tx >= 0);
+    let rx: u64 = snapshot.interfaces.iter().map(|i| i.received).sum();
+    let tx: u64 = snapshot.interfaces.iter().map(|i| i.transmitted).sum();
+    assert_eq!(snapshot.total_rx, rx);
+    assert_eq!(snapshot.total_tx, tx);
+}
+
+#[tokio::test]
+async fn test_network_rates_use_previous_sample() {
+    let backend = SysinfoBackend::new();
+    
+    // First sample has no baseline, so every rate is zero
+    let first = backend.network_snapshot().await.unwrap();
+    assert_eq!(first.total_rx_rate, 0);
+    assert_eq!(first.total_tx_rate, 0);
+    
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    let second = backend.network_snapshot().await.unwrap();
+    
+    let rx_rate: u64 = second.interfaces.iter().map(|i| i.rx_rate).sum();
+    let tx_rate: u64 = second.interfaces.iter().map(|i| i.tx_rate).sum();
+    assert_eq!(second.total_rx_rate, rx_rate);
+    assert_eq!(second.total_tx_rate, tx_rate);
 }
 
 #[tokio::test]
 async fn test_process_list() {
     let backend = SysinfoBackend::new();
-    let result = backend.process_list(None, "cpu", 10).await;
+    let result = backend.process_list(None, ProcessSort::Cpu, SortOrder::Desc, 10).await;
     
     assert!(result.is_ok());
     let processes = result.unwrap();
@@ -80,19 +100,37 @@ async fn test_process_list() {
     }
 }
 
+#[tokio::test]
+async fn test_process_list_sort_and_limit() {
+    let backend = SysinfoBackend::new();
+    
+    let processes = backend.process_list(None, ProcessSort::Pid, SortOrder::Asc, 5).await.unwrap();
+    assert!(!processes.is_empty());
+    assert!(processes.len() <= 5);
+    assert!(processes.windows(2).all(|w| w[0].pid < w[1].pid));
+    
+    let processes = backend.process_list(None, ProcessSort::Mem, SortOrder::Desc, 5).await.unwrap();
+    assert!(processes.windows(2).all(|w| w[0].memory_usage >= w[1].memory_usage));
+    
+    let processes = backend.process_list(None, ProcessSort::Io, SortOrder::Desc, 5).await.unwrap();
+    assert!(processes.windows(2).all(|w| {
+        w[0].disk_read_rate + w[0].disk_write_rate >= w[1].disk_read_rate + w[1].disk_write_rate
+    }));
+}
+
 #[tokio::test]
 async fn test_process_list_with_filter() {
     let backend = SysinfoBackend::new();
     
     // Filter for a common process name
-    let result = backend.process_list(Some("test"), "cpu", 10).await;
+    let result = backend.process_list(Some("test"), ProcessSort::Cpu, SortOrder::Desc, 10).await;
     
     assert!(result.is_ok());
     let processes = result.unwrap();
     
-    // All returned processes should match the filter
+    // All returned processes should match the filter by name or owner
     for proc in &processes {
-        assert!(proc.name.to_lowercase().contains("test"));
+        assert!(proc.name.to_lowercase().contains("test") || proc.user.to_lowercase().contains("test"));
     }
 }
 
@@ -112,3 +150,593 @@ async fn test_disk_list() {
         assert!(disk.total > 0);
     }
 }
+
+#[tokio::test]
+async fn test_collector_publishes_every_metric() {
+    use std::sync::Arc;
+    use systrix::monitor::{Collector, CollectorConfig};
+    
+    let mut collector = Collector::spawn(
+        Arc::new(SysinfoBackend::new()),
+        CollectorConfig::with_refresh_interval(100),
+    );
+    let snapshot = collector.wait_ready().await;
+    
+    assert!(snapshot.cpu.is_some());
+    assert!(snapshot.memory.is_some());
+    assert!(snapshot.disk.is_some());
+    assert!(snapshot.network.is_some());
+    assert!(!snapshot.processes.is_empty());
+    
+    // Later reads come from the cache and never block on sampling
+    let cached = collector.latest();
+    assert!(cached.cpu.is_some());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_process_owner_resolved_to_name() {
+    let backend = SysinfoBackend::new();
+    let own_pid = std::process::id();
+    
+    let processes = backend.process_list(None, ProcessSort::Pid, SortOrder::Asc, usize::MAX).await.unwrap();
+    let own = processes.iter().find(|p| p.pid == own_pid).expect("test process should be listed");
+    
+    // The numeric id is kept separately from the resolved name
+    let uid = own.uid.expect("uid should be numeric on unix");
+    assert!(!own.user.is_empty());
+    if own.user != uid.to_string() {
+        let by_user = backend.process_list(Some(&own.user), ProcessSort::Pid, SortOrder::Asc, usize::MAX).await.unwrap();
+        assert!(by_user.iter().any(|p| p.pid == own_pid));
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_process_detail_of_self() {
+    let backend = SysinfoBackend::new();
+    
+    let detail = backend.process_detail(std::process::id()).await.unwrap();
+    assert_eq!(detail.pid, std::process::id());
+    assert!(!detail.cmdline.is_empty());
+    assert!(detail.cwd.is_some());
+    assert!(detail.open_files.iter().any(|file| file.fd <= 2));
+    assert!(detail.maps.regions > 0);
+    assert!(detail.limits.iter().any(|limit| limit.name == "Max open files"));
+}
+
+#[tokio::test]
+async fn test_process_detail_of_missing_pid() {
+    let backend = SysinfoBackend::new();
+    
+    assert!(backend.process_detail(u32::MAX).await.is_err());
+}
+
+#[tokio::test]
+async fn test_sensors_snapshot_from_fixture() {
+    let backend = SysinfoBackend::new().with_sysfs_root("tests/fixtures/host/sys");
+    
+    let sensors = backend.sensors_snapshot().await.unwrap();
+    let fans: Vec<_> = sensors.fans.iter().map(|f| (f.label.as_str(), f.rpm, f.is_stalled())).collect();
+    assert_eq!(fans, vec![("thinkpad fan1", 2100, false), ("thinkpad fan2", 0, true)]);
+    
+    // Temperatures come from the same root, not from the machine running the test
+    let hwmon = systrix::monitor::sensors::read_hwmon(std::path::Path::new("tests/fixtures/host/sys"));
+    assert_eq!(sensors.temperatures, hwmon.temperatures);
+}
+
+#[test]
+fn test_hwmon_fixture_temperatures() {
+    use systrix::monitor::sensors::{read_hwmon, SensorStatus};
+    
+    let readings = read_hwmon(std::path::Path::new("tests/fixtures/host/sys"));
+    let package = readings.temperatures.iter().find(|t| t.label == "coretemp Package id 0").unwrap();
+    assert_eq!(package.current, 45.0);
+    assert_eq!(package.critical, Some(100.0));
+    assert_eq!(package.status(), SensorStatus::Normal);
+    
+    let core = readings.temperatures.iter().find(|t| t.label == "coretemp Core 0").unwrap();
+    assert_eq!(core.status(), SensorStatus::Hot);
+    assert!(readings.temperatures.iter().any(|t| t.label == "thinkpad temp1" && t.critical.is_none()));
+}
+
+#[tokio::test]
+async fn test_drm_gpus_from_fixture() {
+    use systrix::monitor::gpu::{DrmGpuBackend, GpuBackend, GpuVendor};
+    
+    let backend = DrmGpuBackend::new("tests/fixtures/host/sys");
+    let gpus = backend.gpus().await.unwrap();
+    
+    // Connector directories such as card0-DP-1 are not GPUs
+    assert_eq!(gpus.len(), 2);
+    
+    let amd = &gpus[0];
+    assert_eq!(amd.vendor, GpuVendor::Amd);
+    assert_eq!(amd.driver, "amdgpu");
+    assert_eq!(amd.bus_id.as_deref(), Some("0000:03:00.0"));
+    assert_eq!(amd.utilization, Some(42.0));
+    assert_eq!(amd.memory_total, Some(17163091968));
+    assert_eq!(amd.core_clock_mhz, Some(1800));
+    assert_eq!(amd.memory_clock_mhz, Some(1000));
+    assert_eq!(amd.temperature, Some(61.0));
+    assert_eq!(amd.power_watts, Some(45.0));
+    
+    // i915 has no busy counter; utilization needs a second RC6 sample
+    let intel = &gpus[1];
+    assert_eq!(intel.index, 1);
+    assert_eq!(intel.driver, "i915");
+    assert_eq!(intel.core_clock_mhz, Some(300));
+    assert_eq!(intel.memory_total, None);
+    assert_eq!(intel.utilization, None);
+    
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    let gpus = backend.gpus().await.unwrap();
+    assert!(gpus[1].utilization.is_some());
+}
+
+#[tokio::test]
+async fn test_gpu_list_uses_sysfs_root() {
+    let backend = SysinfoBackend::new().with_sysfs_root("tests/fixtures/host/sys");
+    
+    let gpus = backend.gpu_list().await.unwrap();
+    assert!(gpus.iter().any(|gpu| gpu.driver == "amdgpu"));
+    assert!(gpus.iter().any(|gpu| gpu.driver == "i915"));
+    
+    let backend = SysinfoBackend::new().with_sysfs_root("tests/fixtures/host/sys").without_gpu();
+    assert!(backend.gpu_list().await.unwrap().is_empty());
+}
+
+#[test]
+fn test_batteries_from_fixture() {
+    use systrix::monitor::battery::read_power_supply;
+    
+    // Mains, empty bays and peripheral batteries are skipped
+    let batteries = read_power_supply(std::path::Path::new("tests/fixtures/host/sys"));
+    let names: Vec<_> = batteries.iter().map(|b| b.name.as_str()).collect();
+    assert_eq!(names, vec!["BAT0", "BAT1"]);
+    
+    let bat0 = &batteries[0];
+    assert_eq!(bat0.percentage, 80.0);
+    assert_eq!(bat0.energy_now, Some(40.0));
+    assert_eq!(bat0.time_remaining, Some(4 * 3600));
+    assert!((bat0.health - 83.3).abs() < 0.1);
+    
+    // Charge based battery without a capacity file, converted with voltage_now
+    let bat1 = &batteries[1];
+    assert_eq!(bat1.percentage, 50.0);
+    assert_eq!(bat1.energy_now, Some(24.0));
+    assert_eq!(bat1.power_now, Some(12.0));
+    assert_eq!(bat1.time_remaining, Some(2 * 3600));
+}
+
+#[tokio::test]
+async fn test_combined_battery_uses_sysfs_root() {
+    let backend = SysinfoBackend::new().with_sysfs_root("tests/fixtures/host/sys");
+    
+    let batteries = backend.batteries().await.unwrap();
+    let combined = systrix::monitor::battery::combine(&batteries);
+    assert_eq!(combined.name, "BAT0+BAT1");
+    assert_eq!(combined.status, "Discharging");
+    assert_eq!(combined.energy_now, Some(64.0));
+    // Weighted by energy: 64 Wh of 98 Wh
+    assert!((combined.percentage - 65.3).abs() < 0.1);
+    assert_eq!(combined.time_remaining, Some((64.0 / 22.0 * 3600.0) as u64));
+}
+
+#[test]
+fn test_diskstats_fixture_mapped_to_mounts() {
+    use std::time::{Duration, Instant};
+    use systrix::monitor::diskio::{map_mounts, parse_diskstats, read_dm_names, DiskIoTracker};
+    use systrix::monitor::DiskInfo;
+    
+    let content = std::fs::read_to_string("tests/fixtures/host/proc/diskstats").unwrap();
+    let devices = parse_diskstats(&content);
+    assert_eq!(devices.len(), 4);
+    
+    let mut tracker = DiskIoTracker::new();
+    let start = Instant::now();
+    tracker.update(devices.clone(), start);
+    let mut stats = tracker.update(devices, start + Duration::from_secs(1));
+    assert!(stats.iter().all(|s| s.read_bytes_per_sec == 0 && s.utilization == 0.0));
+    
+    let disk = |name: &str, mount_point: &str| DiskInfo {
+        name: name.to_string(),
+        mount_point: mount_point.to_string(),
+        fs_type: "ext4".to_string(),
+        total: 0,
+        used: 0,
+        available: 0,
+        usage_percent: 0.0,
+        is_removable: false,
+    };
+    let disks = vec![disk("/dev/mapper/vg-root", "/"), disk("/dev/nvme0n1p1", "/boot/efi")];
+    map_mounts(&mut stats, &disks, &read_dm_names(std::path::Path::new("tests/fixtures/host/sys")));
+    
+    let mounts = |device: &str| stats.iter().find(|s| s.device == device).unwrap().mount_points.clone();
+    assert_eq!(mounts("dm-0"), vec!["/"]);
+    assert_eq!(mounts("nvme0n1p1"), vec!["/boot/efi"]);
+    assert!(mounts("nvme0n1").is_empty());
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_memory_detail_from_meminfo() {
+    let backend = SysinfoBackend::new();
+    
+    let memory = backend.memory_snapshot().await.unwrap();
+    let detail = memory.detail.expect("meminfo should be readable on Linux");
+    assert!(detail.total > 0);
+    assert!(detail.commit_limit > 0);
+    assert_eq!(detail.breakdown().iter().map(|(_, bytes)| bytes).sum::<u64>(), detail.total);
+}
+
+#[test]
+fn test_system_pressure_from_fixture() {
+    use systrix::monitor::pressure::read_system_pressure;
+    use systrix::monitor::SystemSnapshot;
+    
+    let pressure = read_system_pressure(std::path::Path::new("tests/fixtures/host/proc")).unwrap();
+    assert_eq!(pressure.memory.unwrap().some.avg10, 12.75);
+    assert_eq!(pressure.cpu.unwrap().full.unwrap().total_us, 0);
+    assert!(read_system_pressure(std::path::Path::new("tests/fixtures/host/sys")).is_err());
+    
+    let snapshot = SystemSnapshot {
+        pressure: Some(pressure),
+        ..Default::default()
+    };
+    assert_eq!(snapshot.metric("psi.memory.full.avg60"), Some(3.3f32 as f64));
+    assert_eq!(snapshot.metric("psi.io.some.total"), Some(345678.0));
+    assert_eq!(snapshot.metric("psi.gpu.some.avg10"), None);
+}
+
+#[cfg(target_endian = "little")]
+#[test]
+fn test_sockets_from_fixture() {
+    use systrix::monitor::sockets::{list_sockets, SocketProtocol, SocketState};
+    
+    let sockets = list_sockets(std::path::Path::new("tests/fixtures/host/proc")).unwrap();
+    assert_eq!(sockets.len(), 4);
+    
+    // Sorted by protocol with listening sockets first
+    let cups = &sockets[0];
+    assert_eq!(cups.protocol, SocketProtocol::Tcp);
+    assert_eq!(cups.state, SocketState::Listen);
+    assert_eq!(cups.local_port(), Some(631));
+    assert_eq!(cups.owner_display(), "cupsd (4242)");
+    
+    assert_eq!(sockets[1].state, SocketState::Established);
+    assert_eq!(sockets[1].remote_port(), Some(443));
+    assert_eq!(sockets[1].pid, None);
+    
+    let listening: Vec<_> = sockets.iter().filter(|s| s.is_listening()).map(|s| s.protocol).collect();
+    assert_eq!(listening, vec![SocketProtocol::Tcp, SocketProtocol::Udp, SocketProtocol::Unix]);
+    assert_eq!(sockets[3].path.as_deref(), Some("/run/cups/cups.sock"));
+    assert_eq!(sockets[3].pid, Some(4242));
+}
+
+#[tokio::test]
+async fn test_replay_backend_serves_recorded_snapshots() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use systrix::monitor::collector::collect_once;
+    use systrix::monitor::replay::{Frame, RecordingHeader, RecordingWriter};
+    use systrix::monitor::{CollectorConfig, ReplayBackend};
+    
+    let live = collect_once(Arc::new(SysinfoBackend::new()), CollectorConfig::default().process_limit(10)).await;
+    
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.ndjson");
+    let file = std::fs::File::create(&path).unwrap();
+    let mut writer = RecordingWriter::new(file, &RecordingHeader::new(None, Duration::from_secs(1))).unwrap();
+    writer.write_frame(&Frame { timestamp: chrono::Utc::now(), snapshot: live.clone() }).unwrap();
+    
+    let replay = ReplayBackend::open(&path).unwrap();
+    let cpu = replay.cpu_snapshot().await.unwrap();
+    assert_eq!(cpu.model, live.cpu.unwrap().model);
+    
+    let processes = replay.process_list(None, ProcessSort::Pid, SortOrder::Asc, 3).await.unwrap();
+    assert!(processes.len() <= 3);
+    assert!(processes.windows(2).all(|w| w[0].pid < w[1].pid));
+    assert!(replay.process_detail(1).await.is_err());
+}
+
+fn fixture_backend() -> systrix::monitor::ProcfsBackend {
+    systrix::monitor::ProcfsBackend::with_root("tests/fixtures/host")
+}
+
+#[tokio::test]
+async fn test_procfs_cpu_snapshot() {
+    let cpu = fixture_backend().cpu_snapshot().await.unwrap();
+    
+    assert_eq!(cpu.model, "Intel(R) Core(TM) i7-8650U CPU @ 1.90GHz");
+    assert_eq!(cpu.physical_cores, 2);
+    assert_eq!(cpu.logical_cores, 4);
+    assert_eq!(cpu.per_core_usage, vec![0.0; 4]);
+    assert_eq!(cpu.frequency, 2100.0);
+    assert_eq!(cpu.load_average, (0.52, 0.58, 0.59));
+    assert_eq!(cpu.uptime, 12345);
+    assert_eq!(cpu.os_name, "Debian GNU/Linux 12");
+    assert_eq!(cpu.hostname, "fixture-host");
+}
+
+#[tokio::test]
+async fn test_procfs_memory_snapshot() {
+    let memory = fixture_backend().memory_snapshot().await.unwrap();
+    
+    assert_eq!(memory.total, 16384000 * 1024);
+    assert_eq!(memory.available, 8192000 * 1024);
+    assert_eq!(memory.used, 8192000 * 1024);
+    assert_eq!(memory.usage_percent, 50.0);
+    assert_eq!(memory.swap_used, 1024000 * 1024);
+    assert_eq!(memory.swap_usage_percent, 25.0);
+    assert_eq!(memory.detail.unwrap().buffers, 512000 * 1024);
+}
+
+#[tokio::test]
+async fn test_procfs_disks() {
+    let backend = fixture_backend();
+    let disks = backend.disk_list().await.unwrap();
+    
+    let mounts: Vec<_> = disks.iter().map(|d| (d.name.as_str(), d.mount_point.as_str(), d.fs_type.as_str())).collect();
+    assert_eq!(mounts, vec![
+        ("/dev/mapper/vg-root", "/", "ext4"),
+        ("/dev/nvme0n1p1", "/boot/efi", "vfat"),
+        ("/dev/sda1", "/media/dev/USB STICK", "vfat"),
+    ]);
+    let removable: Vec<_> = disks.iter().map(|d| d.is_removable).collect();
+    assert_eq!(removable, vec![false, false, true]);
+    // Capacity is only measured on the live system
+    assert_eq!(backend.disk_snapshot().await.unwrap().total, 0);
+    
+    let io = backend.disk_io().await.unwrap();
+    let dm = io.iter().find(|d| d.device == "dm-0").unwrap();
+    assert_eq!(dm.mount_points, vec!["/"]);
+}
+
+#[tokio::test]
+async fn test_procfs_network_snapshot() {
+    let network = fixture_backend().network_snapshot().await.unwrap();
+    
+    assert_eq!(network.interfaces.len(), 2);
+    let eth0 = network.interfaces.iter().find(|i| i.name == "eth0").unwrap();
+    assert_eq!(eth0.received, 987654321);
+    assert_eq!(eth0.transmitted, 123456789);
+    assert_eq!(eth0.packets_received, 654321);
+    assert_eq!(eth0.errors_received, 3);
+    assert_eq!(eth0.errors_transmitted, 1);
+    assert_eq!(network.total_rx, 987654321 + 524288);
+    assert_eq!(network.total_rx_rate, 0);
+}
+
+#[tokio::test]
+async fn test_procfs_process_list() {
+    let backend = fixture_backend();
+    let processes = backend.process_list(None, ProcessSort::Pid, SortOrder::Asc, 10).await.unwrap();
+    
+    let pids: Vec<u32> = processes.iter().map(|p| p.pid).collect();
+    assert_eq!(pids, vec![1, 4242, 5000]);
+    
+    let cupsd = &processes[1];
+    assert_eq!(cupsd.name, "cupsd");
+    assert_eq!(cupsd.ppid, Some(1));
+    assert_eq!(cupsd.user, "lp");
+    assert_eq!(cupsd.uid, Some(7));
+    assert_eq!(cupsd.effective_group, "lp");
+    assert_eq!(cupsd.status, "Sleep");
+    assert_eq!(cupsd.threads, 2);
+    assert_eq!(cupsd.start_time, 1760000000 + 50);
+    assert_eq!(cupsd.memory_usage, 0.1);
+    assert_eq!((cupsd.disk_read, cupsd.disk_write), (4096, 8192));
+    assert_eq!(cupsd.exe_path, "/usr/sbin/cupsd");
+    assert_eq!(cupsd.cgroup.as_deref(), Some("/system.slice/cups.service"));
+    assert_eq!(cupsd.container.as_ref().map(|c| c.id.as_str()), Some("cups.service"));
+    assert_eq!(processes[0].ppid, None);
+    assert_eq!(processes[2].status, "Run");
+    
+    let filtered = backend.process_list(Some("dev"), ProcessSort::Mem, SortOrder::Desc, 10).await.unwrap();
+    assert_eq!(filtered.iter().map(|p| p.pid).collect::<Vec<_>>(), vec![5000]);
+    
+    let detail = backend.process_detail(4242).await.unwrap();
+    assert_eq!(detail.cmdline, vec!["/usr/sbin/cupsd"]);
+}
+
+#[tokio::test]
+async fn test_procfs_signals_stay_inside_the_fixture() {
+    let backend = fixture_backend();
+    
+    assert!(backend.kill_process(99999, "SIGTERM").await.unwrap_err().to_string().contains("not found"));
+    // PID 4242 exists in the fixture but must never be signalled on the host
+    assert!(backend.suspend_process(4242).await.unwrap_err().to_string().contains("live system"));
+}
+
+#[cfg(target_os = "linux")]
+#[tokio::test]
+async fn test_procfs_live_system() {
+    let backend = systrix::monitor::ProcfsBackend::new();
+    
+    let cpu = backend.cpu_snapshot().await.unwrap();
+    assert!(cpu.logical_cores > 0);
+    assert_eq!(cpu.per_core_usage.len(), cpu.logical_cores);
+    
+    let processes = backend.process_list(None, ProcessSort::Pid, SortOrder::Asc, usize::MAX).await.unwrap();
+    assert!(processes.iter().any(|p| p.pid == std::process::id()));
+    
+    let disks = backend.disk_list().await.unwrap();
+    assert!(disks.iter().all(|d| d.used <= d.total));
+}
+
+#[cfg(feature = "remote")]
+#[tokio::test]
+async fn test_remote_backend_reads_the_agent() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use systrix::monitor::remote::{ConnectionStatus, RemoteBackend};
+    use systrix::remote_agent::{RemoteAgent, RemoteConfig};
+    
+    let agent = RemoteAgent::with_backend(Arc::new(fixture_backend()), RemoteConfig {
+        bind: "127.0.0.1".to_string(),
+        port: 0,
+        token: "secret".to_string(),
+        refresh_interval_ms: 500,
+        process_limit: 1,
+        enable_cors: false,
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let router = agent.router();
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    
+    let remote = RemoteBackend::new(&url, Some("secret".to_string())).unwrap().cache_for(Duration::ZERO);
+    assert_eq!(remote.status(), ConnectionStatus::Connecting);
+    
+    // The agent's collector publishes its first samples shortly after starting
+    let mut cpu = remote.cpu_snapshot().await;
+    for _ in 0..50 {
+        if cpu.is_ok() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        cpu = remote.cpu_snapshot().await;
+    }
+    assert_eq!(cpu.unwrap().hostname, "fixture-host");
+    assert_eq!(remote.status(), ConnectionStatus::Connected);
+    assert_eq!(remote.process_detail(4242).await.unwrap().pid, 4242);
+    
+    // The agent publishes one process, but lists and sorts all three
+    let processes = remote.process_list(None, ProcessSort::Mem, SortOrder::Desc, usize::MAX).await.unwrap();
+    assert_eq!(processes.len(), 3);
+    assert!(processes.windows(2).all(|w| w[0].memory_usage >= w[1].memory_usage));
+    let processes = remote.process_list(Some("cups"), ProcessSort::Pid, SortOrder::Asc, 10).await.unwrap();
+    assert_eq!(processes.iter().map(|p| p.pid).collect::<Vec<_>>(), [4242]);
+    assert!(!remote.processes_partial());
+    
+    let intruder = RemoteBackend::new(&url, Some("guess".to_string())).unwrap();
+    let error = intruder.memory_snapshot().await.unwrap_err();
+    assert!(error.to_string().contains("rejected the token"));
+    assert!(matches!(intruder.status(), ConnectionStatus::Lost { failures: 1, .. }));
+    
+    // Until the retry delay has passed, calls fail without contacting the agent
+    let error = intruder.memory_snapshot().await.unwrap_err();
+    assert!(error.to_string().starts_with("connection lost"));
+    assert!(matches!(intruder.status(), ConnectionStatus::Lost { failures: 1, .. }));
+}
+
+#[cfg(feature = "remote")]
+#[tokio::test]
+async fn test_agent_alerts_see_processes_beyond_the_limit() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use systrix::alerts::notify::FileSink;
+    use systrix::alerts::{AlertRule, Notifier};
+    use systrix::remote_agent::{RemoteAgent, RemoteConfig};
+    
+    // The fixture has three processes, only one of them is published
+    let agent = RemoteAgent::with_backend(Arc::new(fixture_backend()), RemoteConfig {
+        bind: "127.0.0.1".to_string(),
+        port: 0,
+        token: "secret".to_string(),
+        refresh_interval_ms: 500,
+        process_limit: 1,
+        enable_cors: false,
+    });
+    let dir = tempfile::tempdir().unwrap();
+    let log = dir.path().join("alerts.ndjson");
+    let mut notifier = Notifier::new();
+    notifier.add_sink("file", Arc::new(FileSink::new(log.clone())), Duration::ZERO);
+    
+    let rule = |name: &str, expr: &str| AlertRule { name: Some(name.to_string()), ..AlertRule::new(expr.parse().unwrap()) };
+    agent.watch_alerts(vec![
+        rule("bash gone", r#"process["bash"].missing"#),
+        rule("cupsd running", r#"process["cupsd"].count == 1"#),
+    ], Arc::new(notifier));
+    
+    let mut logged = String::new();
+    for _ in 0..50 {
+        logged = std::fs::read_to_string(&log).unwrap_or_default();
+        if !logged.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    // Both rules are evaluated in the same round, so a false alert would be logged too
+    assert!(logged.contains("cupsd running"), "{}", logged);
+    assert!(!logged.contains("bash gone"), "{}", logged);
+}
+
+#[cfg(feature = "remote")]
+#[tokio::test]
+async fn test_agent_sorts_processes_beyond_the_limit() {
+    use std::sync::Arc;
+    use systrix::remote_agent::{RemoteAgent, RemoteConfig};
+    
+    // Snapshots only carry the busiest process of the three in the fixture
+    let agent = RemoteAgent::with_backend(Arc::new(fixture_backend()), RemoteConfig {
+        bind: "127.0.0.1".to_string(),
+        port: 0,
+        token: "secret".to_string(),
+        refresh_interval_ms: 500,
+        process_limit: 1,
+        enable_cors: false,
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let router = agent.router();
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    
+    let get = |query: &str| {
+        let request = reqwest::Client::new().get(format!("{}/processes?{}", url, query)).bearer_auth("secret");
+        async move { request.send().await.unwrap().json::<serde_json::Value>().await.unwrap() }
+    };
+    let pids = |body: &serde_json::Value| -> Vec<u64> {
+        body["processes"].as_array().unwrap().iter().map(|p| p["pid"].as_u64().unwrap()).collect()
+    };
+    
+    let body = get("sort=pid&order=asc").await;
+    assert_eq!(pids(&body), [1, 4242, 5000]);
+    assert_eq!(body["total"], 3);
+    
+    let body = get("sort=name&limit=2").await;
+    assert_eq!(pids(&body), [5000, 4242]);
+    assert_eq!((body["count"].as_u64(), body["total"].as_u64()), (Some(2), Some(3)));
+}
+
+#[cfg(feature = "remote")]
+#[tokio::test]
+async fn test_agent_follows_a_new_schedule() {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use systrix::remote_agent::{RemoteAgent, RemoteConfig};
+    
+    let agent = RemoteAgent::with_backend(Arc::new(fixture_backend()), RemoteConfig {
+        bind: "127.0.0.1".to_string(),
+        port: 0,
+        token: "secret".to_string(),
+        refresh_interval_ms: 100,
+        process_limit: 1,
+        enable_cors: false,
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/snapshot", listener.local_addr().unwrap());
+    let router = agent.router();
+    tokio::spawn(async move { axum::serve(listener, router).await });
+    
+    let published = || {
+        let request = reqwest::Client::new().get(&url).bearer_auth("secret");
+        async move {
+            let snapshot: serde_json::Value = request.send().await.unwrap().json().await.unwrap();
+            snapshot["processes"].as_array().unwrap().len()
+        }
+    };
+    for limit in [1, 3] {
+        agent.set_schedule(100, limit);
+        let mut count = 0;
+        for _ in 0..50 {
+            count = published().await;
+            if count == limit {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert_eq!(count, limit);
+    }
+}